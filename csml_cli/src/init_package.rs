@@ -129,6 +129,8 @@ pub struct Manifest {
     pub repository: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_locale: Option<String>,
     pub commands: Vec<Vec<String>>,
 }
 
@@ -143,6 +145,7 @@ impl Manifest {
             description: None,
             repository: None,
             license: None,
+            default_locale: None,
             commands: vec![],
         }
     }
@@ -1,6 +1,6 @@
 use csml_engine::data::CsmlRequest;
 use csml_interpreter::{
-    data::{csml_bot::CsmlBot, csml_flow::CsmlFlow, Client},
+    data::{bot_files::is_bot_file, csml_bot::CsmlBot, csml_flow::CsmlFlow, Client},
     load_components,
 };
 
@@ -8,8 +8,10 @@ use crate::init_package::Manifest;
 use std::error::Error;
 
 use serde_json::json;
+use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::prelude::*;
+use std::path::Path;
 
 pub fn init_request(string: &str, metadata: Option<serde_json::Value>) -> CsmlRequest {
     CsmlRequest {
@@ -31,6 +33,7 @@ pub fn init_request(string: &str, metadata: Option<serde_json::Value>) -> CsmlRe
         ttl_duration: None,
         step_limit: None,
         low_data_mode: None,
+        http_mock: None,
     }
 }
 
@@ -54,20 +57,77 @@ pub fn init_request_flow_trigger(flow_id: &str, step_id: Option<&str>) -> CsmlRe
         ttl_duration: None,
         step_limit: None,
         low_data_mode: None,
+        http_mock: None,
     }
 }
 
+/**
+ * Translation catalogs are stored as locales/<locale>.yaml,
+ * a bot without a locales directory has no translations.
+ */
+fn load_locales(
+    directory_name: &str,
+) -> Result<Option<HashMap<String, serde_json::Value>>, Box<dyn Error>> {
+    let paths = match fs::read_dir(format!("{}/locales", directory_name)) {
+        Ok(paths) => paths,
+        Err(_) => return Ok(None),
+    };
+
+    let mut locales = HashMap::new();
+
+    for path in paths.flatten() {
+        let path = path.path();
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("yaml") | Some("yml") => {}
+            _ => continue,
+        }
+
+        let locale = match path.file_stem().and_then(|name| name.to_str()) {
+            Some(locale) => locale.to_owned(),
+            None => continue,
+        };
+
+        let file = File::open(&path)?;
+        let catalog: serde_json::Value = serde_yaml::from_reader(file)?;
+
+        locales.insert(locale, catalog);
+    }
+
+    Ok(Some(locales))
+}
+
+/**
+ * Static data files (json, yaml, csv) can be stored next to the flows in src/ or in data/
+ */
+fn load_file(path: &Path, files: &mut HashMap<String, String>) -> Result<(), Box<dyn Error>> {
+    let name = match path.file_name().and_then(|name| name.to_str()) {
+        Some(name) => name.to_owned(),
+        None => return Ok(()),
+    };
+
+    files.insert(name, fs::read_to_string(path)?);
+
+    Ok(())
+}
+
 pub fn load_info(directory_name: &str) -> Result<CsmlBot, Box<dyn Error>> {
     let file = File::open(&format!("{}/manifest.yaml", directory_name))?;
 
     let manifest: Manifest = serde_yaml::from_reader(file)?;
 
     let mut flows = vec![];
+    let mut files = HashMap::new();
 
     let paths = fs::read_dir(format!("{}/src", directory_name))?;
 
     for path in paths {
         if let Ok(dir) = path {
+            if is_bot_file(&dir.path().display().to_string()) {
+                load_file(&dir.path(), &mut files)?;
+                continue;
+            }
+
             let mut file = File::open(dir.path())?;
 
             let mut contents = String::new();
@@ -86,10 +146,19 @@ pub fn load_info(directory_name: &str) -> Result<CsmlBot, Box<dyn Error>> {
                 name: file_name[0].to_owned(),
                 content: contents,
                 commands: vec![], // link commands
+                triggers: vec![],
             });
         }
     }
 
+    if let Ok(paths) = fs::read_dir(format!("{}/data", directory_name)) {
+        for path in paths.flatten() {
+            if path.path().is_file() {
+                load_file(&path.path(), &mut files)?;
+            }
+        }
+    }
+
     Ok(CsmlBot {
         id: manifest.name.clone(),
         name: manifest.name.clone(),
@@ -103,6 +172,17 @@ pub fn load_info(directory_name: &str) -> Result<CsmlBot, Box<dyn Error>> {
         env: None,
         modules: None,
         multibot: None,
+        knowledge: None,
+        knowledge_index: None,
+        locales: load_locales(directory_name)?,
+        default_locale: manifest.default_locale.clone(),
+        files: match files.is_empty() {
+            true => None,
+            false => Some(files),
+        },
+        files_cache: None,
+        network_policy: None,
+        nlu: None,
     })
 }
 
@@ -37,6 +37,7 @@ fn init_request(string: &str) -> CsmlRequest {
         ttl_duration: None,
         step_limit: None,
         low_data_mode: None,
+        http_mock: None,
     }
 }
 
@@ -51,12 +52,14 @@ fn init_bot() -> CsmlBot {
                 name: "flow".to_owned(),
                 content: get_flow("flow").expect("error in reading flow"),
                 commands: vec!["/plop".to_owned()],
+                triggers: vec![],
             },
             CsmlFlow {
                 id: "2".to_owned(),
                 name: "flow2".to_owned(),
                 content: get_flow("flow2").expect("error in reading flow"),
                 commands: vec!["/random".to_owned()],
+                triggers: vec![],
             },
         ],
         native_components: Some(load_components().unwrap()),
@@ -67,6 +70,14 @@ fn init_bot() -> CsmlBot {
         env: None,
         modules: None,
         multibot: None,
+        knowledge: None,
+        knowledge_index: None,
+        locales: None,
+        default_locale: None,
+        files: None,
+        files_cache: None,
+        network_policy: None,
+        nlu: None,
     }
 }
 
@@ -26,12 +26,14 @@ fn init_bot() -> CsmlBot {
                 name: "flow".to_owned(),
                 content: get_flow("flow").expect("error in reading flow"),
                 commands: vec!["/plop".to_owned()],
+                triggers: vec![],
             },
             CsmlFlow {
                 id: "2".to_owned(),
                 name: "flow2".to_owned(),
                 content: get_flow("flow2").expect("error in reading flow"),
                 commands: vec!["/random".to_owned()],
+                triggers: vec![],
             },
         ],
         native_components: Some(load_components().unwrap()),
@@ -42,6 +44,14 @@ fn init_bot() -> CsmlBot {
         env: None,
         modules: None,
         multibot: None,
+        knowledge: None,
+        knowledge_index: None,
+        locales: None,
+        default_locale: None,
+        files: None,
+        files_cache: None,
+        network_policy: None,
+        nlu: None,
     }
 }
 
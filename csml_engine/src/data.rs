@@ -3,9 +3,12 @@ use crate::{
     encrypt::{decrypt_data, encrypt_data},
     Client, Context,
 };
-use csml_interpreter::data::{CsmlBot, CsmlFlow, Message, Module, MultiBot};
+use csml_interpreter::data::{
+    CsmlBot, CsmlFlow, HttpMock, Knowledge, Message, Module, MultiBot, NetworkPolicy, NluConfig,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 
 pub const DEBUG: &str = "DEBUG";
 pub const DISABLE_SSL_VERIFY: &str = "DISABLE_SSL_VERIFY";
@@ -24,6 +27,8 @@ pub struct RunRequest {
     #[serde(alias = "fn_endpoint")]
     pub apps_endpoint: Option<String>,
     pub multibot: Option<Vec<MultiBot>>,
+    // like the apps_endpoint, the policy is not saved with the versions of the bot
+    pub network_policy: Option<NetworkPolicy>,
     pub event: CsmlRequest,
 }
 
@@ -34,9 +39,13 @@ impl RunRequest {
             RunRequest {
                 bot: Some(mut csml_bot),
                 multibot,
+                network_policy,
                 ..
             } => {
                 csml_bot.multibot = multibot;
+                if network_policy.is_some() {
+                    csml_bot.network_policy = network_policy;
+                }
 
                 Ok(BotOpt::CsmlBot(csml_bot))
             }
@@ -47,12 +56,14 @@ impl RunRequest {
                 bot_id: Some(bot_id),
                 apps_endpoint,
                 multibot,
+                network_policy,
                 ..
             } => Ok(BotOpt::Id {
                 version_id,
                 bot_id,
                 apps_endpoint,
                 multibot,
+                network_policy,
             }),
 
             // get bot by id will search for the last version id
@@ -60,11 +71,13 @@ impl RunRequest {
                 bot_id: Some(bot_id),
                 apps_endpoint,
                 multibot,
+                network_policy,
                 ..
             } => Ok(BotOpt::BotId {
                 bot_id,
                 apps_endpoint,
                 multibot,
+                network_policy,
             }),
 
             _ => Err(EngineError::Format("Invalid bot_opt format".to_owned())),
@@ -83,6 +96,7 @@ pub enum BotOpt {
         #[serde(alias = "fn_endpoint")]
         apps_endpoint: Option<String>,
         multibot: Option<Vec<MultiBot>>,
+        network_policy: Option<NetworkPolicy>,
     },
     #[serde(rename = "bot_id")]
     BotId {
@@ -90,6 +104,7 @@ pub enum BotOpt {
         #[serde(alias = "fn_endpoint")]
         apps_endpoint: Option<String>,
         multibot: Option<Vec<MultiBot>>,
+        network_policy: Option<NetworkPolicy>,
     },
 }
 
@@ -101,6 +116,7 @@ impl BotOpt {
                 bot_id,
                 apps_endpoint,
                 multibot,
+                network_policy,
             } => {
                 let bot_version = db_connectors::bot::get_last_bot_version(&bot_id, db)?;
 
@@ -108,6 +124,7 @@ impl BotOpt {
                     Some(mut bot_version) => {
                         bot_version.bot.apps_endpoint = apps_endpoint.to_owned();
                         bot_version.bot.multibot = multibot.to_owned();
+                        bot_version.bot.network_policy = network_policy.to_owned();
                         Ok(bot_version.bot)
                     }
                     None => Err(EngineError::Manager(format!(
@@ -121,6 +138,7 @@ impl BotOpt {
                 bot_id,
                 apps_endpoint,
                 multibot,
+                network_policy,
             } => {
                 let bot_version = db_connectors::bot::get_by_version_id(&version_id, &bot_id, db)?;
 
@@ -128,6 +146,7 @@ impl BotOpt {
                     Some(mut bot_version) => {
                         bot_version.bot.apps_endpoint = apps_endpoint.to_owned();
                         bot_version.bot.multibot = multibot.to_owned();
+                        bot_version.bot.network_policy = network_policy.to_owned();
                         Ok(bot_version.bot)
                     }
                     None => Err(EngineError::Manager(format!(
@@ -151,6 +170,13 @@ pub struct SerializeCsmlBot {
     pub no_interruption_delay: Option<i32>,
     pub env: Option<String>,
     pub modules: Option<Vec<Module>>,
+    pub knowledge: Option<Knowledge>,
+    pub knowledge_index: Option<String>,
+    pub locales: Option<HashMap<String, Value>>,
+    pub default_locale: Option<String>,
+    pub files: Option<HashMap<String, String>>,
+    pub files_cache: Option<String>,
+    pub nlu: Option<String>,
 }
 
 /**
@@ -164,28 +190,69 @@ pub struct SerializeCsmlBot {
 pub struct CsmlBotBincode {
     pub id: String,
     pub name: String,
-    pub flows: Vec<CsmlFlow>,
+    pub flows: Vec<CsmlFlowBincode>,
     pub native_components: Option<String>, // serde_json::Map<String, serde_json::Value>
     pub custom_components: Option<String>, // serde_json::Value
     pub default_flow: String,
 }
 
+/**
+ * Flows of bincode-encoded bots predate flow triggers,
+ * so their layout must be kept as is to be decoded.
+ * TO BE REMOVED in CSML v2
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsmlFlowBincode {
+    pub id: String,
+    pub name: String,
+    pub content: String,
+    pub commands: Vec<String>,
+}
+
+impl CsmlFlowBincode {
+    pub fn to_flow(self) -> CsmlFlow {
+        CsmlFlow::new(&self.id, &self.name, &self.content, self.commands)
+    }
+}
+
 impl CsmlBotBincode {
     pub fn to_bot(self) -> SerializeCsmlBot {
         SerializeCsmlBot {
             id: self.id,
             name: self.name,
-            flows: self.flows,
+            flows: self.flows.into_iter().map(|flow| flow.to_flow()).collect(),
             native_components: self.native_components,
             custom_components: self.custom_components,
             default_flow: self.default_flow,
             no_interruption_delay: None,
             env: None,
             modules: None,
+            knowledge: None,
+            knowledge_index: None,
+            locales: None,
+            default_locale: None,
+            files: None,
+            files_cache: None,
+            nlu: None,
         }
     }
 }
 
+/**
+ * The NLU config has the token of the provider, it is encrypted like the bot env
+ */
+fn encrypt_nlu(nlu: &Option<NluConfig>) -> Option<String> {
+    let value = serde_json::to_value(nlu.as_ref()?).ok()?;
+
+    encrypt_data(&value).ok()
+}
+
+fn decrypt_nlu(nlu: &Option<String>) -> Option<NluConfig> {
+    let value = decrypt_data(nlu.to_owned()?).ok()?;
+
+    serde_json::from_value(value).ok()
+}
+
 pub fn to_serializable_bot(bot: &CsmlBot) -> SerializeCsmlBot {
     SerializeCsmlBot {
         id: bot.id.to_owned(),
@@ -210,6 +277,13 @@ pub fn to_serializable_bot(bot: &CsmlBot) -> SerializeCsmlBot {
             None => None,
         },
         modules: bot.modules.to_owned(),
+        knowledge: bot.knowledge.to_owned(),
+        knowledge_index: bot.knowledge_index.to_owned(),
+        locales: bot.locales.to_owned(),
+        default_locale: bot.default_locale.to_owned(),
+        files: bot.files.to_owned(),
+        files_cache: bot.files_cache.to_owned(),
+        nlu: encrypt_nlu(&bot.nlu),
     }
 }
 
@@ -247,6 +321,14 @@ impl SerializeCsmlBot {
             },
             modules: self.modules.to_owned(),
             multibot: None,
+            knowledge: self.knowledge.to_owned(),
+            knowledge_index: self.knowledge_index.to_owned(),
+            locales: self.locales.to_owned(),
+            default_locale: self.default_locale.to_owned(),
+            files: self.files.to_owned(),
+            files_cache: self.files_cache.to_owned(),
+            network_policy: None,
+            nlu: decrypt_nlu(&self.nlu),
         }
     }
 }
@@ -259,6 +341,13 @@ pub struct DynamoBot {
     pub default_flow: String,
     pub no_interruption_delay: Option<i32>,
     pub env: Option<String>,
+    // the knowledge is stored in S3 with the bot files, only the versions
+    // created before have it in the item
+    pub knowledge: Option<Knowledge>,
+    pub knowledge_index: Option<String>,
+    pub locales: Option<HashMap<String, Value>>,
+    pub default_locale: Option<String>,
+    pub nlu: Option<String>,
 }
 
 /**
@@ -285,10 +374,36 @@ impl DynamoBotBincode {
             default_flow: self.default_flow,
             no_interruption_delay: None,
             env: None,
+            knowledge: None,
+            knowledge_index: None,
+            locales: None,
+            default_locale: None,
+            nlu: None,
         }
     }
 }
 
+/**
+ * Bot files and knowledge are stored in S3 next to the flows of the bot version,
+ * as they can exceed the size limit of a dynamodb item.
+ */
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DynamoBotFiles {
+    pub files: Option<HashMap<String, String>>,
+    pub files_cache: Option<String>,
+    pub knowledge: Option<Knowledge>,
+    pub knowledge_index: Option<String>,
+}
+
+pub fn to_dynamo_bot_files(csml_bot: &CsmlBot) -> DynamoBotFiles {
+    DynamoBotFiles {
+        files: csml_bot.files.to_owned(),
+        files_cache: csml_bot.files_cache.to_owned(),
+        knowledge: csml_bot.knowledge.to_owned(),
+        knowledge_index: csml_bot.knowledge_index.to_owned(),
+    }
+}
+
 pub fn to_dynamo_bot(csml_bot: &CsmlBot) -> DynamoBot {
     DynamoBot {
         id: csml_bot.id.to_owned(),
@@ -303,11 +418,21 @@ pub fn to_dynamo_bot(csml_bot: &CsmlBot) -> DynamoBot {
             Some(value) => encrypt_data(value).ok(),
             None => None,
         },
+        knowledge: None,
+        knowledge_index: None,
+        locales: csml_bot.locales.to_owned(),
+        default_locale: csml_bot.default_locale.to_owned(),
+        nlu: encrypt_nlu(&csml_bot.nlu),
     }
 }
 
 impl DynamoBot {
-    pub fn to_bot(&self, flows: Vec<CsmlFlow>, modules: Vec<Module>) -> CsmlBot {
+    pub fn to_bot(
+        &self,
+        flows: Vec<CsmlFlow>,
+        modules: Vec<Module>,
+        files: DynamoBotFiles,
+    ) -> CsmlBot {
         CsmlBot {
             id: self.id.to_owned(),
             name: self.name.to_owned(),
@@ -332,6 +457,16 @@ impl DynamoBot {
             },
             modules: Some(modules),
             multibot: None,
+            knowledge: files.knowledge.or_else(|| self.knowledge.to_owned()),
+            knowledge_index: files
+                .knowledge_index
+                .or_else(|| self.knowledge_index.to_owned()),
+            locales: self.locales.to_owned(),
+            default_locale: self.default_locale.to_owned(),
+            files: files.files,
+            files_cache: files.files_cache,
+            network_policy: None,
+            nlu: decrypt_nlu(&self.nlu),
         }
     }
 }
@@ -346,6 +481,7 @@ pub struct CsmlRequest {
     pub step_limit: Option<usize>,
     pub ttl_duration: Option<serde_json::Value>,
     pub low_data_mode: Option<serde_json::Value>,
+    pub http_mock: Option<HttpMock>,
 }
 
 pub enum Database {
@@ -62,6 +62,8 @@ pub fn create_bot_version(
             }
         };
 
+        let files = serde_json::json!(crate::data::to_dynamo_bot_files(&csml_bot));
+
         let bot = serde_json::json!(dynamo_bot).to_string();
 
         let version_id = dynamodb_connector::bot::create_bot_version(
@@ -69,6 +71,7 @@ pub fn create_bot_version(
             bot,
             flows.to_string(),
             flow_modules.to_string(),
+            files.to_string(),
             db,
         )?;
 
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use csml_interpreter::data::{context::ContextStepInfo, CsmlFlow, Message};
+    use csml_interpreter::interpreter::builtins::oauth2::OAuth2Clients;
     use std::collections::HashMap;
 
     use crate::{db_connectors::*, init_db, make_migrations, Client, Context, ConversationInfo};
@@ -22,6 +23,7 @@ mod tests {
             step: ContextStepInfo::Normal("start".to_owned()),
             flow: "Default".to_owned(),
             previous_bot: None,
+            oauth2_clients: OAuth2Clients::default(),
         }
     }
 
@@ -35,6 +37,7 @@ mod tests {
                 name: "Default".to_owned(),
                 content: "start: say \"hello\"".to_owned(),
                 commands: vec![],
+                triggers: vec![],
             }],
             native_components: None,
             custom_components: None,
@@ -44,6 +47,14 @@ mod tests {
             env: None,
             modules: None,
             multibot: None,
+            knowledge: None,
+            knowledge_index: None,
+            locales: None,
+            default_locale: None,
+            files: None,
+            files_cache: None,
+            network_policy: None,
+            nlu: None,
         }
     }
 
@@ -1,4 +1,4 @@
-use crate::data::{DynamoBot, DynamoBotBincode, DynamoDbClient};
+use crate::data::{DynamoBot, DynamoBotBincode, DynamoBotFiles, DynamoDbClient};
 use crate::db_connectors::dynamodb::utils::*;
 use crate::db_connectors::{
     dynamodb::{aws_s3, Bot, BotKeys, Class, DynamoDbKey},
@@ -14,6 +14,7 @@ pub fn create_bot_version(
     bot: String,
     flows: String,
     flow_modules: String,
+    files: String,
     db: &mut DynamoDbClient,
 ) -> Result<String, EngineError> {
     let data: Bot = Bot::new(bot_id, bot);
@@ -37,6 +38,9 @@ pub fn create_bot_version(
     );
     aws_s3::put_object(db, &key, flow_modules)?;
 
+    let key = format!("bots/{}/versions/{}/files.json", &data.id, &data.version_id);
+    aws_s3::put_object(db, &key, files)?;
+
     Ok(data.version_id.to_owned())
 }
 
@@ -64,6 +68,20 @@ pub fn get_modules(key: &str, db: &mut DynamoDbClient) -> Result<Vec<Module>, En
     Ok(modules)
 }
 
+pub fn get_bot_files(key: &str, db: &mut DynamoDbClient) -> Result<DynamoBotFiles, EngineError> {
+    let object = match aws_s3::get_object(db, key) {
+        Ok(obj) => obj,
+        Err(_) => return Ok(DynamoBotFiles::default()), // bot versions created before bot files
+    };
+
+    let files: DynamoBotFiles = match serde_json::from_str(&object) {
+        Ok(files) => files,
+        Err(_) => DynamoBotFiles::default(),
+    };
+
+    Ok(files)
+}
+
 fn query_bot_version(
     bot_id: &str,
     limit: i64,
@@ -228,8 +246,11 @@ pub fn get_bot_by_version_id(
             let key = format!("bots/{}/versions/{}/modules.json", bot_id, version_id);
             let modules = get_modules(&key, db)?;
 
+            let key = format!("bots/{}/versions/{}/files.json", bot_id, version_id);
+            let files = get_bot_files(&key, db)?;
+
             Ok(Some(BotVersion {
-                bot: csml_bot.to_bot(flows, modules),
+                bot: csml_bot.to_bot(flows, modules, files),
                 version_id: bot.version_id,
                 engine_version: env!("CARGO_PKG_VERSION").to_owned(),
             }))
@@ -342,8 +363,11 @@ pub fn get_last_bot_version(
             let key = format!("bots/{}/versions/{}/modules.json", bot_id, bot.version_id);
             let modules = get_modules(&key, db)?;
 
+            let key = format!("bots/{}/versions/{}/files.json", bot_id, bot.version_id);
+            let files = get_bot_files(&key, db)?;
+
             Ok(Some(BotVersion {
-                bot: csml_bot.to_bot(flows, modules),
+                bot: csml_bot.to_bot(flows, modules, files),
                 version_id: bot.version_id,
                 engine_version: env!("CARGO_PKG_VERSION").to_owned(),
             }))
@@ -363,6 +387,9 @@ pub fn delete_bot_version(
     let key = format!("bots/{}/versions/{}/modules.json", bot_id, version_id);
     aws_s3::delete_object(db, &key)?;
 
+    let key = format!("bots/{}/versions/{}/files.json", bot_id, version_id);
+    aws_s3::delete_object(db, &key)?;
+
     let item_key = DynamoDbKey {
         hash: Bot::get_hash(bot_id),
         range: Bot::get_range(version_id),
@@ -408,6 +435,9 @@ pub fn delete_bot_versions(bot_id: &str, db: &mut DynamoDbClient) -> Result<(),
             let key = format!("bots/{}/versions/{}/modules.json", bot_id, version_id);
             aws_s3::delete_object(db, &key)?;
 
+            let key = format!("bots/{}/versions/{}/files.json", bot_id, version_id);
+            aws_s3::delete_object(db, &key)?;
+
             let key = serde_dynamodb::to_hashmap(&DynamoDbKey {
                 hash: data.hash,
                 range: data.range,
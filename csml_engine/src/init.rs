@@ -4,7 +4,7 @@ use crate::{
     data::{ConversationInfo, CsmlRequest, Database, EngineError},
     utils::{
         get_default_flow, get_flow_by_id, get_low_data_mode_value, get_ttl_duration_value,
-        search_flow, send_msg_to_callback_url,
+        init_oauth2_clients, search_flow, send_msg_to_callback_url,
     },
     BotOpt, Context, CsmlBot, CsmlFlow, CsmlResult,
 };
@@ -14,7 +14,7 @@ use csml_interpreter::{
     data::{
         ast::Flow,
         context::{get_hashmap_from_json, get_hashmap_from_mem},
-        ApiInfo, Client, Event, Message, PreviousBot,
+        ApiInfo, BotFiles, Client, Event, KnowledgeIndex, Message, PreviousBot,
     },
     load_components, search_for_modules, validate_bot,
 };
@@ -37,7 +37,7 @@ use std::collections::HashMap;
  */
 pub fn init_conversation_info<'a>(
     default_flow: String,
-    event: &Event,
+    event: &mut Event,
     request: &'a CsmlRequest,
     bot: &'a CsmlBot,
     mut db: Database,
@@ -51,13 +51,19 @@ pub fn init_conversation_info<'a>(
         &bot.apps_endpoint,
         &mut db,
     );
-    let ttl = get_ttl_duration_value(Some(event));
+    let ttl = get_ttl_duration_value(Some(&*event));
     let low_data = get_low_data_mode_value(event);
 
     // Do we have a flow matching the request? If the user is requesting a flow in one way
     // or another, this takes precedence over any previously open conversation
     // and a new conversation is created with the new flow as a starting point.
-    let flow_found = search_flow(event, &bot, &request.client, &mut db).ok();
+    let flow_found = match search_flow(event, &bot, &request.client, &request.metadata, &mut db) {
+        Ok((flow, step, trigger)) => {
+            event.trigger = trigger;
+            Some((flow, step))
+        }
+        Err(_) => None,
+    };
     let conversation_id = get_or_create_conversation(
         &mut context,
         &bot,
@@ -152,6 +158,37 @@ fn set_bot_ast(bot: &mut CsmlBot) -> Result<(), EngineError> {
         _ => return Err(EngineError::Interpreter(format!("empty bot"))),
     }
 
+    set_knowledge_index(bot)?;
+    set_files_cache(bot)
+}
+
+/**
+ * Build the knowledge index of the bot if it has a knowledge base.
+ * Bot versions are stored with their index so it is only built once per version.
+ */
+pub fn set_knowledge_index(bot: &mut CsmlBot) -> Result<(), EngineError> {
+    if let (None, Some(knowledge)) = (&bot.knowledge_index, &bot.knowledge) {
+        match KnowledgeIndex::from_knowledge(knowledge) {
+            Ok(index) => bot.knowledge_index = Some(index.encode()),
+            Err(err) => return Err(EngineError::Interpreter(err)),
+        }
+    }
+
+    Ok(())
+}
+
+/**
+ * Parse the static data files of the bot if it has any.
+ * Bot versions are stored with their parsed files so they are only parsed once per version.
+ */
+pub fn set_files_cache(bot: &mut CsmlBot) -> Result<(), EngineError> {
+    if let (None, Some(files)) = (&bot.files_cache, &bot.files) {
+        match BotFiles::from_files(files) {
+            Ok(files) => bot.files_cache = Some(files.encode()),
+            Err(err) => return Err(EngineError::Interpreter(err)),
+        }
+    }
+
     Ok(())
 }
 
@@ -165,6 +202,7 @@ pub fn init_context(
     db: &mut Database,
 ) -> Context {
     let previous_bot = get_previous_bot(&client, db);
+    let oauth2_clients = init_oauth2_clients(&client);
 
     let api_info = match apps_endpoint {
         Some(value) => Some(ApiInfo {
@@ -182,6 +220,7 @@ pub fn init_context(
         step: ContextStepInfo::Normal("start".to_owned()),
         flow,
         previous_bot,
+        oauth2_clients,
     }
 }
 
@@ -275,11 +314,13 @@ pub fn switch_bot(
             bot_id: next_bot.bot_id,
             apps_endpoint: bot.apps_endpoint.take(),
             multibot: bot.multibot.take(),
+            network_policy: bot.network_policy.take(),
         },
         None => BotOpt::BotId {
             bot_id: next_bot.bot_id,
             apps_endpoint: bot.apps_endpoint.take(),
             multibot: bot.multibot.take(),
+            network_policy: bot.network_policy.take(),
         },
     };
 
@@ -331,6 +372,7 @@ pub fn switch_bot(
 
     // update event to flow trigger
     event.content_type = "flow_trigger".to_owned();
+    event.trigger = None;
     event.content = serde_json::json!({
             "flow_id": flow.id,
             "step_id": step
@@ -244,6 +244,7 @@ pub fn interpret_step(
     }
 
     add_memories(data, &memories)?;
+    save_oauth2_clients(data)?;
 
     Ok((
         messages_formatter(
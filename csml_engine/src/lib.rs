@@ -5,6 +5,7 @@ mod encrypt;
 mod error_messages;
 mod init;
 mod interpreter_actions;
+mod nlu;
 mod send;
 mod utils;
 
@@ -69,9 +70,14 @@ pub fn start_conversation(
     let mut bot = bot_opt.search_bot(&mut db)?;
     init_bot(&mut bot)?;
 
+    // only the bots with an NLU provider wait for its annotations
+    if let Some(nlu) = &bot.nlu {
+        nlu::annotate_event(nlu, &mut formatted_event);
+    }
+
     let mut data = init_conversation_info(
         get_default_flow(&bot)?.name.to_owned(),
-        &formatted_event,
+        &mut formatted_event,
         &request,
         &bot,
         db,
@@ -277,6 +283,12 @@ pub fn create_bot_version(mut csml_bot: CsmlBot) -> Result<BotVersionCreated, En
             ..
         } => Err(EngineError::Interpreter(format!("{:?}", errors))),
         CsmlResult { .. } => {
+            // the knowledge index and the parsed files are stored with the bot version
+            csml_bot.knowledge_index = None;
+            set_knowledge_index(&mut csml_bot)?;
+            csml_bot.files_cache = None;
+            set_files_cache(&mut csml_bot)?;
+
             let version_id = bot::create_bot_version(bot_id, csml_bot, &mut db)?;
             let engine_version = env!("CARGO_PKG_VERSION").to_owned();
 
@@ -0,0 +1,253 @@
+use crate::data::EngineError;
+
+use csml_interpreter::data::{
+    csml_logs::*, Event, Intent, NluConfig, NluEntity, NluProvider, NluResult,
+};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+// maximum time to wait for the NLU provider before going on without annotations
+const NLU_TIMEOUT: u64 = 5000;
+
+fn gen_body(config: &NluConfig, text: &str) -> Value {
+    match config.provider {
+        NluProvider::Rasa => json!({ "text": text }),
+        NluProvider::Dialogflow => json!({
+            "queryInput": {
+                "text": {
+                    "text": text,
+                    "languageCode": config.language,
+                }
+            }
+        }),
+    }
+}
+
+fn parse_response(config: &NluConfig, response: &Value) -> NluResult {
+    match config.provider {
+        NluProvider::Rasa => parse_rasa(response),
+        NluProvider::Dialogflow => parse_dialogflow(response),
+    }
+}
+
+fn parse_intent(intent: &Value) -> Option<Intent> {
+    Some(Intent {
+        name: intent["name"].as_str()?.to_owned(),
+        confidence: intent["confidence"].as_f64().unwrap_or(1.0),
+    })
+}
+
+/**
+ * Rasa /model/parse response:
+ * { "intent": {"name", "confidence"}, "intent_ranking": [...], "entities": [{"entity", "value", "start", "end", "confidence_entity"}] }
+ */
+fn parse_rasa(response: &Value) -> NluResult {
+    let intent = parse_intent(&response["intent"]);
+
+    let intents = match response["intent_ranking"].as_array() {
+        Some(ranking) => ranking.iter().filter_map(parse_intent).collect(),
+        None => intent.iter().cloned().collect(),
+    };
+
+    let entities = match response["entities"].as_array() {
+        Some(entities) => entities
+            .iter()
+            .filter_map(|entity| {
+                Some(NluEntity {
+                    entity: entity["entity"].as_str()?.to_owned(),
+                    value: entity["value"].to_owned(),
+                    confidence: entity["confidence_entity"].as_f64(),
+                    start: entity["start"].as_u64().map(|start| start as usize),
+                    end: entity["end"].as_u64().map(|end| end as usize),
+                })
+            })
+            .collect(),
+        None => vec![],
+    };
+
+    NluResult {
+        intent,
+        intents,
+        entities,
+    }
+}
+
+/**
+ * Dialogflow detectIntent response:
+ * { "queryResult": { "intent": {"displayName"}, "intentDetectionConfidence", "parameters": {...} } }
+ */
+fn parse_dialogflow(response: &Value) -> NluResult {
+    let query_result = &response["queryResult"];
+
+    let intent = query_result["intent"]["displayName"]
+        .as_str()
+        .map(|name| Intent {
+            name: name.to_owned(),
+            confidence: query_result["intentDetectionConfidence"]
+                .as_f64()
+                .unwrap_or(1.0),
+        });
+
+    // unfilled parameters are returned as empty strings
+    let entities = match query_result["parameters"].as_object() {
+        Some(parameters) => parameters
+            .iter()
+            .filter(|(_, value)| !value.is_null() && *value != &json!(""))
+            .map(|(name, value)| NluEntity {
+                entity: name.to_owned(),
+                value: value.to_owned(),
+                confidence: None,
+                start: None,
+                end: None,
+            })
+            .collect(),
+        None => vec![],
+    };
+
+    NluResult {
+        intents: intent.iter().cloned().collect(),
+        intent,
+        entities,
+    }
+}
+
+fn call_provider(config: &NluConfig, text: &str) -> Result<NluResult, EngineError> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(Duration::from_millis(NLU_TIMEOUT))
+        .build();
+
+    let mut request = agent
+        .post(&config.url)
+        .set("Accept", "application/json")
+        .set("Content-Type", "application/json");
+
+    if let Some(token) = &config.token {
+        request = request.set("Authorization", &format!("Bearer {}", token));
+    }
+
+    let response: Value = match request.send_json(gen_body(config, text)) {
+        Ok(response) => response.into_json()?,
+        Err(err) => {
+            return Err(EngineError::Manager(format!(
+                "NLU provider call failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(parse_response(config, &response))
+}
+
+/**
+ * Annotate a text event with the intents and entities detected by the NLU provider of the bot.
+ * A failing provider must not break the conversation: the event is simply left as is.
+ */
+pub fn annotate_event(config: &NluConfig, event: &mut Event) {
+    if event.content_type != "text" {
+        return;
+    }
+
+    match call_provider(config, &event.content_value) {
+        Ok(nlu) => event.nlu = Some(nlu),
+        Err(err) => csml_logger(
+            CsmlLog::new(None, None, None, format!("{:?}", err)),
+            LogLvl::Error,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    fn init_stub(body: Value) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/model/parse", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buffer = [0; 4096];
+            let _request = stream.read(&mut buffer).unwrap();
+
+            let body = body.to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+
+        url
+    }
+
+    fn init_config(provider: NluProvider, url: &str) -> NluConfig {
+        NluConfig {
+            provider,
+            url: url.to_owned(),
+            token: None,
+            language: "en".to_owned(),
+        }
+    }
+
+    #[test]
+    fn ok_nlu_rasa() {
+        let url = init_stub(json!({
+            "text": "I want a pizza",
+            "intent": {"name": "order_pizza", "confidence": 0.92},
+            "intent_ranking": [
+                {"name": "order_pizza", "confidence": 0.92},
+                {"name": "greet", "confidence": 0.08}
+            ],
+            "entities": [
+                {"entity": "food", "value": "pizza", "start": 9, "end": 14, "confidence_entity": 0.99}
+            ]
+        }));
+        let mut event = Event::new("text", "I want a pizza", json!({"text": "I want a pizza"}));
+
+        annotate_event(&init_config(NluProvider::Rasa, &url), &mut event);
+
+        let intent = event.get_intent().unwrap();
+        assert_eq!(intent.name, "order_pizza");
+        assert_eq!(intent.confidence, 0.92);
+
+        let nlu = event.nlu.unwrap();
+        assert_eq!(nlu.intents.len(), 2);
+        assert_eq!(nlu.entities[0].entity, "food");
+        assert_eq!(nlu.entities[0].value, json!("pizza"));
+        assert_eq!(nlu.entities[0].start, Some(9));
+    }
+
+    #[test]
+    fn ok_nlu_dialogflow() {
+        let url = init_stub(json!({
+            "queryResult": {
+                "intent": {"displayName": "order_pizza"},
+                "intentDetectionConfidence": 0.8,
+                "parameters": {"food": "pizza", "size": ""}
+            }
+        }));
+        let mut event = Event::new("text", "I want a pizza", json!({"text": "I want a pizza"}));
+
+        annotate_event(&init_config(NluProvider::Dialogflow, &url), &mut event);
+
+        let nlu = event.nlu.unwrap();
+        assert_eq!(nlu.intent.unwrap().name, "order_pizza");
+        assert_eq!(nlu.entities.len(), 1);
+        assert_eq!(nlu.entities[0].value, json!("pizza"));
+    }
+
+    #[test]
+    fn ok_nlu_unreachable_provider() {
+        let mut event = Event::new("text", "hello", json!({"text": "hello"}));
+
+        annotate_event(
+            &init_config(NluProvider::Rasa, "http://127.0.0.1:1/model/parse"),
+            &mut event,
+        );
+
+        assert!(event.nlu.is_none());
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
     data::{ConversationInfo, CsmlRequest, Database, EngineError, FlowTrigger},
-    db_connectors::state::delete_state_key,
+    db_connectors::{
+        init_db,
+        state::{delete_state_key, get_state_key, set_state_items},
+    },
     send::send_to_callback_url,
     CsmlBot, CsmlFlow,
 };
@@ -11,11 +14,11 @@ use csml_interpreter::{
         ast::{Flow, InsertStep, InstructionScope},
         context::ContextStepInfo,
         csml_logs::*,
-        Client, Context, Event, Interval, Memory, Message,
+        Client, Context, Event, Interval, Memory, Message, TriggerMatch,
     },
     error_format::{ERROR_KEY_ALPHANUMERIC, ERROR_NUMBER_AS_KEY, ERROR_SIZE_IDENT},
     get_step,
-    interpreter::json_to_literal,
+    interpreter::{builtins::oauth2::OAuth2Clients, json_to_literal},
 };
 use rand::seq::SliceRandom;
 use serde_json::{json, map::Map, Value};
@@ -145,7 +148,14 @@ pub fn format_event(request: &CsmlRequest) -> Result<Event, EngineError> {
         ttl_duration: json_event["ttl_duration"].as_i64(),
         low_data_mode: json_event["low_data_mode"].as_bool(),
         step_limit,
+        // mocks of the requests can only use the fixtures of the mock directory of the server
+        http_mock: request
+            .http_mock
+            .as_ref()
+            .and_then(|mock| mock.in_mock_directory()),
         secure: json_event["payload"]["secure"].as_bool().unwrap_or(false),
+        trigger: None,
+        nlu: None,
     })
 }
 
@@ -263,17 +273,45 @@ pub fn get_default_flow<'a>(bot: &'a CsmlBot) -> Result<&'a CsmlFlow, EngineErro
     }
 }
 
+/**
+ * Among all the flows matching the user's input, keep the ones with the best trigger:
+ * - triggers with the highest priority win
+ * - then triggers with the highest weighted score
+ * - remaining ties are broken at random
+ */
+fn select_flow<'a>(
+    mut matches: Vec<(&'a CsmlFlow, TriggerMatch)>,
+) -> Option<(&'a CsmlFlow, TriggerMatch)> {
+    let best = matches
+        .iter()
+        .fold(
+            None,
+            |best: Option<&TriggerMatch>, (_, trigger)| match best {
+                Some(best) if !trigger.is_better_than(best) => Some(best),
+                _ => Some(trigger),
+            },
+        )?
+        .to_owned();
+
+    matches.retain(|(_, trigger)| trigger.is_equivalent_to(&best));
+
+    matches.choose(&mut rand::thread_rng()).cloned()
+}
+
 /**
  * Find a flow in a bot based on the user's input.
  * - flow_trigger events must will match a flow's id or name and reset the hold position
- * - other events will try to match a flow trigger
+ * - regex events will try to match a flow command or exact trigger
+ * - other events will try to match a flow trigger (exact, regex or fuzzy) whose conditions
+ * are satisfied by the request metadata
  */
 pub fn search_flow<'a>(
     event: &Event,
     bot: &'a CsmlBot,
     client: &Client,
+    metadata: &Value,
     db: &mut Database,
-) -> Result<(&'a CsmlFlow, String), EngineError> {
+) -> Result<(&'a CsmlFlow, String, Option<TriggerMatch>), EngineError> {
     match event {
         event if event.content_type == "flow_trigger" => {
             delete_state_key(&client, "hold", "position", db)?;
@@ -282,36 +320,31 @@ pub fn search_flow<'a>(
 
             match get_flow_by_id(&flow_trigger.flow_id, &bot.flows) {
                 Ok(flow) => match flow_trigger.step_id {
-                    Some(step_id) => Ok((flow, step_id)),
-                    None => Ok((flow, "start".to_owned())),
+                    Some(step_id) => Ok((flow, step_id, None)),
+                    None => Ok((flow, "start".to_owned(), None)),
                 },
                 Err(_) => Ok((
                     get_flow_by_id(&bot.default_flow, &bot.flows)?,
                     "start".to_owned(),
+                    None,
                 )),
             }
         }
         event if event.content_type == "regex" => {
-            let mut random_flows = vec![];
+            let mut matches = vec![];
 
-            for flow in bot.flows.iter() {
-                let contains_command = flow.commands.iter().any(|cmd| {
-                    if let Ok(action) = Regex::new(&event.content_value) {
-                        action.is_match(&cmd)
-                    } else {
-                        false
+            if let Ok(action) = Regex::new(&event.content_value) {
+                for flow in bot.flows.iter() {
+                    if let Some(trigger) = flow.search_regex(&action) {
+                        matches.push((flow, trigger))
                     }
-                });
-
-                if contains_command {
-                    random_flows.push(flow)
                 }
             }
 
-            match random_flows.choose(&mut rand::thread_rng()) {
-                Some(flow) => {
+            match select_flow(matches) {
+                Some((flow, trigger)) => {
                     delete_state_key(&client, "hold", "position", db)?;
-                    Ok((flow, "start".to_owned()))
+                    Ok((flow, "start".to_owned(), Some(trigger)))
                 }
                 None => Err(EngineError::Interpreter(format!(
                     "no match found for regex: {}",
@@ -320,23 +353,20 @@ pub fn search_flow<'a>(
             }
         }
         event => {
-            let mut random_flows = vec![];
+            let mut matches = vec![];
 
             for flow in bot.flows.iter() {
-                let contains_command = flow
-                    .commands
-                    .iter()
-                    .any(|cmd| &cmd.as_str().to_lowercase() == &event.content_value.to_lowercase());
-
-                if contains_command {
-                    random_flows.push(flow)
+                if let Some(trigger) =
+                    flow.search_trigger(&event.content_value, event.get_intent(), metadata)
+                {
+                    matches.push((flow, trigger))
                 }
             }
 
-            match random_flows.choose(&mut rand::thread_rng()) {
-                Some(flow) => {
+            match select_flow(matches) {
+                Some((flow, trigger)) => {
                     delete_state_key(&client, "hold", "position", db)?;
-                    Ok((flow, "start".to_owned()))
+                    Ok((flow, "start".to_owned(), Some(trigger)))
                 }
                 None => Err(EngineError::Interpreter(format!(
                     "Flow '{}' does not exist",
@@ -455,6 +485,57 @@ pub fn clean_hold_and_restart(data: &mut ConversationInfo) -> Result<(), EngineE
     return Ok(());
 }
 
+/**
+ * OAuth2 clients are saved in the state of the bot, not of the user, their id already
+ * depends on the bot and on the credentials.
+ */
+fn get_oauth2_state_client(client: &Client) -> Client {
+    Client::new(client.bot_id.to_owned(), String::new(), String::new())
+}
+
+/**
+ * OAuth2 clients of a request, the clients that are not in the conversation yet
+ * are read from the state of the bot so their tokens are shared by its requests.
+ */
+pub fn init_oauth2_clients(client: &Client) -> OAuth2Clients {
+    let state_client = get_oauth2_state_client(client);
+
+    OAuth2Clients::with_loader(move |id| {
+        let mut db = init_db().ok()?;
+        let value = get_state_key(&state_client, "oauth2", id, &mut db).ok()??;
+
+        serde_json::from_value(value).ok()
+    })
+}
+
+/**
+ * Save the OAuth2 clients registered or with a new token during the step, they expire
+ * with the conversation data.
+ */
+pub fn save_oauth2_clients(data: &mut ConversationInfo) -> Result<(), EngineError> {
+    let mut values = vec![];
+    for (id, client) in data.context.oauth2_clients.take_updated() {
+        values.push((id, serde_json::to_value(client)?));
+    }
+
+    if values.is_empty() {
+        return Ok(());
+    }
+
+    let keys_values = values
+        .iter()
+        .map(|(id, value)| (id.as_str(), value))
+        .collect();
+
+    set_state_items(
+        &get_oauth2_state_client(&data.client),
+        "oauth2",
+        keys_values,
+        data.ttl,
+        &mut data.db,
+    )
+}
+
 pub fn get_ttl_duration_value(event: Option<&Event>) -> Option<chrono::Duration> {
     if let Some(event) = event {
         if let Some(ttl) = event.ttl_duration {
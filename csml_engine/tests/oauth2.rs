@@ -0,0 +1,157 @@
+use csml_engine::{
+    data::{BotOpt, CsmlRequest},
+    delete_client, start_conversation,
+};
+use csml_interpreter::data::{csml_bot::CsmlBot, csml_flow::CsmlFlow, Client};
+use serde_json::json;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use uuid::Uuid;
+
+/**
+ * Read a whole request so the client is done writing before the response is sent
+ */
+fn read_request(stream: &mut TcpStream) -> String {
+    let mut request = vec![];
+    let mut buffer = [0; 4096];
+
+    loop {
+        let size = match stream.read(&mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(size) => size,
+        };
+        request.extend_from_slice(&buffer[..size]);
+
+        let text = String::from_utf8_lossy(&request).to_lowercase();
+        if let Some(end) = text.find("\r\n\r\n") {
+            let length = text[..end]
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .and_then(|value| value.trim().parse::<usize>().ok())
+                .unwrap_or(0);
+
+            if request.len() >= end + 4 + length {
+                break;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&request).to_string()
+}
+
+/**
+ * Local server delivering OAuth2 tokens on /token, returns its url and the number of token calls
+ */
+fn start_oauth2_server() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => break,
+            };
+
+            let request = read_request(&mut stream);
+            let body = if request.starts_with("POST /token ") {
+                counter.fetch_add(1, Ordering::SeqCst);
+                r#"{"access_token":"tok","expires_in":3600}"#
+            } else {
+                r#"{"ok":true}"#
+            };
+
+            let _ = stream.write_all(
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .as_bytes(),
+            );
+        }
+    });
+
+    (url, calls)
+}
+
+fn init_bot(url: &str) -> CsmlBot {
+    let content = format!(
+        "start:\n\tsay HTTP(\"{url}/api\").oauth2(\"{url}/token\", \"client\", \"secret\").get().send()\n\tgoto end\n",
+        url = url
+    );
+
+    CsmlBot {
+        id: "oauth2".to_owned(),
+        name: "oauth2".to_owned(),
+        apps_endpoint: None,
+        flows: vec![CsmlFlow::new("start", "start", &content, vec![])],
+        native_components: None,
+        custom_components: None,
+        default_flow: "start".to_owned(),
+        bot_ast: None,
+        no_interruption_delay: None,
+        env: None,
+        modules: None,
+        multibot: None,
+        knowledge: None,
+        knowledge_index: None,
+        locales: None,
+        default_locale: None,
+        files: None,
+        files_cache: None,
+        network_policy: None,
+        nlu: None,
+    }
+}
+
+fn init_request(client: Client) -> CsmlRequest {
+    CsmlRequest {
+        request_id: "tmp".to_owned(),
+        client,
+        callback_url: None,
+        payload: json!({
+            "content_type": "text",
+            "content": { "text": "start"},
+        }),
+        metadata: json!({}),
+        ttl_duration: None,
+        step_limit: None,
+        low_data_mode: None,
+        http_mock: None,
+    }
+}
+
+#[test]
+fn ok_oauth2_token_shared_by_requests() {
+    let (url, calls) = start_oauth2_server();
+    let bot = init_bot(&url);
+    let bot_id = Uuid::new_v4().to_string();
+
+    let clients: Vec<Client> = (0..2)
+        .map(|_| Client {
+            user_id: Uuid::new_v4().to_string(),
+            bot_id: bot_id.clone(),
+            channel_id: Uuid::new_v4().to_string(),
+        })
+        .collect();
+
+    for client in clients.iter() {
+        let obj =
+            start_conversation(init_request(client.clone()), BotOpt::CsmlBot(bot.clone())).unwrap();
+        let messages = obj["messages"].as_array().unwrap();
+
+        assert_eq!(messages[0]["payload"]["content"], json!({"ok": true}));
+    }
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    for client in clients.iter() {
+        delete_client(client).unwrap();
+    }
+}
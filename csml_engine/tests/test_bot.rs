@@ -69,6 +69,7 @@ fn init_bot(bot_name: &str) -> Result<CsmlBot, std::io::Error> {
             id: name.to_owned(),
             name: name.to_owned(),
             commands,
+            triggers: vec![],
             content: flow_content,
         });
     }
@@ -89,6 +90,14 @@ fn init_bot(bot_name: &str) -> Result<CsmlBot, std::io::Error> {
         })),
         modules: None,
         multibot: None,
+        knowledge: None,
+        knowledge_index: None,
+        locales: None,
+        default_locale: None,
+        files: None,
+        files_cache: None,
+        network_policy: None,
+        nlu: None,
     };
 
     Ok(bot)
@@ -111,6 +120,7 @@ fn init_request(string: &str, bot_id: String, channel_id: String) -> CsmlRequest
         ttl_duration: None,
         step_limit: None,
         low_data_mode: None,
+        http_mock: None,
     }
 }
 
@@ -52,7 +52,10 @@ fn main() {
         ttl_duration: None,
         low_data_mode: None,
         step_limit: None,
+        http_mock: None,
         secure: false,
+        trigger: None,
+        nlu: None,
     };
 
     // Create context
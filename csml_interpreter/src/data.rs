@@ -1,4 +1,5 @@
 pub mod ast;
+pub mod bot_files;
 pub mod client;
 pub mod context;
 pub mod csml_bot;
@@ -10,30 +11,39 @@ pub mod error_info;
 pub mod event;
 pub mod fn_args_type;
 pub mod hold;
+pub mod http_mock;
+pub mod knowledge;
 pub mod literal;
+pub mod localization;
 pub mod memories;
 pub mod message;
 pub mod message_data;
 pub mod msg;
+pub mod network_policy;
 pub mod position;
 pub mod primitive;
 pub mod tokens;
 pub mod warnings;
 
 pub use ast::Interval;
+pub use bot_files::BotFiles;
 pub use client::Client;
 pub use context::{ApiInfo, Context, PreviousBot};
-pub use csml_bot::{CsmlBot, Module, MultiBot};
-pub use csml_flow::CsmlFlow;
+pub use csml_bot::{CsmlBot, Module, MultiBot, NluConfig, NluProvider};
+pub use csml_flow::{CsmlFlow, CsmlTrigger, TriggerKind, TriggerMatch};
 pub use csml_result::CsmlResult;
 pub use data::Data;
-pub use event::Event;
+pub use event::{Event, Intent, NluEntity, NluResult};
 pub use fn_args_type::ArgsType;
 pub use hold::{Hold, IndexInfo};
+pub use http_mock::{HttpMock, HttpMockMode};
+pub use knowledge::{Knowledge, KnowledgeEntry, KnowledgeIndex};
 pub use literal::Literal;
+pub use localization::Localization;
 pub use memories::{Memory, MemoryType};
 pub use message::Message;
 pub use message_data::MessageData;
+pub use network_policy::{HostPolicy, NetworkPolicy, NetworkService};
 pub use position::Position;
 
 pub use msg::MSG;
@@ -314,6 +314,24 @@ pub enum IfStatement {
     ElseStmt(Block, Interval),
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormField {
+    pub name: Identifier,
+    pub field_type: String,
+    pub ask: Box<Expr>,
+    pub validate: Option<Box<Expr>>,
+    pub retries: Option<usize>,
+    pub error: Option<Box<Expr>>,
+    pub skip: Option<Box<Expr>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Form {
+    pub name: Identifier,
+    pub fields: Vec<FormField>,
+    pub interval: Interval,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Expr {
     Scope {
@@ -345,6 +363,9 @@ pub enum Expr {
         literal: Literal,
         in_in_substring: bool, // this value is use to determine if this literal was declare inside a string or not
     },
+
+    // added last to keep the bot_ast of existing bot versions readable
+    FormExpr(Form),
 }
 
 impl Expr {
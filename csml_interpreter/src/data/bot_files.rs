@@ -0,0 +1,99 @@
+use crate::data::primitive::tools::csv_to_json;
+use crate::data::{Interval, Literal};
+use crate::interpreter::json_to_literal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+// extensions of the static data files that can be bundled with a bot
+pub const BOT_FILES_EXTENSIONS: &[&str] = &[
+    "json", "yaml", "yml", "csv", "mustache", "hbs", "html", "txt",
+];
+
+/**
+ * Static data files of the bot already parsed into literals, indexed by file name.
+ */
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BotFiles {
+    files: HashMap<String, Literal>,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+fn get_extension(name: &str) -> String {
+    match name.rsplit_once('.') {
+        Some((_, extension)) => extension.to_lowercase(),
+        None => String::new(),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PUBLIC FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+pub fn is_bot_file(name: &str) -> bool {
+    BOT_FILES_EXTENSIONS.contains(&get_extension(name).as_str())
+}
+
+pub fn parse_file(name: &str, content: &str) -> Result<Value, String> {
+    match get_extension(name).as_str() {
+        "json" => serde_json::from_str(content)
+            .map_err(|err| format!("invalid JSON file [{}]: {}", name, err)),
+        "yaml" | "yml" => serde_yaml::from_str(content)
+            .map_err(|err| format!("invalid YAML file [{}]: {}", name, err)),
+        "csv" => csv_to_json(content, ',', true)
+            .map_err(|err| format!("invalid CSV file [{}]: {}", name, err)),
+        // templates are kept as text and rendered with the Template builtin
+        "mustache" | "hbs" | "html" | "txt" => Ok(Value::String(content.to_owned())),
+        _ => Err(format!(
+            "unsupported file [{}], bot files must be one of: {}",
+            name,
+            BOT_FILES_EXTENSIONS.join(", ")
+        )),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// METHOD FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+impl BotFiles {
+    pub fn from_files(files: &HashMap<String, String>) -> Result<Self, String> {
+        let mut parsed_files = HashMap::new();
+
+        for (name, content) in files.iter() {
+            let value = parse_file(name, content)?;
+            let literal = json_to_literal(&value, Interval::default(), name)
+                .map_err(|err| format!("invalid file [{}]: {}", name, err.message))?;
+
+            parsed_files.insert(name.to_owned(), literal);
+        }
+
+        Ok(Self {
+            files: parsed_files,
+        })
+    }
+
+    /**
+     * The parsed files are stored with the bot the same way as the bot_ast
+     */
+    pub fn encode(&self) -> String {
+        base64::encode(bincode::serialize(self).unwrap())
+    }
+
+    pub fn decode(files: &str) -> Option<Self> {
+        let base64decoded = base64::decode(files).ok()?;
+
+        bincode::deserialize(&base64decoded[..]).ok()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Literal> {
+        self.files.get(name.trim_start_matches("./"))
+    }
+}
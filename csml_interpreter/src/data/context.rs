@@ -3,6 +3,7 @@ use crate::data::{
     Client, Hold, Interval, Literal,
 };
 
+use crate::interpreter::builtins::oauth2::OAuth2Clients;
 use crate::interpreter::{json_to_literal, memory_to_literal};
 
 use nom::lib::std::collections::HashMap;
@@ -67,6 +68,7 @@ pub struct Context {
     pub step: ContextStepInfo,
     pub flow: String,
     pub previous_bot: Option<PreviousBot>,
+    pub oauth2_clients: OAuth2Clients,
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -135,6 +137,7 @@ impl Context {
             step: ContextStepInfo::Normal(step.to_owned()),
             flow: flow.to_owned(),
             previous_bot,
+            oauth2_clients: OAuth2Clients::default(),
         }
     }
 }
@@ -1,7 +1,8 @@
-use crate::data::{CsmlFlow, Position};
+use crate::data::{CsmlFlow, Knowledge, NetworkPolicy, Position};
 use crate::error_format::*;
 use crate::Interval;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 ////////////////////////////////////////////////////////////////////////////////
 // DATA STRUCTURE
@@ -22,6 +23,14 @@ pub struct CsmlBot {
     pub bot_ast: Option<String>,
     pub no_interruption_delay: Option<i32>,
     pub env: Option<serde_json::Value>,
+    pub knowledge: Option<Knowledge>,
+    pub knowledge_index: Option<String>,
+    pub locales: Option<HashMap<String, serde_json::Value>>,
+    pub default_locale: Option<String>,
+    pub files: Option<HashMap<String, String>>,
+    pub files_cache: Option<String>,
+    pub network_policy: Option<NetworkPolicy>,
+    pub nlu: Option<NluConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,10 +50,42 @@ pub struct MultiBot {
     pub version_id: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NluProvider {
+    Rasa,
+    Dialogflow,
+}
+
+impl Default for NluProvider {
+    fn default() -> Self {
+        NluProvider::Rasa
+    }
+}
+
+/**
+ * NLU provider of the bot, the engine annotates the text events with the intents and
+ * entities it detects. Bots without NLU provider are not sent to any.
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NluConfig {
+    #[serde(default)]
+    pub provider: NluProvider,
+    pub url: String,
+    pub token: Option<String>,
+    // language code sent to dialogflow
+    #[serde(default = "default_language")]
+    pub language: String,
+}
+
 fn default_version() -> String {
     "latest".to_string()
 }
 
+fn default_language() -> String {
+    "en".to_string()
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // STATIC FUNCTIONS
 ////////////////////////////////////////////////////////////////////////////////
@@ -77,6 +118,14 @@ impl CsmlBot {
             bot_ast,
             no_interruption_delay,
             env,
+            knowledge: None,
+            knowledge_index: None,
+            locales: None,
+            default_locale: None,
+            files: None,
+            files_cache: None,
+            network_policy: None,
+            nlu: None,
         }
     }
 
@@ -128,6 +177,18 @@ impl CsmlBot {
             serde_json::json!(self.no_interruption_delay),
         );
         map.insert("env".to_owned(), serde_json::json!(self.env));
+        map.insert("knowledge".to_owned(), serde_json::json!(self.knowledge));
+        map.insert("locales".to_owned(), serde_json::json!(self.locales));
+        map.insert(
+            "default_locale".to_owned(),
+            serde_json::json!(self.default_locale),
+        );
+        map.insert("files".to_owned(), serde_json::json!(self.files));
+        map.insert(
+            "network_policy".to_owned(),
+            serde_json::json!(self.network_policy),
+        );
+        map.insert("nlu".to_owned(), serde_json::json!(self.nlu));
 
         serde_json::json!(map)
     }
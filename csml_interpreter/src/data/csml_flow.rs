@@ -1,3 +1,7 @@
+use crate::data::primitive::tools::get_similarity;
+use crate::data::Intent;
+use once_cell::sync::OnceCell;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -10,6 +14,59 @@ pub struct CsmlFlow {
     pub name: String,
     pub content: String,
     pub commands: Vec<String>,
+    #[serde(default)]
+    pub triggers: Vec<CsmlTrigger>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TriggerKind {
+    Exact,
+    Regex,
+    Fuzzy,
+    Intent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsmlTrigger {
+    pub value: String,
+    #[serde(default)]
+    pub kind: TriggerKind,
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+    #[serde(default)]
+    pub priority: i64,
+    pub threshold: Option<f64>,
+    pub conditions: Option<serde_json::Map<String, serde_json::Value>>,
+    // regex of the trigger, compiled on its first search and kept with the flow
+    #[serde(skip)]
+    regex: OnceCell<Option<Regex>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriggerMatch {
+    pub flow: String,
+    pub value: String,
+    pub kind: TriggerKind,
+    pub score: f64,
+    pub priority: i64,
+}
+
+// minimal similarity for a fuzzy trigger when no threshold is given
+const DEFAULT_FUZZY_THRESHOLD: f64 = 0.8;
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// TRAIT FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+impl Default for TriggerKind {
+    fn default() -> Self {
+        TriggerKind::Exact
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -23,6 +80,193 @@ impl CsmlFlow {
             name: name.to_owned(),
             content: content.to_owned(),
             commands,
+            triggers: vec![],
+        }
+    }
+
+    /**
+     * Legacy commands are handled as exact triggers with the default weight and priority.
+     */
+    pub fn get_triggers(&self) -> Vec<CsmlTrigger> {
+        let mut triggers: Vec<CsmlTrigger> = self
+            .commands
+            .iter()
+            .map(|command| CsmlTrigger::new(command, TriggerKind::Exact))
+            .collect();
+
+        triggers.extend(self.triggers.iter().cloned());
+
+        triggers
+    }
+
+    /**
+     * Return the best trigger of the flow matching the user's input or its NLU intent, if any.
+     * Triggers are compared by priority first and then by score.
+     */
+    pub fn search_trigger(
+        &self,
+        text: &str,
+        intent: Option<&Intent>,
+        metadata: &serde_json::Value,
+    ) -> Option<TriggerMatch> {
+        let mut best: Option<TriggerMatch> = None;
+
+        let commands: Vec<CsmlTrigger> = self
+            .commands
+            .iter()
+            .map(|command| CsmlTrigger::new(command, TriggerKind::Exact))
+            .collect();
+
+        for trigger in commands.iter().chain(self.triggers.iter()) {
+            let score = match trigger.get_score(text, intent, metadata) {
+                Some(score) => score,
+                None => continue,
+            };
+
+            let trigger_match = TriggerMatch {
+                flow: self.name.to_owned(),
+                value: trigger.value.to_owned(),
+                kind: trigger.kind.to_owned(),
+                score,
+                priority: trigger.priority,
+            };
+
+            best = match best {
+                Some(best) if !trigger_match.is_better_than(&best) => Some(best),
+                _ => Some(trigger_match),
+            };
         }
+
+        best
+    }
+
+    /**
+     * Return the first exact trigger of the flow matched by the regex of a "regex" event.
+     */
+    pub fn search_regex(&self, regex: &Regex) -> Option<TriggerMatch> {
+        self.get_triggers()
+            .into_iter()
+            .find(|trigger| trigger.kind == TriggerKind::Exact && regex.is_match(&trigger.value))
+            .map(|trigger| TriggerMatch {
+                flow: self.name.to_owned(),
+                value: trigger.value,
+                kind: TriggerKind::Regex,
+                score: 1.0,
+                priority: trigger.priority,
+            })
+    }
+}
+
+impl CsmlTrigger {
+    pub fn new(value: &str, kind: TriggerKind) -> Self {
+        Self {
+            value: value.to_owned(),
+            kind,
+            weight: default_weight(),
+            priority: 0,
+            threshold: None,
+            conditions: None,
+            regex: OnceCell::new(),
+        }
+    }
+
+    /**
+     * None if the value of the trigger is not a valid regex.
+     */
+    fn get_regex(&self) -> Option<&Regex> {
+        self.regex
+            .get_or_init(|| Regex::new(&self.value).ok())
+            .as_ref()
+    }
+
+    /**
+     * Every condition must be satisfied by the request metadata:
+     * - an array condition matches if the metadata value is one of its elements
+     * - any other condition must be equal to the metadata value
+     */
+    pub fn check_conditions(&self, metadata: &serde_json::Value) -> bool {
+        let conditions = match &self.conditions {
+            Some(conditions) => conditions,
+            None => return true,
+        };
+
+        conditions
+            .iter()
+            .all(|(key, expected)| match metadata.get(key) {
+                Some(value) => match expected {
+                    serde_json::Value::Array(values) => values.contains(value),
+                    expected => expected == value,
+                },
+                None => false,
+            })
+    }
+
+    /**
+     * Score how well the user's input matches this trigger, weighted by the trigger's weight.
+     * Intent triggers are scored with the confidence of the NLU intent.
+     * Return None if the trigger does not match or if its conditions are not met.
+     */
+    pub fn get_score(
+        &self,
+        text: &str,
+        intent: Option<&Intent>,
+        metadata: &serde_json::Value,
+    ) -> Option<f64> {
+        if !self.check_conditions(metadata) {
+            return None;
+        }
+
+        let score = match self.kind {
+            TriggerKind::Exact if self.value.to_lowercase() == text.to_lowercase() => 1.0,
+            TriggerKind::Exact => return None,
+            TriggerKind::Regex => match self.get_regex() {
+                Some(regex) if regex.is_match(text) => 1.0,
+                _ => return None,
+            },
+            TriggerKind::Fuzzy => {
+                let similarity = get_similarity(&self.value.to_lowercase(), &text.to_lowercase());
+                let threshold = self.threshold.unwrap_or(DEFAULT_FUZZY_THRESHOLD);
+
+                if similarity < threshold {
+                    return None;
+                }
+
+                similarity
+            }
+            TriggerKind::Intent => match intent {
+                Some(intent) if intent.name.to_lowercase() == self.value.to_lowercase() => {
+                    match self.threshold {
+                        Some(threshold) if intent.confidence < threshold => return None,
+                        _ => intent.confidence,
+                    }
+                }
+                _ => return None,
+            },
+        };
+
+        Some(score * self.weight)
+    }
+}
+
+impl TriggerMatch {
+    pub fn is_better_than(&self, other: &TriggerMatch) -> bool {
+        match self.priority.cmp(&other.priority) {
+            std::cmp::Ordering::Equal => self.score > other.score,
+            ordering => ordering == std::cmp::Ordering::Greater,
+        }
+    }
+
+    pub fn is_equivalent_to(&self, other: &TriggerMatch) -> bool {
+        self.priority == other.priority && self.score == other.score
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "flow": self.flow,
+            "value": self.value,
+            "kind": self.kind,
+            "score": self.score,
+            "priority": self.priority,
+        })
     }
 }
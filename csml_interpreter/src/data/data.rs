@@ -1,6 +1,6 @@
 use crate::data::context::Context;
-use crate::data::Event;
 use crate::data::{ast::*, Literal};
+use crate::data::{BotFiles, Event, KnowledgeIndex, Localization, NetworkPolicy};
 
 use crate::data::context::ContextStepInfo;
 
@@ -37,6 +37,10 @@ pub struct Data<'a> {
     pub previous_info: Option<PreviousInfo>,
     pub custom_component: &'a serde_json::Map<String, serde_json::Value>,
     pub native_component: &'a serde_json::Map<String, serde_json::Value>,
+    pub knowledge: Option<&'a KnowledgeIndex>,
+    pub localization: Option<Localization<'a>>,
+    pub files: Option<&'a BotFiles>,
+    pub network_policies: &'a [NetworkPolicy],
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -77,6 +81,10 @@ impl<'a> Data<'a> {
         previous_info: Option<PreviousInfo>,
         custom_component: &'a serde_json::Map<String, serde_json::Value>,
         native_component: &'a serde_json::Map<String, serde_json::Value>,
+        knowledge: Option<&'a KnowledgeIndex>,
+        localization: Option<Localization<'a>>,
+        files: Option<&'a BotFiles>,
+        network_policies: &'a [NetworkPolicy],
     ) -> Self {
         let constants = flow.constants.clone();
 
@@ -97,6 +105,10 @@ impl<'a> Data<'a> {
             previous_info,
             custom_component,
             native_component,
+            knowledge,
+            localization,
+            files,
+            network_policies,
         }
     }
 
@@ -144,6 +156,7 @@ pub fn init_child_context(data: &Data) -> Context {
         step: data.context.step.clone(),
         flow: data.context.flow.clone(),
         previous_bot: data.context.previous_bot.clone(),
+        oauth2_clients: data.context.oauth2_clients.clone(),
     }
 }
 
@@ -168,5 +181,9 @@ pub fn init_child_scope<'a>(
         data.previous_info.clone(),
         &data.custom_component,
         &data.native_component,
+        data.knowledge,
+        data.localization,
+        data.files,
+        data.network_policies,
     )
 }
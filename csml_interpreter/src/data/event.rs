@@ -1,3 +1,6 @@
+use crate::data::{HttpMock, TriggerMatch};
+use serde::{Deserialize, Serialize};
+
 ////////////////////////////////////////////////////////////////////////////////
 // DATA STRUCTURES
 ////////////////////////////////////////////////////////////////////////////////
@@ -10,7 +13,32 @@ pub struct Event {
     pub ttl_duration: Option<i64>,
     pub low_data_mode: Option<bool>,
     pub step_limit: Option<usize>,
+    pub http_mock: Option<HttpMock>,
     pub secure: bool,
+    pub trigger: Option<TriggerMatch>,
+    pub nlu: Option<NluResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Intent {
+    pub name: String,
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NluEntity {
+    pub entity: String,
+    pub value: serde_json::Value,
+    pub confidence: Option<f64>,
+    pub start: Option<usize>,
+    pub end: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NluResult {
+    pub intent: Option<Intent>,
+    pub intents: Vec<Intent>,
+    pub entities: Vec<NluEntity>,
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -26,7 +54,10 @@ impl Default for Event {
             ttl_duration: None,
             low_data_mode: None,
             step_limit: None,
+            http_mock: None,
             secure: false,
+            trigger: None,
+            nlu: None,
         }
     }
 }
@@ -44,7 +75,28 @@ impl Event {
             ttl_duration: None,
             low_data_mode: None,
             step_limit: None,
+            http_mock: None,
             secure: false,
+            trigger: None,
+            nlu: None,
         }
     }
+
+    pub fn get_intent(&self) -> Option<&Intent> {
+        self.nlu.as_ref().and_then(|nlu| nlu.intent.as_ref())
+    }
+}
+
+impl NluResult {
+    /**
+     * NLU annotations are exposed in CSML as event.nlu.intent, event.nlu.intents
+     * and event.nlu.entities, and as event.intent and event.entities
+     */
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "intent": self.intent,
+            "intents": self.intents,
+            "entities": self.entities,
+        })
+    }
 }
@@ -59,7 +59,7 @@ impl ArgsType {
                 while index < size {
                     let lit = map[&format!("arg{}", index)].clone();
                     if lit.secure_variable {
-                        return "secure variables can not be logged".to_string()
+                        return "secure variables can not be logged".to_string();
                     }
 
                     let value = lit.primitive.to_string();
@@ -86,6 +86,52 @@ impl ArgsType {
         }
     }
 
+    /**
+     * Arguments of a method by position, the named arguments take the position of their
+     * parameter. Methods without parameter names do not accept named arguments.
+     */
+    pub fn to_positional(
+        &self,
+        params: Option<&[&str]>,
+        flow_name: &str,
+        interval: Interval,
+    ) -> Result<HashMap<String, Literal>, ErrorInfo> {
+        let (var, params) = match (self, params) {
+            (Self::Normal(var), _) => return Ok(var.to_owned()),
+            (Self::Named(var), Some(params)) => (var, params),
+            (Self::Named(_), None) => {
+                return Err(gen_error_info(
+                    Position::new(interval, flow_name),
+                    ERROR_METHOD_NAMED_ARGS.to_owned(),
+                ))
+            }
+        };
+
+        let mut args = HashMap::new();
+        for (key, value) in var.iter() {
+            let position = match params.iter().position(|param| param == key) {
+                Some(index) => format!("arg{}", index),
+                // arguments before the named ones are already in their position
+                None if key.starts_with("arg") => key.to_owned(),
+                None => {
+                    return Err(gen_error_info(
+                        Position::new(interval, flow_name),
+                        format!("{} [{}]", ERROR_METHOD_UNKNOWN_ARG, key),
+                    ))
+                }
+            };
+
+            if args.insert(position, value.to_owned()).is_some() {
+                return Err(gen_error_info(
+                    Position::new(interval, flow_name),
+                    format!("{} [{}]", ERROR_METHOD_DUPLICATE_ARG, key),
+                ));
+            }
+        }
+
+        Ok(args)
+    }
+
     pub fn len(&self) -> usize {
         match self {
             Self::Named(var) | Self::Normal(var) => var.len(),
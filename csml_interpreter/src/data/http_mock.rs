@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::path::{Component, Path};
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HttpMockMode {
+    // calls are sent and the request/response pairs are saved in the fixture file
+    Record,
+    // responses come from the fixture file, unknown requests fail
+    Replay,
+    // responses come from the url patterns of the stub file
+    Stub,
+}
+
+/**
+ * Mock of the HTTP and App calls, set for a run with the CSML_HTTP_MOCK env var
+ * (`replay:tests/fixtures/bot.json`) or for a single request of the engine.
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpMock {
+    pub mode: HttpMockMode,
+    pub file: String,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// STATIC FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+impl HttpMock {
+    pub fn new(mode: HttpMockMode, file: &str) -> Self {
+        Self {
+            mode,
+            file: file.to_owned(),
+        }
+    }
+
+    pub fn from_env() -> Option<Self> {
+        let value = env::var("CSML_HTTP_MOCK").ok()?;
+        let (mode, file) = value.split_once(':')?;
+
+        let mode = match mode.trim() {
+            "record" => HttpMockMode::Record,
+            "replay" => HttpMockMode::Replay,
+            "stub" => HttpMockMode::Stub,
+            _ => return None,
+        };
+
+        Some(Self::new(mode, file.trim()))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// METHOD FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+impl HttpMock {
+    /**
+     * Mocks sent in engine requests can only use the files of the CSML_HTTP_MOCK_DIR
+     * directory, they are disabled when it is not set.
+     */
+    pub fn in_mock_directory(&self) -> Option<Self> {
+        let directory = env::var("CSML_HTTP_MOCK_DIR").ok()?;
+        let file = Path::new(&self.file);
+
+        let is_relative = file
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+        if !is_relative {
+            return None;
+        }
+
+        let path = Path::new(&directory).join(file);
+
+        Some(Self::new(self.mode.to_owned(), path.to_str()?))
+    }
+}
@@ -0,0 +1,192 @@
+use crate::data::primitive::tools::{get_tokens, normalize_text, parse_csv};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgeEntry {
+    pub id: Option<String>,
+    pub question: String,
+    pub answer: String,
+}
+
+/**
+ * A knowledge base can be given either as a list of entries
+ * or as a CSV text with at least a "question" and an "answer" column.
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Knowledge {
+    Entries(Vec<KnowledgeEntry>),
+    Csv(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgeIndex {
+    pub entries: Vec<KnowledgeEntry>,
+    term_frequencies: Vec<HashMap<String, usize>>,
+    document_frequencies: HashMap<String, usize>,
+    lengths: Vec<usize>,
+    average_length: f64,
+}
+
+// BM25 parameters
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+fn tokenize(text: &str) -> Vec<String> {
+    get_tokens(&normalize_text(text, true))
+        .into_iter()
+        .map(|token| token.to_owned())
+        .collect()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// METHOD FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+impl Knowledge {
+    pub fn get_entries(&self) -> Result<Vec<KnowledgeEntry>, String> {
+        match self {
+            Knowledge::Entries(entries) => Ok(entries.to_owned()),
+            Knowledge::Csv(csv) => {
+                let mut rows = parse_csv(csv, ',').into_iter();
+
+                let header: Vec<String> = match rows.next() {
+                    Some(header) => header
+                        .iter()
+                        .map(|name| name.trim().to_lowercase())
+                        .collect(),
+                    None => return Ok(vec![]),
+                };
+                let get_column = |name: &str| header.iter().position(|column| column == name);
+
+                let (question, answer) = match (get_column("question"), get_column("answer")) {
+                    (Some(question), Some(answer)) => (question, answer),
+                    _ => {
+                        return Err(
+                            "knowledge CSV expects a 'question' and an 'answer' column".to_owned()
+                        )
+                    }
+                };
+                let id = get_column("id");
+
+                Ok(rows
+                    .filter_map(|row| {
+                        Some(KnowledgeEntry {
+                            id: id.and_then(|id| row.get(id).cloned()),
+                            question: row.get(question)?.to_owned(),
+                            answer: row.get(answer)?.to_owned(),
+                        })
+                    })
+                    .collect())
+            }
+        }
+    }
+}
+
+impl KnowledgeIndex {
+    pub fn new(entries: Vec<KnowledgeEntry>) -> Self {
+        let mut term_frequencies = vec![];
+        let mut document_frequencies: HashMap<String, usize> = HashMap::new();
+        let mut lengths = vec![];
+
+        for entry in entries.iter() {
+            let tokens = tokenize(&entry.question);
+            let mut frequencies: HashMap<String, usize> = HashMap::new();
+
+            for token in tokens.iter() {
+                *frequencies.entry(token.to_owned()).or_insert(0) += 1;
+            }
+            for token in frequencies.keys() {
+                *document_frequencies.entry(token.to_owned()).or_insert(0) += 1;
+            }
+
+            lengths.push(tokens.len());
+            term_frequencies.push(frequencies);
+        }
+
+        let average_length = match lengths.len() {
+            0 => 0.0,
+            len => lengths.iter().sum::<usize>() as f64 / len as f64,
+        };
+
+        Self {
+            entries,
+            term_frequencies,
+            document_frequencies,
+            lengths,
+            average_length,
+        }
+    }
+
+    pub fn from_knowledge(knowledge: &Knowledge) -> Result<Self, String> {
+        Ok(Self::new(knowledge.get_entries()?))
+    }
+
+    /**
+     * The index is stored with the bot the same way as the bot_ast
+     */
+    pub fn encode(&self) -> String {
+        base64::encode(bincode::serialize(self).unwrap())
+    }
+
+    pub fn decode(index: &str) -> Option<Self> {
+        let base64decoded = base64::decode(index).ok()?;
+
+        bincode::deserialize(&base64decoded[..]).ok()
+    }
+
+    /**
+     * Rank the entries matching the query with BM25,
+     * only the top entries with a positive score are returned.
+     */
+    pub fn search(&self, query: &str, top: usize) -> Vec<(&KnowledgeEntry, f64)> {
+        let tokens = tokenize(query);
+        let count = self.entries.len() as f64;
+
+        let mut results: Vec<(&KnowledgeEntry, f64)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let length = self.lengths[index] as f64;
+
+                let score = tokens
+                    .iter()
+                    .map(|token| {
+                        let frequency = match self.term_frequencies[index].get(token) {
+                            Some(frequency) => *frequency as f64,
+                            None => return 0.0,
+                        };
+                        let document_frequency = self.document_frequencies[token] as f64;
+
+                        let idf = ((count - document_frequency + 0.5) / (document_frequency + 0.5)
+                            + 1.0)
+                            .ln();
+
+                        idf * (frequency * (K1 + 1.0))
+                            / (frequency + K1 * (1.0 - B + B * length / self.average_length))
+                    })
+                    .sum::<f64>();
+
+                (entry, score)
+            })
+            .filter(|(_, score)| *score > 0.0)
+            .collect();
+
+        results.sort_by(|(_, lhs), (_, rhs)| {
+            rhs.partial_cmp(lhs).unwrap_or(std::cmp::Ordering::Equal)
+        });
+        results.truncate(top);
+
+        results
+    }
+}
@@ -33,6 +33,8 @@ pub enum ContentType {
     Jwt,
     Crypto,
     Time,
+    Knowledge,
+    Llm,
     Primitive,
 }
 
@@ -194,10 +196,26 @@ impl ContentType {
             "jwt" => ContentType::Jwt,
             "crypto" => ContentType::Crypto,
             "time" => ContentType::Time,
+            "knowledge" => ContentType::Knowledge,
+            "llm" => ContentType::Llm,
             "event" => ContentType::Event(String::from("")),
             _ => ContentType::Primitive,
         }
     }
+
+    /**
+     * Parameters of the methods that accept named arguments, in the order of their
+     * positions: Knowledge.search(event, top = 3)
+     */
+    pub fn get_named_params(&self, method: &str) -> Option<&'static [&'static str]> {
+        match (self, method) {
+            (ContentType::Knowledge, "search") => Some(&["query", "top"]),
+            (ContentType::Time, "format") => Some(&["format", "locale"]),
+            (ContentType::Primitive, "format_number") => Some(&["locale", "decimals"]),
+            (ContentType::Primitive, "format_currency") => Some(&["code", "locale"]),
+            _ => None,
+        }
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,164 @@
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+// plural forms that can be given for a single translation key
+pub const PLURAL_FORMS: &[&str] = &["zero", "one", "two", "few", "many", "other"];
+
+/**
+ * Translation catalogs of the bot indexed by locale (e.g. "en", "fr", "fr-CA").
+ * Each catalog is an object of translations, keys can be nested and are accessed with a dotted path.
+ */
+#[derive(Debug, Clone, Copy)]
+pub struct Localization<'a> {
+    pub locales: &'a HashMap<String, Value>,
+    pub default_locale: Option<&'a str>,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+fn is_plural(value: &Value) -> bool {
+    match value.as_object() {
+        Some(map) => !map.is_empty() && map.keys().all(|key| PLURAL_FORMS.contains(&key.as_str())),
+        None => false,
+    }
+}
+
+fn get_language(locale: &str) -> &str {
+    locale
+        .split(|c| c == '-' || c == '_')
+        .next()
+        .unwrap_or(locale)
+}
+
+fn flatten_keys(prefix: &str, value: &Value, keys: &mut BTreeSet<String>) {
+    match value.as_object() {
+        Some(map) if !is_plural(value) => {
+            for (key, value) in map.iter() {
+                let key = match prefix {
+                    "" => key.to_owned(),
+                    prefix => format!("{}.{}", prefix, key),
+                };
+
+                flatten_keys(&key, value, keys);
+            }
+        }
+        _ => {
+            keys.insert(prefix.to_owned());
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PUBLIC FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+/**
+ * Get all the translation keys of a catalog as dotted paths,
+ * plural forms are considered as a single key.
+ */
+pub fn get_catalog_keys(catalog: &Value) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+
+    flatten_keys("", catalog, &mut keys);
+    keys.remove("");
+
+    keys
+}
+
+/**
+ * CLDR plural category of a count for the most common languages,
+ * languages without plural forms always use "other".
+ */
+pub fn get_plural_category(locale: &str, count: f64) -> &'static str {
+    let is_int = count.fract() == 0.0;
+
+    match get_language(locale).to_lowercase().as_str() {
+        "fr" | "pt" | "hy" | "kab" if (0.0..2.0).contains(&count) => "one",
+        "ja" | "zh" | "ko" | "th" | "vi" | "id" | "ms" | "tr" => "other",
+        "ru" | "uk" | "be" if is_int => {
+            let (modulo_10, modulo_100) = (count as i64 % 10, count as i64 % 100);
+
+            match (modulo_10, modulo_100) {
+                (1, _) if modulo_100 != 11 => "one",
+                (2..=4, _) if !(12..=14).contains(&modulo_100) => "few",
+                _ => "many",
+            }
+        }
+        _ if count == 1.0 => "one",
+        _ => "other",
+    }
+}
+
+/**
+ * Select the plural form of a translation, the "zero" form is used
+ * for a count of 0 if present and "other" is the fallback of every category.
+ */
+pub fn get_plural_form<'a>(forms: &'a Value, locale: &str, count: f64) -> Option<&'a Value> {
+    let category = get_plural_category(locale, count);
+
+    match (count == 0.0, forms.get("zero")) {
+        (true, Some(zero)) => Some(zero),
+        _ => forms.get(category).or_else(|| forms.get("other")),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// METHOD FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+impl<'a> Localization<'a> {
+    pub fn new(
+        locales: &'a Option<HashMap<String, Value>>,
+        default_locale: &'a Option<String>,
+    ) -> Option<Self> {
+        locales.as_ref().map(|locales| Self {
+            locales,
+            default_locale: default_locale.as_deref(),
+        })
+    }
+
+    /**
+     * Find the catalog of a locale: "fr-CA" falls back to "fr" if there is no "fr-CA" catalog.
+     */
+    pub fn get_catalog(&self, locale: &str) -> Option<(&'a str, &'a Value)> {
+        let language = get_language(locale);
+
+        self.locales
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(locale))
+            .or_else(|| {
+                self.locales
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(language))
+            })
+            .map(|(name, catalog)| (name.as_str(), catalog))
+    }
+
+    /**
+     * Look for a translation key in the given locale first, then in the default locale.
+     * The locale where the translation was found is returned with it.
+     */
+    pub fn get_translation(&self, locale: Option<&str>, key: &str) -> Option<(&'a str, &'a Value)> {
+        let locales = locale.into_iter().chain(self.default_locale);
+
+        for locale in locales {
+            if let Some((name, catalog)) = self.get_catalog(locale) {
+                let value = key
+                    .split('.')
+                    .try_fold(catalog, |value, key| value.get(key));
+
+                if let Some(value) = value {
+                    return Some((name, value));
+                }
+            }
+        }
+
+        None
+    }
+}
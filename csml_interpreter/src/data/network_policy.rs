@@ -0,0 +1,389 @@
+use crate::data::csml_logs::*;
+
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs};
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NetworkService {
+    Http,
+    Smtp,
+}
+
+/**
+ * Hosts are a domain (`api.example.com`), all its subdomains (`*.example.com`),
+ * an ip (`10.0.0.12`), a CIDR range (`10.0.0.0/8`) or any host (`*`).
+ * An empty allowed list allows all the hosts that are not denied.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HostPolicy {
+    #[serde(default)]
+    pub allowed: Vec<String>,
+    #[serde(default)]
+    pub denied: Vec<String>,
+}
+
+/**
+ * Outbound network policy of the bots, set for the engine with the CSML_NETWORK_POLICY
+ * env var (json) or for a bot with its `network_policy`. Both apply when they are set.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetworkPolicy {
+    #[serde(default)]
+    pub http: HostPolicy,
+    #[serde(default)]
+    pub smtp: HostPolicy,
+    // loopback, private, link-local and unique local addresses, unless their host is allowed
+    #[serde(default)]
+    pub block_private_networks: bool,
+    // HTTP calls with disable_ssl_verify and SMTP servers without TLS
+    #[serde(default)]
+    pub forbid_disable_ssl_verify: bool,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+fn normalize_host(host: &str) -> String {
+    host.trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_ascii_lowercase()
+}
+
+/**
+ * IPv4 addresses mapped in IPv6 (::ffff:10.0.0.1) are checked as IPv4 addresses
+ */
+fn to_canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(ipv6) => match ipv6.segments() {
+            [0, 0, 0, 0, 0, 0xffff, high, low] => IpAddr::V4(Ipv4Addr::new(
+                (high >> 8) as u8,
+                high as u8,
+                (low >> 8) as u8,
+                low as u8,
+            )),
+            _ => ip,
+        },
+        ip => ip,
+    }
+}
+
+fn in_network(ip: IpAddr, network: IpAddr, prefix: u32) -> bool {
+    match (to_canonical(ip), to_canonical(network)) {
+        (IpAddr::V4(ip), IpAddr::V4(network)) if prefix <= 32 => {
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+
+            u32::from(ip) & mask == u32::from(network) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(network)) if prefix <= 128 => {
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+
+            u128::from(ip) & mask == u128::from(network) & mask
+        }
+        _ => false,
+    }
+}
+
+fn matches_ip(pattern: &str, ip: IpAddr) -> bool {
+    match pattern.split_once('/') {
+        Some((network, prefix)) => match (network.parse::<IpAddr>(), prefix.parse::<u32>()) {
+            (Ok(network), Ok(prefix)) => in_network(ip, network, prefix),
+            _ => false,
+        },
+        None => match pattern.parse::<IpAddr>() {
+            Ok(pattern) => to_canonical(pattern) == to_canonical(ip),
+            Err(_) => false,
+        },
+    }
+}
+
+fn matches_host(pattern: &str, host: &str) -> bool {
+    let pattern = normalize_host(pattern.trim());
+
+    if pattern == "*" {
+        return true;
+    }
+
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return matches_ip(&pattern, ip);
+    }
+
+    match pattern.strip_prefix("*.") {
+        Some(domain) => host.ends_with(&format!(".{}", domain)),
+        None => pattern == host,
+    }
+}
+
+fn is_private_ip(ip: IpAddr) -> bool {
+    match to_canonical(ip) {
+        IpAddr::V4(ip) => {
+            let octets = ip.octets();
+
+            ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_multicast()
+                // 0.0.0.0/8, "this" network
+                || octets[0] == 0
+                // 100.64.0.0/10, shared address space
+                || (octets[0] == 100 && octets[1] & 0xc0 == 64)
+                // 198.18.0.0/15, benchmarking
+                || (octets[0] == 198 && octets[1] & 0xfe == 18)
+                // 240.0.0.0/4, reserved, with the broadcast address
+                || octets[0] & 0xf0 == 240
+        }
+        IpAddr::V6(ip) => {
+            let segments = ip.segments();
+
+            ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                // fc00::/7, unique local addresses
+                || segments[0] & 0xfe00 == 0xfc00
+                // fe80::/10, link-local addresses
+                || segments[0] & 0xffc0 == 0xfe80
+                // 64:ff9b::/96, NAT64 of the embedded IPv4 address
+                || (segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0]
+                    && is_private_ip(IpAddr::V4(Ipv4Addr::new(
+                        (segments[6] >> 8) as u8,
+                        segments[6] as u8,
+                        (segments[7] >> 8) as u8,
+                        segments[7] as u8,
+                    ))))
+        }
+    }
+}
+
+fn policy_error(err: String) -> io::Error {
+    io::Error::new(io::ErrorKind::PermissionDenied, err)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// STATIC FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+impl NetworkPolicy {
+    /**
+     * A policy that can not be read denies all the hosts, so a mistake in the
+     * configuration does not open the network.
+     */
+    pub fn deny_all() -> Self {
+        let hosts = HostPolicy {
+            allowed: vec![],
+            denied: vec!["*".to_owned()],
+        };
+
+        Self {
+            http: hosts.clone(),
+            smtp: hosts,
+            block_private_networks: true,
+            forbid_disable_ssl_verify: true,
+        }
+    }
+
+    pub fn from_env() -> Option<Self> {
+        let value = env::var("CSML_NETWORK_POLICY").ok()?;
+
+        match serde_json::from_str(&value) {
+            Ok(policy) => Some(policy),
+            Err(err) => {
+                csml_logger(
+                    CsmlLog::new(
+                        None,
+                        None,
+                        None,
+                        format!("invalid CSML_NETWORK_POLICY, all hosts are denied: {}", err),
+                    ),
+                    LogLvl::Error,
+                );
+
+                Some(Self::deny_all())
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// METHOD FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+impl NetworkPolicy {
+    fn get_hosts(&self, service: NetworkService) -> &HostPolicy {
+        match service {
+            NetworkService::Http => &self.http,
+            NetworkService::Smtp => &self.smtp,
+        }
+    }
+
+    /**
+     * Hosts explicitly allowed can be in private networks
+     */
+    fn is_explicitly_allowed(&self, service: NetworkService, host: &str, ip: IpAddr) -> bool {
+        self.get_hosts(service).allowed.iter().any(|pattern| {
+            let pattern = pattern.trim();
+
+            pattern != "*" && (matches_host(pattern, host) || matches_ip(pattern, ip))
+        })
+    }
+
+    pub fn check_host(&self, service: NetworkService, host: &str) -> Result<(), String> {
+        let host = normalize_host(host);
+        let hosts = self.get_hosts(service);
+
+        if hosts
+            .denied
+            .iter()
+            .any(|pattern| matches_host(pattern, &host))
+        {
+            return Err(format!("host '{}' is denied", host));
+        }
+
+        if !hosts.allowed.is_empty()
+            && !hosts
+                .allowed
+                .iter()
+                .any(|pattern| matches_host(pattern, &host))
+        {
+            return Err(format!("host '{}' is not allowed", host));
+        }
+
+        match host.parse::<IpAddr>() {
+            Ok(ip) => self.check_ip(service, &host, ip),
+            Err(_) => Ok(()),
+        }
+    }
+
+    /**
+     * Addresses of the host, checked once it is resolved
+     */
+    pub fn check_ip(&self, service: NetworkService, host: &str, ip: IpAddr) -> Result<(), String> {
+        let host = normalize_host(host);
+
+        if self
+            .get_hosts(service)
+            .denied
+            .iter()
+            .any(|pattern| matches_ip(pattern.trim(), ip))
+        {
+            return Err(format!("address {} of host '{}' is denied", ip, host));
+        }
+
+        if self.block_private_networks
+            && is_private_ip(ip)
+            && !self.is_explicitly_allowed(service, &host, ip)
+        {
+            return Err(format!(
+                "address {} of host '{}' is in a private network",
+                ip, host
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PUBLIC FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+pub fn check_host(
+    policies: &[NetworkPolicy],
+    service: NetworkService,
+    host: &str,
+) -> Result<(), String> {
+    for policy in policies.iter() {
+        policy.check_host(service, host)?;
+    }
+
+    Ok(())
+}
+
+pub fn allows_disable_ssl_verify(policies: &[NetworkPolicy]) -> bool {
+    !policies
+        .iter()
+        .any(|policy| policy.forbid_disable_ssl_verify)
+}
+
+/**
+ * Addresses of the host allowed by the policies, the call fails if none of them is.
+ */
+pub fn resolve(
+    policies: &[NetworkPolicy],
+    service: NetworkService,
+    host: &str,
+    port: u16,
+) -> Result<Vec<SocketAddr>, String> {
+    check_host(policies, service, host)?;
+
+    let host = normalize_host(host);
+    let addresses: Vec<SocketAddr> = match host.parse::<IpAddr>() {
+        Ok(ip) => vec![SocketAddr::new(ip, port)],
+        Err(_) => (host.as_str(), port)
+            .to_socket_addrs()
+            .map_err(|err| format!("failed to resolve host '{}': {}", host, err))?
+            .collect(),
+    };
+
+    let mut error = None;
+    let allowed: Vec<SocketAddr> = addresses
+        .into_iter()
+        .filter(|address| {
+            policies.iter().all(
+                |policy| match policy.check_ip(service, &host, address.ip()) {
+                    Ok(_) => true,
+                    Err(err) => {
+                        error.get_or_insert(err);
+                        false
+                    }
+                },
+            )
+        })
+        .collect();
+
+    match (allowed.is_empty(), error) {
+        (true, Some(err)) => Err(err),
+        _ => Ok(allowed),
+    }
+}
+
+/**
+ * Resolver of the HTTP calls, the policies are checked on every connection so
+ * redirections and DNS changes after the first check are also covered.
+ */
+pub fn resolve_netloc(policies: &[NetworkPolicy], netloc: &str) -> io::Result<Vec<SocketAddr>> {
+    let (host, port) = match netloc.rsplit_once(':') {
+        Some((host, port)) => match port.parse::<u16>() {
+            Ok(port) => (host, port),
+            Err(_) => (netloc, 80),
+        },
+        None => (netloc, 80),
+    };
+
+    resolve(policies, NetworkService::Http, host, port).map_err(policy_error)
+}
+
+/**
+ * Checks the host of the url before the call, so a call that is not allowed fails
+ * with the reason instead of a connection error.
+ */
+pub fn check_url(policies: &[NetworkPolicy], url: &str) -> Result<(), String> {
+    if policies.is_empty() {
+        return Ok(());
+    }
+
+    let url = url::Url::parse(url).map_err(|err| format!("invalid url: {}", err))?;
+    let host = match url.host_str() {
+        Some(host) => host,
+        None => return Err("url without host".to_owned()),
+    };
+    let port = url.port_or_known_default().unwrap_or(80);
+
+    resolve(policies, NetworkService::Http, host, port).map(|_| ())
+}
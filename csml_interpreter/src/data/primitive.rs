@@ -1,6 +1,7 @@
 pub mod array;
 pub mod boolean;
 pub mod closure;
+pub mod decimal;
 pub mod float;
 pub mod int;
 pub mod null;
@@ -10,6 +11,8 @@ pub mod string;
 pub mod tools;
 pub mod tools_crypto;
 pub mod tools_jwt;
+pub mod tools_locale;
+pub mod tools_query;
 pub mod tools_smtp;
 pub mod tools_time;
 
@@ -17,6 +20,7 @@ use crate::data::literal::ContentType;
 pub use array::PrimitiveArray;
 pub use boolean::PrimitiveBoolean;
 pub use closure::PrimitiveClosure;
+pub use decimal::PrimitiveDecimal;
 pub use float::PrimitiveFloat;
 pub use int::PrimitiveInt;
 pub use null::PrimitiveNull;
@@ -45,6 +49,7 @@ pub enum Right {
 pub enum PrimitiveType {
     PrimitiveArray,
     PrimitiveBoolean,
+    PrimitiveDecimal,
     PrimitiveFloat,
     PrimitiveInt,
     PrimitiveNull,
@@ -88,6 +93,27 @@ pub trait Primitive: Send {
     ) -> Result<(Literal, Right), ErrorInfo>;
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+/**
+ * As soon as one operand is a decimal the other one is converted to a decimal,
+ * so that ints, floats and numeric strings can be used with decimals.
+ */
+fn get_decimal_operands(
+    lhs: &dyn Primitive,
+    rhs: &dyn Primitive,
+) -> Option<(PrimitiveDecimal, PrimitiveDecimal)> {
+    match (
+        PrimitiveDecimal::from_primitive(lhs),
+        PrimitiveDecimal::from_primitive(rhs),
+    ) {
+        (Ok(lhs), Ok(rhs)) => Some((lhs, rhs)),
+        _ => None,
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // PUBLIC FUNCTIONS
 ////////////////////////////////////////////////////////////////////////////////
@@ -97,6 +123,7 @@ impl PrimitiveType {
         match self {
             PrimitiveType::PrimitiveArray => "array".to_owned(),
             PrimitiveType::PrimitiveBoolean => "boolean".to_owned(),
+            PrimitiveType::PrimitiveDecimal => "decimal".to_owned(),
             PrimitiveType::PrimitiveFloat => "float".to_owned(),
             PrimitiveType::PrimitiveInt => "int".to_owned(),
             PrimitiveType::PrimitiveNull => "null".to_owned(),
@@ -162,6 +189,12 @@ impl PartialEq for dyn Primitive {
     fn eq(&self, other: &Self) -> bool {
         match (self.get_type(), other.get_type()) {
             (lhs, rhs) if lhs == rhs => self.is_eq(other),
+            (lhs, rhs)
+                if lhs == PrimitiveType::PrimitiveDecimal
+                    || rhs == PrimitiveType::PrimitiveDecimal =>
+            {
+                get_decimal_operands(self, other).map_or(false, |(lhs, rhs)| lhs.value == rhs.value)
+            }
             (lhs, rhs)
                 if lhs == PrimitiveType::PrimitiveInt && rhs == PrimitiveType::PrimitiveFloat =>
             {
@@ -237,6 +270,13 @@ impl PartialOrd for dyn Primitive {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match (self.get_type(), other.get_type()) {
             (lhs, rhs) if lhs == rhs => self.is_cmp(other),
+            (lhs, rhs)
+                if lhs == PrimitiveType::PrimitiveDecimal
+                    || rhs == PrimitiveType::PrimitiveDecimal =>
+            {
+                get_decimal_operands(self, other)
+                    .and_then(|(lhs, rhs)| lhs.value.partial_cmp(&rhs.value))
+            }
             (lhs, rhs)
                 if lhs == PrimitiveType::PrimitiveInt && rhs == PrimitiveType::PrimitiveFloat =>
             {
@@ -320,6 +360,20 @@ impl Add for Box<dyn Primitive> {
     fn add(self, other: Self) -> Result<Self, String> {
         match (self.get_type(), other.get_type()) {
             (lhs, rhs) if lhs == rhs => self.do_add(&(*other)),
+            (lhs, rhs)
+                if lhs == PrimitiveType::PrimitiveDecimal
+                    || rhs == PrimitiveType::PrimitiveDecimal =>
+            {
+                match get_decimal_operands(&*self, &*other) {
+                    Some((lhs, rhs)) => lhs.do_add(&rhs),
+                    None => Err(format!(
+                        "{} {:?} + {:?}",
+                        ERROR_ILLEGAL_OPERATION,
+                        self.get_type(),
+                        other.get_type()
+                    )),
+                }
+            }
             (lhs, rhs)
                 if lhs == PrimitiveType::PrimitiveInt && rhs == PrimitiveType::PrimitiveFloat =>
             {
@@ -431,6 +485,20 @@ impl Sub for Box<dyn Primitive> {
     fn sub(self, other: Self) -> Result<Self, String> {
         match (self.get_type(), other.get_type()) {
             (lhs, rhs) if lhs == rhs => self.do_sub(&(*other)),
+            (lhs, rhs)
+                if lhs == PrimitiveType::PrimitiveDecimal
+                    || rhs == PrimitiveType::PrimitiveDecimal =>
+            {
+                match get_decimal_operands(&*self, &*other) {
+                    Some((lhs, rhs)) => lhs.do_sub(&rhs),
+                    None => Err(format!(
+                        "{} {:?} - {:?}",
+                        ERROR_ILLEGAL_OPERATION,
+                        self.get_type(),
+                        other.get_type()
+                    )),
+                }
+            }
             (lhs, rhs)
                 if lhs == PrimitiveType::PrimitiveInt && rhs == PrimitiveType::PrimitiveFloat =>
             {
@@ -541,6 +609,20 @@ impl Div for Box<dyn Primitive> {
     fn div(self, other: Self) -> Result<Self, String> {
         match (self.get_type(), other.get_type()) {
             (lhs, rhs) if lhs == rhs => self.do_div(&(*other)),
+            (lhs, rhs)
+                if lhs == PrimitiveType::PrimitiveDecimal
+                    || rhs == PrimitiveType::PrimitiveDecimal =>
+            {
+                match get_decimal_operands(&*self, &*other) {
+                    Some((lhs, rhs)) => lhs.do_div(&rhs),
+                    None => Err(format!(
+                        "{} {:?} / {:?}",
+                        ERROR_ILLEGAL_OPERATION,
+                        self.get_type(),
+                        other.get_type()
+                    )),
+                }
+            }
             (lhs, rhs)
                 if lhs == PrimitiveType::PrimitiveInt && rhs == PrimitiveType::PrimitiveFloat =>
             {
@@ -651,6 +733,20 @@ impl Mul for Box<dyn Primitive> {
     fn mul(self, other: Self) -> Result<Self, String> {
         match (self.get_type(), other.get_type()) {
             (lhs, rhs) if lhs == rhs => self.do_mul(&(*other)),
+            (lhs, rhs)
+                if lhs == PrimitiveType::PrimitiveDecimal
+                    || rhs == PrimitiveType::PrimitiveDecimal =>
+            {
+                match get_decimal_operands(&*self, &*other) {
+                    Some((lhs, rhs)) => lhs.do_mul(&rhs),
+                    None => Err(format!(
+                        "{} {:?} * {:?}",
+                        ERROR_ILLEGAL_OPERATION,
+                        self.get_type(),
+                        other.get_type()
+                    )),
+                }
+            }
             (lhs, rhs)
                 if lhs == PrimitiveType::PrimitiveInt && rhs == PrimitiveType::PrimitiveFloat =>
             {
@@ -761,6 +857,20 @@ impl Rem for Box<dyn Primitive> {
     fn rem(self, other: Self) -> Result<Self, String> {
         match (self.get_type(), other.get_type()) {
             (lhs, rhs) if lhs == rhs => self.do_rem(&(*other)),
+            (lhs, rhs)
+                if lhs == PrimitiveType::PrimitiveDecimal
+                    || rhs == PrimitiveType::PrimitiveDecimal =>
+            {
+                match get_decimal_operands(&*self, &*other) {
+                    Some((lhs, rhs)) => lhs.do_rem(&rhs),
+                    None => Err(format!(
+                        "{} {:?} % {:?}",
+                        ERROR_ILLEGAL_OPERATION,
+                        self.get_type(),
+                        other.get_type()
+                    )),
+                }
+            }
             (lhs, rhs)
                 if lhs == PrimitiveType::PrimitiveInt && rhs == PrimitiveType::PrimitiveFloat =>
             {
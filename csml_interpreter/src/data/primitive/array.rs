@@ -4,8 +4,9 @@ use crate::data::{
     literal,
     literal::ContentType,
     primitive::{
-        Primitive, PrimitiveBoolean, PrimitiveClosure, PrimitiveInt, PrimitiveNull,
-        PrimitiveString, PrimitiveType, Right,
+        closure::{exec_closure_on_values, find_closure_value},
+        tools, tools_query, Primitive, PrimitiveBoolean, PrimitiveClosure, PrimitiveFloat,
+        PrimitiveInt, PrimitiveNull, PrimitiveObject, PrimitiveString, PrimitiveType, Right,
     },
     tokens::TYPES,
     ArgsType, Interval, Literal, MemoryType, Message, MessageData, MSG,
@@ -65,6 +66,23 @@ const FUNCTIONS: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map! {
     "reverse" => (PrimitiveArray::reverse as PrimitiveMethod, Right::Read),
     "append" => (PrimitiveArray::append as PrimitiveMethod, Right::Read),
     "flatten" => (PrimitiveArray::flatten as PrimitiveMethod, Right::Read),
+    "sort" => (PrimitiveArray::sort as PrimitiveMethod, Right::Read),
+    "sort_by" => (PrimitiveArray::sort_by as PrimitiveMethod, Right::Read),
+    "unique" => (PrimitiveArray::unique as PrimitiveMethod, Right::Read),
+    "group_by" => (PrimitiveArray::group_by as PrimitiveMethod, Right::Read),
+    "zip" => (PrimitiveArray::zip as PrimitiveMethod, Right::Read),
+    "chunk" => (PrimitiveArray::chunk as PrimitiveMethod, Right::Read),
+    "find_index" => (PrimitiveArray::find_index as PrimitiveMethod, Right::Read),
+    "some" => (PrimitiveArray::some as PrimitiveMethod, Right::Read),
+    "every" => (PrimitiveArray::every as PrimitiveMethod, Right::Read),
+    "min" => (PrimitiveArray::min as PrimitiveMethod, Right::Read),
+    "max" => (PrimitiveArray::max as PrimitiveMethod, Right::Read),
+    "sum" => (PrimitiveArray::sum as PrimitiveMethod, Right::Read),
+    "intersection" => (PrimitiveArray::intersection as PrimitiveMethod, Right::Read),
+    "difference" => (PrimitiveArray::difference as PrimitiveMethod, Right::Read),
+    "from_entries" => (PrimitiveArray::from_entries as PrimitiveMethod, Right::Read),
+    "query" => (PrimitiveArray::jmespath_query as PrimitiveMethod, Right::Read),
+    "to_csv" => (PrimitiveArray::to_csv as PrimitiveMethod, Right::Read),
 };
 
 #[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
@@ -99,6 +117,97 @@ fn check_index(
     Ok(())
 }
 
+fn get_closure<'a>(
+    args: &'a HashMap<String, Literal>,
+    usage: &str,
+    data: &Data,
+    interval: Interval,
+) -> Result<&'a PrimitiveClosure, ErrorInfo> {
+    match args.get("arg0") {
+        Some(lit) => Literal::get_value::<PrimitiveClosure>(
+            &lit.primitive,
+            &data.context.flow,
+            interval,
+            format!("usage: {}", usage),
+        ),
+        None => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            format!("usage: {}", usage),
+        )),
+    }
+}
+
+fn get_other_array(
+    args: &HashMap<String, Literal>,
+    error: &str,
+    data: &Data,
+    interval: Interval,
+) -> Result<Vec<Literal>, ErrorInfo> {
+    match args.get("arg0") {
+        Some(res) if args.len() == 1 && res.content_type == "array" => {
+            let value = Literal::get_value::<Vec<Literal>>(
+                &res.primitive,
+                &data.context.flow,
+                interval,
+                error.to_owned(),
+            )?;
+
+            Ok(value.to_owned())
+        }
+        _ => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            error.to_owned(),
+        )),
+    }
+}
+
+fn indexed_values(values: &[Literal], interval: Interval) -> Vec<(Literal, Literal)> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            (
+                value.to_owned(),
+                PrimitiveInt::get_literal(index as i64, interval),
+            )
+        })
+        .collect()
+}
+
+fn is_nan(lit: &Literal) -> bool {
+    match lit.primitive.as_any().downcast_ref::<PrimitiveFloat>() {
+        Some(float) => float.value.is_nan(),
+        None => false,
+    }
+}
+
+fn get_type_rank(lit: &Literal) -> String {
+    match lit.primitive.get_type() {
+        PrimitiveType::PrimitiveInt
+        | PrimitiveType::PrimitiveFloat
+        | PrimitiveType::PrimitiveDecimal => "number".to_owned(),
+        primitive_type => primitive_type.to_string(),
+    }
+}
+
+/**
+ * Total order of the values for sort and sort_by: values that can not be compared
+ * are ranked by the name of their type, numbers together, and NaN is last.
+ */
+fn compare_literals(lhs: &Literal, rhs: &Literal) -> Ordering {
+    match (is_nan(lhs), is_nan(rhs)) {
+        (true, true) => return Ordering::Equal,
+        (true, false) => return Ordering::Greater,
+        (false, true) => return Ordering::Less,
+        (false, false) => {}
+    }
+
+    match lhs.partial_cmp(rhs) {
+        Some(ordering) => ordering,
+        None => get_type_rank(lhs).cmp(&get_type_rank(rhs)),
+    }
+}
+
 impl PrimitiveArray {
     fn is_number(
         _array: &mut PrimitiveArray,
@@ -1122,6 +1231,596 @@ impl PrimitiveArray {
     }
 }
 
+impl PrimitiveArray {
+    fn sort(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "sort() => [Literal]";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let mut sorted_list = array.value.clone();
+        sorted_list.sort_by(compare_literals);
+
+        Ok(PrimitiveArray::get_literal(&sorted_list, interval))
+    }
+
+    fn sort_by(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        msg_data: &mut MessageData,
+        sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage =
+            "sort_by(fn) expect one argument of type [Closure] returning the value to sort by";
+
+        let closure = get_closure(args, usage, data, interval)?;
+        let keys = exec_closure_on_values(
+            closure,
+            &indexed_values(&array.value, interval),
+            interval,
+            data,
+            msg_data,
+            sender,
+        )?;
+
+        let mut pairs: Vec<(Literal, Literal)> =
+            keys.into_iter().zip(array.value.iter().cloned()).collect();
+        pairs.sort_by(|(lhs, _), (rhs, _)| compare_literals(lhs, rhs));
+
+        let sorted_list: Vec<Literal> = pairs.into_iter().map(|(_, value)| value).collect();
+
+        Ok(PrimitiveArray::get_literal(&sorted_list, interval))
+    }
+
+    fn unique(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "unique() => [Literal]";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let mut unique_list: Vec<Literal> = vec![];
+
+        for value in array.value.iter() {
+            if !unique_list.contains(value) {
+                unique_list.push(value.to_owned());
+            }
+        }
+
+        Ok(PrimitiveArray::get_literal(&unique_list, interval))
+    }
+
+    fn group_by(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        msg_data: &mut MessageData,
+        sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage =
+            "group_by(fn) expect one argument of type [Closure] returning the key of the group";
+
+        let closure = get_closure(args, usage, data, interval)?;
+        let keys = exec_closure_on_values(
+            closure,
+            &indexed_values(&array.value, interval),
+            interval,
+            data,
+            msg_data,
+            sender,
+        )?;
+
+        let mut groups: HashMap<String, Vec<Literal>> = HashMap::new();
+
+        for (key, value) in keys.iter().zip(array.value.iter()) {
+            groups
+                .entry(key.primitive.to_string())
+                .or_insert_with(Vec::new)
+                .push(value.to_owned());
+        }
+
+        let groups: HashMap<String, Literal> = groups
+            .into_iter()
+            .map(|(key, values)| (key, PrimitiveArray::get_literal(&values, interval)))
+            .collect();
+
+        Ok(PrimitiveObject::get_literal(&groups, interval))
+    }
+
+    fn zip(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let other_array = get_other_array(args, ERROR_ARRAY_ZIP, data, interval)?;
+
+        let zipped_list: Vec<Literal> = array
+            .value
+            .iter()
+            .zip(other_array.iter())
+            .map(|(lhs, rhs)| {
+                PrimitiveArray::get_literal(&[lhs.to_owned(), rhs.to_owned()], interval)
+            })
+            .collect();
+
+        Ok(PrimitiveArray::get_literal(&zipped_list, interval))
+    }
+
+    fn chunk(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let size = match args.get("arg0") {
+            Some(res)
+                if args.len() == 1 && res.primitive.get_type() == PrimitiveType::PrimitiveInt =>
+            {
+                *Literal::get_value::<i64>(
+                    &res.primitive,
+                    &data.context.flow,
+                    interval,
+                    ERROR_ARRAY_CHUNK.to_owned(),
+                )?
+            }
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_ARRAY_CHUNK.to_owned(),
+                ));
+            }
+        };
+
+        if size <= 0 {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                ERROR_ARRAY_CHUNK.to_owned(),
+            ));
+        }
+
+        let chunks: Vec<Literal> = array
+            .value
+            .chunks(size as usize)
+            .map(|chunk| PrimitiveArray::get_literal(chunk, interval))
+            .collect();
+
+        Ok(PrimitiveArray::get_literal(&chunks, interval))
+    }
+
+    fn find_index(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        msg_data: &mut MessageData,
+        sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "find_index(fn) expect one argument of type [Closure]";
+
+        let closure = get_closure(args, usage, data, interval)?;
+        let found = find_closure_value(
+            closure,
+            &indexed_values(&array.value, interval),
+            true,
+            interval,
+            data,
+            msg_data,
+            sender,
+        )?;
+
+        let index = match found {
+            Some(index) => index as i64,
+            None => -1,
+        };
+
+        Ok(PrimitiveInt::get_literal(index, interval))
+    }
+
+    fn some(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        msg_data: &mut MessageData,
+        sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "some(fn) expect one argument of type [Closure]";
+
+        let closure = get_closure(args, usage, data, interval)?;
+        let found = find_closure_value(
+            closure,
+            &indexed_values(&array.value, interval),
+            true,
+            interval,
+            data,
+            msg_data,
+            sender,
+        )?;
+
+        let result = found.is_some();
+
+        Ok(PrimitiveBoolean::get_literal(result, interval))
+    }
+
+    fn every(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        msg_data: &mut MessageData,
+        sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "every(fn) expect one argument of type [Closure]";
+
+        let closure = get_closure(args, usage, data, interval)?;
+        let found = find_closure_value(
+            closure,
+            &indexed_values(&array.value, interval),
+            false,
+            interval,
+            data,
+            msg_data,
+            sender,
+        )?;
+
+        let result = found.is_none();
+
+        Ok(PrimitiveBoolean::get_literal(result, interval))
+    }
+
+    fn min(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "min() => Literal";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let result = array
+            .value
+            .iter()
+            .fold(None, |min: Option<&Literal>, value| match min {
+                Some(min) if value.partial_cmp(min) != Some(Ordering::Less) => Some(min),
+                _ => Some(value),
+            });
+
+        match result {
+            Some(min) => Ok(min.to_owned()),
+            None => Ok(PrimitiveNull::get_literal(interval)),
+        }
+    }
+
+    fn max(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "max() => Literal";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let result = array
+            .value
+            .iter()
+            .fold(None, |max: Option<&Literal>, value| match max {
+                Some(max) if value.partial_cmp(max) != Some(Ordering::Greater) => Some(max),
+                _ => Some(value),
+            });
+
+        match result {
+            Some(max) => Ok(max.to_owned()),
+            None => Ok(PrimitiveNull::get_literal(interval)),
+        }
+    }
+
+    fn sum(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "sum() => number";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let mut sum: Box<dyn Primitive> = Box::new(PrimitiveInt::new(0));
+
+        for value in array.value.iter() {
+            match value.primitive.get_type() {
+                PrimitiveType::PrimitiveInt
+                | PrimitiveType::PrimitiveFloat
+                | PrimitiveType::PrimitiveDecimal => {}
+                _ => {
+                    return Err(gen_error_info(
+                        Position::new(interval, &data.context.flow),
+                        ERROR_ARRAY_SUM.to_owned(),
+                    ))
+                }
+            }
+
+            sum = match sum + value.primitive.clone() {
+                Ok(sum) => sum,
+                Err(err) => {
+                    return Err(gen_error_info(
+                        Position::new(interval, &data.context.flow),
+                        err,
+                    ))
+                }
+            };
+        }
+
+        Ok(Literal {
+            content_type: sum.get_type().to_string(),
+            primitive: sum,
+            additional_info: None,
+            secure_variable: false,
+            interval,
+        })
+    }
+
+    fn intersection(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let other_array = get_other_array(args, ERROR_ARRAY_INTERSECTION, data, interval)?;
+
+        let mut result: Vec<Literal> = vec![];
+
+        for value in array.value.iter() {
+            if other_array.contains(value) && !result.contains(value) {
+                result.push(value.to_owned());
+            }
+        }
+
+        Ok(PrimitiveArray::get_literal(&result, interval))
+    }
+
+    fn difference(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let other_array = get_other_array(args, ERROR_ARRAY_DIFFERENCE, data, interval)?;
+
+        let mut result: Vec<Literal> = vec![];
+
+        for value in array.value.iter() {
+            if !other_array.contains(value) && !result.contains(value) {
+                result.push(value.to_owned());
+            }
+        }
+
+        Ok(PrimitiveArray::get_literal(&result, interval))
+    }
+    fn from_entries(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "from_entries() => Object";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let mut object = HashMap::new();
+
+        for entry in array.value.iter() {
+            let entry = Literal::get_value::<Vec<Literal>>(
+                &entry.primitive,
+                &data.context.flow,
+                interval,
+                ERROR_ARRAY_FROM_ENTRIES.to_owned(),
+            )?;
+
+            match entry.as_slice() {
+                [key, value] if key.primitive.get_type() == PrimitiveType::PrimitiveString => {
+                    object.insert(key.primitive.to_string(), value.to_owned());
+                }
+                _ => {
+                    return Err(gen_error_info(
+                        Position::new(interval, &data.context.flow),
+                        ERROR_ARRAY_FROM_ENTRIES.to_owned(),
+                    ));
+                }
+            }
+        }
+
+        Ok(PrimitiveObject::get_literal(&object, interval))
+    }
+
+    fn jmespath_query(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        tools_query::query(&array.to_json(), args, data, interval)
+    }
+
+    fn to_csv(
+        array: &mut PrimitiveArray,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let options = match args.get("arg0") {
+            Some(res)
+                if args.len() == 1
+                    && res.primitive.get_type() == PrimitiveType::PrimitiveObject =>
+            {
+                Literal::get_value::<HashMap<String, Literal>>(
+                    &res.primitive,
+                    &data.context.flow,
+                    interval,
+                    ERROR_ARRAY_TO_CSV.to_owned(),
+                )?
+                .to_owned()
+            }
+            None if args.is_empty() => HashMap::new(),
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_ARRAY_TO_CSV.to_owned(),
+                ));
+            }
+        };
+
+        let delimiter = match tools::get_csv_delimiter(&options) {
+            Some(delimiter) => delimiter,
+            None => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_ARRAY_TO_CSV.to_owned(),
+                ));
+            }
+        };
+
+        let mut objects = vec![];
+        for value in array.value.iter() {
+            let object = Literal::get_value::<HashMap<String, Literal>>(
+                &value.primitive,
+                &data.context.flow,
+                interval,
+                ERROR_ARRAY_TO_CSV.to_owned(),
+            )?;
+            objects.push(object);
+        }
+
+        // without explicit columns, every key found in the objects is a column
+        let columns: Vec<String> = match options.get("columns") {
+            Some(columns) => {
+                let columns = Literal::get_value::<Vec<Literal>>(
+                    &columns.primitive,
+                    &data.context.flow,
+                    interval,
+                    ERROR_ARRAY_TO_CSV.to_owned(),
+                )?;
+
+                columns
+                    .iter()
+                    .map(|column| column.primitive.to_string())
+                    .collect()
+            }
+            None => {
+                let mut columns: Vec<String> = objects
+                    .iter()
+                    .flat_map(|object| object.keys().cloned())
+                    .collect();
+                columns.sort();
+                columns.dedup();
+                columns
+            }
+        };
+
+        let mut rows = vec![columns.clone()];
+        for object in objects.iter() {
+            let row = columns
+                .iter()
+                .map(|column| match object.get(column) {
+                    Some(value) if value.primitive.get_type() != PrimitiveType::PrimitiveNull => {
+                        value.primitive.to_string()
+                    }
+                    _ => String::new(),
+                })
+                .collect();
+            rows.push(row);
+        }
+
+        Ok(PrimitiveString::get_literal(
+            &tools::rows_to_csv(&rows, delimiter),
+            interval,
+        ))
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // PUBLIC FUNCTIONS
 ////////////////////////////////////////////////////////////////////////////////
@@ -8,10 +8,14 @@ use crate::data::primitive::Right;
 use crate::data::primitive::{Primitive, PrimitiveType};
 use crate::data::{
     ast::{Expr, Interval},
+    data::{init_child_context, init_child_scope},
     message::Message,
-    Data, Literal, MemoryType, MessageData, MSG,
+    ArgsType, Data, Literal, MemoryType, MessageData, MSG,
 };
 use crate::error_format::*;
+use crate::interpreter::variable_handler::resolve_csml_object::{
+    exec_closure, insert_args_in_scope_memory, insert_memories_in_scope_memory,
+};
 use phf::phf_map;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
@@ -23,7 +27,7 @@ pub fn capture_variables(
     flow_name: &str,
 ) {
     if literal.content_type == "closure" {
-        let mut closure = Literal::get_mut_value::<PrimitiveClosure>(
+        let closure = Literal::get_mut_value::<PrimitiveClosure>(
             &mut literal.primitive,
             flow_name,
             literal.interval,
@@ -34,6 +38,125 @@ pub fn capture_variables(
     }
 }
 
+/**
+ * Call the closure for each (value, second) pair, in order, until `on_result` returns false.
+ * The second argument (the index of an array value or the key of an object value)
+ * is only given if the closure takes two arguments.
+ */
+fn exec_closure_while<F>(
+    closure: &PrimitiveClosure,
+    values: &[(Literal, Literal)],
+    interval: Interval,
+    data: &mut Data,
+    msg_data: &mut MessageData,
+    sender: &Option<mpsc::Sender<MSG>>,
+    mut on_result: F,
+) -> Result<(), ErrorInfo>
+where
+    F: FnMut(Literal) -> bool,
+{
+    let mut context = init_child_context(&data);
+    let mut step_count = data.step_count.clone();
+    let mut new_scope_data = init_child_scope(data, &mut context, &mut step_count);
+
+    if let Some(memories) = closure.enclosed_variables.clone() {
+        insert_memories_in_scope_memory(&mut new_scope_data, memories, msg_data, sender);
+    }
+
+    for (value, second) in values.iter() {
+        let mut map = HashMap::new();
+        map.insert("arg0".to_owned(), value.to_owned());
+        if closure.args.len() >= 2 {
+            map.insert("arg1".to_owned(), second.to_owned());
+        }
+
+        let args = ArgsType::Normal(map);
+        insert_args_in_scope_memory(&mut new_scope_data, &closure.args, &args, msg_data, sender);
+
+        let result = exec_closure(
+            &closure.func,
+            &closure.args,
+            args,
+            interval,
+            &mut new_scope_data,
+            msg_data,
+            sender,
+        )?;
+
+        if !on_result(result) {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/**
+ * Call the closure once for each (value, second) pair and return the results in the same order.
+ */
+pub fn exec_closure_on_values(
+    closure: &PrimitiveClosure,
+    values: &[(Literal, Literal)],
+    interval: Interval,
+    data: &mut Data,
+    msg_data: &mut MessageData,
+    sender: &Option<mpsc::Sender<MSG>>,
+) -> Result<Vec<Literal>, ErrorInfo> {
+    let mut results = vec![];
+
+    exec_closure_while(
+        closure,
+        values,
+        interval,
+        data,
+        msg_data,
+        sender,
+        |result| {
+            results.push(result);
+            true
+        },
+    )?;
+
+    Ok(results)
+}
+
+/**
+ * Index of the first value for which the result of the closure is `expected`,
+ * the closure is not called on the values after it.
+ */
+pub fn find_closure_value(
+    closure: &PrimitiveClosure,
+    values: &[(Literal, Literal)],
+    expected: bool,
+    interval: Interval,
+    data: &mut Data,
+    msg_data: &mut MessageData,
+    sender: &Option<mpsc::Sender<MSG>>,
+) -> Result<Option<usize>, ErrorInfo> {
+    let mut index = 0;
+    let mut found = None;
+
+    exec_closure_while(
+        closure,
+        values,
+        interval,
+        data,
+        msg_data,
+        sender,
+        |result| {
+            if result.primitive.as_bool() == expected {
+                found = Some(index);
+                return false;
+            }
+
+            index += 1;
+            true
+        },
+    )?;
+
+    Ok(found)
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // DATA STRUCTURES
 ////////////////////////////////////////////////////////////////////////////////
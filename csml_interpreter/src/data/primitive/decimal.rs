@@ -0,0 +1,727 @@
+use crate::data::error_info::ErrorInfo;
+use crate::data::position::Position;
+use crate::data::primitive::boolean::PrimitiveBoolean;
+use crate::data::primitive::float::PrimitiveFloat;
+use crate::data::primitive::int::PrimitiveInt;
+use crate::data::primitive::object::PrimitiveObject;
+use crate::data::primitive::string::PrimitiveString;
+use crate::data::primitive::tools_locale;
+use crate::data::primitive::Right;
+use crate::data::primitive::{Primitive, PrimitiveType};
+use crate::data::{ast::Interval, message::Message, Data, Literal, MemoryType, MessageData, MSG};
+use crate::data::{literal, literal::ContentType};
+use crate::error_format::*;
+use bigdecimal::{BigDecimal, RoundingMode};
+use phf::phf_map;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::str::FromStr;
+use std::{collections::HashMap, sync::mpsc};
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURES
+////////////////////////////////////////////////////////////////////////////////
+
+type PrimitiveMethod = fn(
+    decimal: &mut PrimitiveDecimal,
+    args: &HashMap<String, Literal>,
+    additional_info: &Option<HashMap<String, Literal>>,
+    data: &mut Data,
+    interval: Interval,
+) -> Result<Literal, ErrorInfo>;
+
+const FUNCTIONS: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map! {
+    "is_number" => (PrimitiveDecimal::is_number as PrimitiveMethod, Right::Read),
+    "is_int" => (PrimitiveDecimal::is_int as PrimitiveMethod, Right::Read),
+    "is_float" => (PrimitiveDecimal::is_float as PrimitiveMethod, Right::Read),
+    "type_of" => (PrimitiveDecimal::type_of as PrimitiveMethod, Right::Read),
+    "is_error" => (PrimitiveDecimal::is_error as PrimitiveMethod, Right::Read),
+    "get_info" => (PrimitiveDecimal::get_info as PrimitiveMethod, Right::Read),
+    "to_string" => (PrimitiveDecimal::to_string as PrimitiveMethod, Right::Read),
+    "format_number" => (PrimitiveDecimal::format_number as PrimitiveMethod, Right::Read),
+    "format_currency" => (PrimitiveDecimal::format_currency as PrimitiveMethod, Right::Read),
+
+    "abs" => (PrimitiveDecimal::abs as PrimitiveMethod, Right::Read),
+    "ceil" => (PrimitiveDecimal::ceil as PrimitiveMethod, Right::Read),
+    "floor" => (PrimitiveDecimal::floor as PrimitiveMethod, Right::Read),
+    "round" => (PrimitiveDecimal::round as PrimitiveMethod, Right::Read),
+    "scale" => (PrimitiveDecimal::scale as PrimitiveMethod, Right::Read),
+    "to_int" => (PrimitiveDecimal::to_int as PrimitiveMethod, Right::Read),
+    "to_float" => (PrimitiveDecimal::to_float as PrimitiveMethod, Right::Read),
+};
+
+// rounding used when no mode is given, also known as banker's rounding
+pub const DEFAULT_ROUNDING: &str = "half_even";
+
+// larger exponents and scales make the operations on decimals build huge integers
+const MAX_DECIMAL_EXPONENT: i64 = 1000;
+pub const MAX_DECIMAL_SCALE: i64 = 100;
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct PrimitiveDecimal {
+    pub value: BigDecimal,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+fn get_scale(
+    args: &HashMap<String, Literal>,
+    usage: &str,
+    data: &Data,
+    interval: Interval,
+) -> Result<i64, ErrorInfo> {
+    match args.get("arg0") {
+        Some(scale) if scale.primitive.get_type() == PrimitiveType::PrimitiveInt => {
+            let scale = *Literal::get_value::<i64>(
+                &scale.primitive,
+                &data.context.flow,
+                scale.interval,
+                format!("usage: {}", usage),
+            )?;
+
+            match (0..=MAX_DECIMAL_SCALE).contains(&scale) {
+                true => Ok(scale),
+                false => Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_DECIMAL_SCALE.to_owned(),
+                )),
+            }
+        }
+        Some(_) => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            format!("usage: {}", usage),
+        )),
+        None => Ok(0),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// METHOD FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+impl PrimitiveDecimal {
+    fn is_number(
+        _decimal: &mut PrimitiveDecimal,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "is_number() => boolean";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        Ok(PrimitiveBoolean::get_literal(true, interval))
+    }
+
+    fn is_int(
+        _decimal: &mut PrimitiveDecimal,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "is_int() => boolean";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        Ok(PrimitiveBoolean::get_literal(false, interval))
+    }
+
+    fn is_float(
+        _decimal: &mut PrimitiveDecimal,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "is_float() => boolean";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        Ok(PrimitiveBoolean::get_literal(false, interval))
+    }
+
+    fn type_of(
+        _decimal: &mut PrimitiveDecimal,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "type_of() => string";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        Ok(PrimitiveString::get_literal("decimal", interval))
+    }
+
+    fn get_info(
+        _decimal: &mut PrimitiveDecimal,
+        args: &HashMap<String, Literal>,
+        additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        literal::get_info(args, additional_info, interval, data)
+    }
+
+    fn is_error(
+        _decimal: &mut PrimitiveDecimal,
+        _args: &HashMap<String, Literal>,
+        additional_info: &Option<HashMap<String, Literal>>,
+        _data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        match additional_info {
+            Some(map) if map.contains_key("error") => {
+                Ok(PrimitiveBoolean::get_literal(true, interval))
+            }
+            _ => Ok(PrimitiveBoolean::get_literal(false, interval)),
+        }
+    }
+
+    fn to_string(
+        decimal: &mut PrimitiveDecimal,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "to_string() => string";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        Ok(PrimitiveString::get_literal(
+            &decimal.value.to_string(),
+            interval,
+        ))
+    }
+
+    fn format_number(
+        decimal: &mut PrimitiveDecimal,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let value = decimal.value.clone();
+
+        tools_locale::format_number(
+            args,
+            data,
+            interval,
+            tools_locale::MAX_DECIMALS,
+            &|decimals: Option<u32>| match decimals {
+                Some(decimals) => value
+                    .with_scale_round(decimals as i64, RoundingMode::HalfUp)
+                    .to_string(),
+                None => value.to_string(),
+            },
+        )
+    }
+
+    fn format_currency(
+        decimal: &mut PrimitiveDecimal,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let value = decimal.value.clone();
+
+        tools_locale::format_currency(
+            args,
+            data,
+            interval,
+            &|decimals: Option<u32>| match decimals {
+                Some(decimals) => value
+                    .with_scale_round(decimals as i64, RoundingMode::HalfUp)
+                    .to_string(),
+                None => value.to_string(),
+            },
+        )
+    }
+}
+
+impl PrimitiveDecimal {
+    fn abs(
+        decimal: &mut PrimitiveDecimal,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "abs() => decimal";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        Ok(PrimitiveDecimal::get_literal(decimal.value.abs(), interval))
+    }
+
+    fn ceil(
+        decimal: &mut PrimitiveDecimal,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "ceil() => decimal";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let result = decimal.value.with_scale_round(0, RoundingMode::Ceiling);
+
+        Ok(PrimitiveDecimal::get_literal(result, interval))
+    }
+
+    fn floor(
+        decimal: &mut PrimitiveDecimal,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "floor() => decimal";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let result = decimal.value.with_scale_round(0, RoundingMode::Floor);
+
+        Ok(PrimitiveDecimal::get_literal(result, interval))
+    }
+
+    fn round(
+        decimal: &mut PrimitiveDecimal,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "round(scale: int = 0, rounding: string = \"half_even\") => decimal";
+
+        if args.len() > 2 {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let scale = get_scale(args, usage, data, interval)?;
+
+        let rounding = match args.get("arg1") {
+            Some(rounding) => rounding.primitive.to_string(),
+            None => DEFAULT_ROUNDING.to_owned(),
+        };
+        let rounding = match get_rounding_mode(&rounding) {
+            Some(rounding) => rounding,
+            None => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_DECIMAL_ROUNDING.to_owned(),
+                ))
+            }
+        };
+
+        let result = decimal.value.with_scale_round(scale, rounding);
+
+        Ok(PrimitiveDecimal::get_literal(result, interval))
+    }
+
+    fn scale(
+        decimal: &mut PrimitiveDecimal,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "scale() => int";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let (_, scale) = decimal.value.as_bigint_and_exponent();
+
+        Ok(PrimitiveInt::get_literal(scale.max(0), interval))
+    }
+
+    fn to_int(
+        decimal: &mut PrimitiveDecimal,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "to_int() => int";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let int = decimal
+            .value
+            .with_scale_round(0, RoundingMode::Down)
+            .to_string()
+            .parse::<i64>();
+
+        match int {
+            Ok(int) => Ok(PrimitiveInt::get_literal(int, interval)),
+            Err(_) => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("{} {}", OVERFLOWING_OPERATION, usage),
+            )),
+        }
+    }
+
+    fn to_float(
+        decimal: &mut PrimitiveDecimal,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "to_float() => float";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let float = decimal.value.to_string().parse::<f64>().unwrap_or(f64::NAN);
+
+        Ok(PrimitiveFloat::get_literal(float, interval))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PUBLIC FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+/**
+ * Rounding modes accepted by the Decimal builtin and the round() method
+ */
+pub fn get_rounding_mode(mode: &str) -> Option<RoundingMode> {
+    match mode.to_lowercase().as_str() {
+        "up" => Some(RoundingMode::Up),
+        "down" => Some(RoundingMode::Down),
+        "ceiling" => Some(RoundingMode::Ceiling),
+        "floor" => Some(RoundingMode::Floor),
+        "half_up" => Some(RoundingMode::HalfUp),
+        "half_down" => Some(RoundingMode::HalfDown),
+        "half_even" => Some(RoundingMode::HalfEven),
+        _ => None,
+    }
+}
+
+impl PrimitiveDecimal {
+    pub fn new(value: BigDecimal) -> Self {
+        Self { value }
+    }
+
+    pub fn get_literal(decimal: BigDecimal, interval: Interval) -> Literal {
+        let primitive = Box::new(PrimitiveDecimal::new(decimal));
+
+        Literal {
+            content_type: "decimal".to_owned(),
+            primitive,
+            additional_info: None,
+            secure_variable: false,
+            interval,
+        }
+    }
+
+    /**
+     * Numbers with an exponent out of range are rejected, "1e-100000000" would
+     * otherwise be aligned on a 10^8 digits integer by the operations
+     */
+    pub fn parse(value: &str) -> Result<Self, &'static str> {
+        let value = BigDecimal::from_str(value.trim()).map_err(|_| ERROR_DECIMAL)?;
+        let (_, exponent) = value.as_bigint_and_exponent();
+
+        match (-MAX_DECIMAL_EXPONENT..=MAX_DECIMAL_EXPONENT).contains(&exponent) {
+            true => Ok(Self::new(value)),
+            false => Err(ERROR_DECIMAL_EXPONENT),
+        }
+    }
+
+    /**
+     * Convert the operand of an operation with a decimal, floats are converted
+     * from their shortest representation so that 0.1 stays 0.1
+     */
+    pub fn from_primitive(primitive: &dyn Primitive) -> Result<Self, &'static str> {
+        if let Some(decimal) = primitive.as_any().downcast_ref::<Self>() {
+            return Ok(decimal.to_owned());
+        }
+
+        match primitive.get_type() {
+            PrimitiveType::PrimitiveInt
+            | PrimitiveType::PrimitiveFloat
+            | PrimitiveType::PrimitiveString => Self::parse(&primitive.to_string()),
+            _ => Err(ERROR_DECIMAL),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == BigDecimal::from(0)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// TRAIT FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+#[typetag::serde]
+impl Primitive for PrimitiveDecimal {
+    fn is_eq(&self, other: &dyn Primitive) -> bool {
+        if let Some(other) = other.as_any().downcast_ref::<Self>() {
+            return self.value == other.value;
+        }
+
+        false
+    }
+
+    fn is_cmp(&self, other: &dyn Primitive) -> Option<Ordering> {
+        if let Some(other) = other.as_any().downcast_ref::<Self>() {
+            return self.value.partial_cmp(&other.value);
+        }
+
+        None
+    }
+
+    fn do_add(&self, other: &dyn Primitive) -> Result<Box<dyn Primitive>, String> {
+        if let Some(other) = other.as_any().downcast_ref::<Self>() {
+            return Ok(Box::new(PrimitiveDecimal::new(&self.value + &other.value)));
+        }
+
+        Err(format!(
+            "{} {:?} + {:?}",
+            ERROR_ILLEGAL_OPERATION,
+            self.get_type(),
+            other.get_type()
+        ))
+    }
+
+    fn do_sub(&self, other: &dyn Primitive) -> Result<Box<dyn Primitive>, String> {
+        if let Some(other) = other.as_any().downcast_ref::<Self>() {
+            return Ok(Box::new(PrimitiveDecimal::new(&self.value - &other.value)));
+        }
+
+        Err(format!(
+            "{} {:?} - {:?}",
+            ERROR_ILLEGAL_OPERATION,
+            self.get_type(),
+            other.get_type()
+        ))
+    }
+
+    fn do_div(&self, other: &dyn Primitive) -> Result<Box<dyn Primitive>, String> {
+        if let Some(other) = other.as_any().downcast_ref::<Self>() {
+            if other.is_zero() {
+                return Err(ERROR_OPS_DIV_DECIMAL.to_owned());
+            }
+
+            return Ok(Box::new(PrimitiveDecimal::new(&self.value / &other.value)));
+        }
+
+        Err(format!(
+            "{} {:?} / {:?}",
+            ERROR_ILLEGAL_OPERATION,
+            self.get_type(),
+            other.get_type()
+        ))
+    }
+
+    fn do_mul(&self, other: &dyn Primitive) -> Result<Box<dyn Primitive>, String> {
+        if let Some(other) = other.as_any().downcast_ref::<Self>() {
+            return Ok(Box::new(PrimitiveDecimal::new(&self.value * &other.value)));
+        }
+
+        Err(format!(
+            "{} {:?} * {:?}",
+            ERROR_ILLEGAL_OPERATION,
+            self.get_type(),
+            other.get_type()
+        ))
+    }
+
+    fn do_rem(&self, other: &dyn Primitive) -> Result<Box<dyn Primitive>, String> {
+        if let Some(other) = other.as_any().downcast_ref::<Self>() {
+            if other.is_zero() {
+                return Err(ERROR_OPS_DIV_DECIMAL.to_owned());
+            }
+
+            return Ok(Box::new(PrimitiveDecimal::new(&self.value % &other.value)));
+        }
+
+        Err(format!(
+            "{} {:?} % {:?}",
+            ERROR_ILLEGAL_OPERATION,
+            self.get_type(),
+            other.get_type()
+        ))
+    }
+
+    fn as_debug(&self) -> &dyn std::fmt::Debug {
+        self
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn get_type(&self) -> PrimitiveType {
+        PrimitiveType::PrimitiveDecimal
+    }
+
+    fn as_box_clone(&self) -> Box<dyn Primitive> {
+        Box::new((*self).clone())
+    }
+
+    // decimals are sent as strings so that no precision is lost by the JSON parsers
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!(self.value.to_string())
+    }
+
+    fn format_mem(&self, _content_type: &str, _first: bool) -> serde_json::Value {
+        let mut map: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
+        map.insert("_decimal".to_owned(), self.to_json());
+
+        serde_json::Value::Object(map)
+    }
+
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+
+    fn as_bool(&self) -> bool {
+        !self.is_zero()
+    }
+
+    fn get_value(&self) -> &dyn std::any::Any {
+        &self.value
+    }
+
+    fn get_mut_value(&mut self) -> &mut dyn std::any::Any {
+        &mut self.value
+    }
+
+    fn to_msg(&self, _content_type: String) -> Message {
+        let mut hashmap: HashMap<String, Literal> = HashMap::new();
+
+        hashmap.insert(
+            "text".to_owned(),
+            Literal {
+                content_type: "decimal".to_owned(),
+                primitive: Box::new(PrimitiveString::new(&self.value.to_string())),
+                additional_info: None,
+                secure_variable: false,
+                interval: Interval {
+                    start_column: 0,
+                    start_line: 0,
+                    offset: 0,
+                    end_line: None,
+                    end_column: None,
+                },
+            },
+        );
+
+        let mut result = PrimitiveObject::get_literal(
+            &hashmap,
+            Interval {
+                start_column: 0,
+                start_line: 0,
+                offset: 0,
+                end_line: None,
+                end_column: None,
+            },
+        );
+        result.set_content_type("text");
+
+        Message {
+            content_type: result.content_type,
+            content: result.primitive.to_json(),
+        }
+    }
+
+    fn do_exec(
+        &mut self,
+        name: &str,
+        args: &HashMap<String, Literal>,
+        mem_type: &MemoryType,
+        additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        _content_type: &ContentType,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<(Literal, Right), ErrorInfo> {
+        if let Some((f, right)) = FUNCTIONS.get(name) {
+            if *mem_type == MemoryType::Constant && *right == Right::Write {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    format!("{}", ERROR_CONSTANT_MUTABLE_FUNCTION),
+                ));
+            } else {
+                let res = f(self, args, additional_info, data, interval)?;
+
+                return Ok((res, *right));
+            }
+        }
+
+        Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            format!("[{}] {}", name, ERROR_DECIMAL_UNKNOWN_METHOD),
+        ))
+    }
+}
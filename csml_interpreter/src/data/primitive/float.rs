@@ -1,4 +1,5 @@
 use crate::data::primitive::tools::check_division_by_zero_f64;
+use crate::data::primitive::tools_locale;
 use crate::data::{
     ast::Interval,
     error_info::ErrorInfo,
@@ -38,6 +39,8 @@ const FUNCTIONS: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map! {
     "is_error" => (PrimitiveFloat::is_error as PrimitiveMethod, Right::Read),
     "get_info" => (PrimitiveFloat::get_info as PrimitiveMethod, Right::Read),
     "to_string" => (PrimitiveFloat::to_string as PrimitiveMethod, Right::Read),
+    "format_number" => (PrimitiveFloat::format_number as PrimitiveMethod, Right::Read),
+    "format_currency" => (PrimitiveFloat::format_currency as PrimitiveMethod, Right::Read),
 
     "precision" => (PrimitiveFloat::precision as PrimitiveMethod, Right::Read),
     "abs" => (PrimitiveFloat::abs as PrimitiveMethod, Right::Read),
@@ -181,6 +184,55 @@ impl PrimitiveFloat {
 
         Ok(PrimitiveString::get_literal(&float.to_string(), interval))
     }
+
+    fn format_number(
+        float: &mut PrimitiveFloat,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let value = float.value;
+
+        tools_locale::format_number(
+            args,
+            data,
+            interval,
+            tools_locale::MAX_FLOAT_DECIMALS,
+            &|decimals: Option<u32>| match decimals {
+                Some(decimals) => format!("{:.*}", decimals as usize, value),
+                // up to 3 decimals by default, without trailing zeros
+                None => {
+                    let plain = format!("{:.3}", value);
+                    plain.trim_end_matches('0').trim_end_matches('.').to_owned()
+                }
+            },
+        )
+    }
+
+    fn format_currency(
+        float: &mut PrimitiveFloat,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let value = float.value;
+
+        tools_locale::format_currency(
+            args,
+            data,
+            interval,
+            &|decimals: Option<u32>| match decimals {
+                Some(decimals) => format!("{:.*}", decimals as usize, value),
+                // up to 3 decimals by default, without trailing zeros
+                None => {
+                    let plain = format!("{:.3}", value);
+                    plain.trim_end_matches('0').trim_end_matches('.').to_owned()
+                }
+            },
+        )
+    }
 }
 
 impl PrimitiveFloat {
@@ -738,7 +790,7 @@ impl Primitive for PrimitiveFloat {
             if *mem_type == MemoryType::Constant && *right == Right::Write {
                 return Err(gen_error_info(
                     Position::new(interval, &data.context.flow),
-                    format!("{}" , ERROR_CONSTANT_MUTABLE_FUNCTION),
+                    format!("{}", ERROR_CONSTANT_MUTABLE_FUNCTION),
                 ));
             } else {
                 let res = f(self, args, additional_info, data, interval)?;
@@ -5,6 +5,7 @@ use crate::data::primitive::float::PrimitiveFloat;
 use crate::data::primitive::object::PrimitiveObject;
 use crate::data::primitive::string::PrimitiveString;
 use crate::data::primitive::tools::check_division_by_zero_i64;
+use crate::data::primitive::tools_locale;
 use crate::data::primitive::Right;
 use crate::data::primitive::{Primitive, PrimitiveType};
 use crate::data::{ast::Interval, message::Message, Data, Literal, MemoryType, MessageData, MSG};
@@ -35,6 +36,8 @@ const FUNCTIONS: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map! {
     "is_error" => (PrimitiveInt::is_error as PrimitiveMethod, Right::Read),
     "get_info" => (PrimitiveInt::get_info as PrimitiveMethod, Right::Read),
     "to_string" => (PrimitiveInt::to_string as PrimitiveMethod, Right::Read),
+    "format_number" => (PrimitiveInt::format_number as PrimitiveMethod, Right::Read),
+    "format_currency" => (PrimitiveInt::format_currency as PrimitiveMethod, Right::Read),
 
     "precision" => (PrimitiveInt::precision as PrimitiveMethod, Right::Read),
     "abs" => (PrimitiveInt::abs as PrimitiveMethod, Right::Read),
@@ -178,6 +181,51 @@ impl PrimitiveInt {
 
         Ok(PrimitiveString::get_literal(&int.to_string(), interval))
     }
+
+    fn format_number(
+        int: &mut PrimitiveInt,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let value = int.value;
+
+        tools_locale::format_number(
+            args,
+            data,
+            interval,
+            tools_locale::MAX_DECIMALS,
+            &|decimals: Option<u32>| match decimals {
+                Some(decimals) if decimals > 0 => {
+                    format!("{}.{}", value, "0".repeat(decimals as usize))
+                }
+                _ => value.to_string(),
+            },
+        )
+    }
+
+    fn format_currency(
+        int: &mut PrimitiveInt,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+    ) -> Result<Literal, ErrorInfo> {
+        let value = int.value;
+
+        tools_locale::format_currency(
+            args,
+            data,
+            interval,
+            &|decimals: Option<u32>| match decimals {
+                Some(decimals) if decimals > 0 => {
+                    format!("{}.{}", value, "0".repeat(decimals as usize))
+                }
+                _ => value.to_string(),
+            },
+        )
+    }
 }
 
 impl PrimitiveInt {
@@ -742,7 +790,7 @@ impl Primitive for PrimitiveInt {
             if *mem_type == MemoryType::Constant && *right == Right::Write {
                 return Err(gen_error_info(
                     Position::new(interval, &data.context.flow),
-                    format!("{}" , ERROR_CONSTANT_MUTABLE_FUNCTION),
+                    format!("{}", ERROR_CONSTANT_MUTABLE_FUNCTION),
                 ));
             } else {
                 let res = f(self, args, additional_info, data, interval)?;
@@ -7,8 +7,9 @@ use crate::data::{
     literal::ContentType,
     message::Message,
     primitive::{
-        tools_crypto, tools_jwt, tools_smtp, tools_time, Data, MessageData, Primitive,
-        PrimitiveArray, PrimitiveBoolean, PrimitiveInt, PrimitiveNull, PrimitiveString,
+        closure::exec_closure_on_values, tools_crypto, tools_jwt, tools_query, tools_smtp,
+        tools_time, Data, MessageData, Primitive, PrimitiveArray, PrimitiveBoolean,
+        PrimitiveClosure, PrimitiveFloat, PrimitiveInt, PrimitiveNull, PrimitiveString,
         PrimitiveType, Right, MSG,
     },
     tokens::TYPES,
@@ -16,13 +17,18 @@ use crate::data::{
 };
 use crate::error_format::*;
 use crate::interpreter::{
-    builtins::http_builtin::http_request, json_to_rust::json_to_literal,
-    variable_handler::match_literals::match_obj,
+    builtins::{
+        http_builtin::{get_method, http_request},
+        llm::llm_request,
+        oauth2,
+    },
+    json_to_rust::json_to_literal,
+    variable_handler::match_literals::find_match,
 };
 use std::cmp::Ordering;
 use std::{collections::HashMap, sync::mpsc};
 
-use chrono::{DateTime, FixedOffset, LocalResult, TimeZone, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, LocalResult, TimeZone, Utc};
 use chrono_tz::{Tz, UTC};
 use lettre::Transport;
 use phf::phf_map;
@@ -37,7 +43,14 @@ const FUNCTIONS_HTTP: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map
     "set" => (PrimitiveObject::set as PrimitiveMethod, Right::Read),
     "disable_ssl_verify" => (PrimitiveObject::disable_ssl_verify as PrimitiveMethod, Right::Read),
     "auth" => (PrimitiveObject::auth as PrimitiveMethod, Right::Read),
+    "oauth2" => (PrimitiveObject::oauth2 as PrimitiveMethod, Right::Read),
     "query" => (PrimitiveObject::query as PrimitiveMethod, Right::Read),
+    "timeout" => (PrimitiveObject::http_timeout as PrimitiveMethod, Right::Read),
+    "retry" => (PrimitiveObject::retry as PrimitiveMethod, Right::Read),
+    "form" => (PrimitiveObject::form as PrimitiveMethod, Right::Read),
+    "multipart" => (PrimitiveObject::multipart as PrimitiveMethod, Right::Read),
+    "response_type" => (PrimitiveObject::response_type as PrimitiveMethod, Right::Read),
+    "max_size" => (PrimitiveObject::max_size as PrimitiveMethod, Right::Read),
     "get" => (PrimitiveObject::get_http as PrimitiveMethod, Right::Read),
     "post" => (PrimitiveObject::post as PrimitiveMethod, Right::Read),
     "put" => (PrimitiveObject::put as PrimitiveMethod, Right::Read),
@@ -63,18 +76,34 @@ const FUNCTIONS_TIME: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map
     "sub" => (PrimitiveObject::sub_time as PrimitiveMethod, Right::Write),
     "format" => (PrimitiveObject::date_format as PrimitiveMethod, Right::Read),
     "parse" => (PrimitiveObject::parse_date as PrimitiveMethod, Right::Read),
+    "diff" => (PrimitiveObject::time_diff as PrimitiveMethod, Right::Read),
+    "start_of" => (PrimitiveObject::start_of as PrimitiveMethod, Right::Read),
+    "end_of" => (PrimitiveObject::end_of as PrimitiveMethod, Right::Read),
+    "weekday" => (PrimitiveObject::weekday as PrimitiveMethod, Right::Read),
+    "iso_week" => (PrimitiveObject::iso_week as PrimitiveMethod, Right::Read),
+    "is_before" => (PrimitiveObject::is_before as PrimitiveMethod, Right::Read),
+    "is_after" => (PrimitiveObject::is_after as PrimitiveMethod, Right::Read),
+    "is_business_hours" => (PrimitiveObject::is_business_hours as PrimitiveMethod, Right::Read),
 };
 
 const FUNCTIONS_JWT: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map! {
     "sign" => (PrimitiveObject::jwt_sign as PrimitiveMethod, Right::Read),
     "decode" => (PrimitiveObject::jwt_decode as PrimitiveMethod, Right::Read),
     "verify" => (PrimitiveObject::jwt_verity as PrimitiveMethod, Right::Read),
+    "verify_jwks" => (PrimitiveObject::jwt_verify_jwks as PrimitiveMethod, Right::Read),
 };
 
 const FUNCTIONS_CRYPTO: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map! {
     "create_hmac" => (PrimitiveObject::create_hmac as PrimitiveMethod, Right::Read),
     "create_hash" => (PrimitiveObject::create_hash as PrimitiveMethod, Right::Read),
     "digest" => (PrimitiveObject::digest as PrimitiveMethod, Right::Read),
+    "encrypt" => (PrimitiveObject::encrypt as PrimitiveMethod, Right::Read),
+    "decrypt" => (PrimitiveObject::decrypt as PrimitiveMethod, Right::Read),
+    "sign" => (PrimitiveObject::sign as PrimitiveMethod, Right::Read),
+    "verify" => (PrimitiveObject::verify_signature as PrimitiveMethod, Right::Read),
+    "hash_password" => (PrimitiveObject::hash_password as PrimitiveMethod, Right::Read),
+    "verify_password" => (PrimitiveObject::verify_password as PrimitiveMethod, Right::Read),
+    "random_bytes" => (PrimitiveObject::random_bytes as PrimitiveMethod, Right::Read),
 };
 
 const FUNCTIONS_BASE64: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map! {
@@ -87,6 +116,20 @@ const FUNCTIONS_HEX: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map!
     "decode" => (PrimitiveObject::hex_decode as PrimitiveMethod, Right::Read),
 };
 
+const FUNCTIONS_KNOWLEDGE: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map! {
+    "search" => (PrimitiveObject::knowledge_search as PrimitiveMethod, Right::Read),
+};
+
+const FUNCTIONS_LLM: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map! {
+    "system" => (PrimitiveObject::llm_system as PrimitiveMethod, Right::Read),
+    "temperature" => (PrimitiveObject::llm_temperature as PrimitiveMethod, Right::Read),
+    "max_tokens" => (PrimitiveObject::llm_max_tokens as PrimitiveMethod, Right::Read),
+    "json" => (PrimitiveObject::llm_json as PrimitiveMethod, Right::Read),
+    "timeout" => (PrimitiveObject::llm_timeout as PrimitiveMethod, Right::Read),
+    "disable_ssl_verify" => (PrimitiveObject::llm_disable_ssl_verify as PrimitiveMethod, Right::Read),
+    "send" => (PrimitiveObject::llm_send as PrimitiveMethod, Right::Read),
+};
+
 const FUNCTIONS_EVENT: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map! {
     "get_type" => (PrimitiveObject::get_type as PrimitiveMethod, Right::Read),
     "get_content" => (PrimitiveObject::get_content as PrimitiveMethod, Right::Read),
@@ -113,7 +156,12 @@ const FUNCTIONS_READ: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map
     "keys" => (PrimitiveObject::keys as PrimitiveMethod, Right::Read),
     "values" => (PrimitiveObject::values as PrimitiveMethod, Right::Read),
     "get" => (PrimitiveObject::get_generics as PrimitiveMethod, Right::Read),
-
+    "get_path" => (PrimitiveObject::get_path as PrimitiveMethod, Right::Read),
+    "deep_merge" => (PrimitiveObject::deep_merge as PrimitiveMethod, Right::Read),
+    "pick" => (PrimitiveObject::pick as PrimitiveMethod, Right::Read),
+    "omit" => (PrimitiveObject::omit as PrimitiveMethod, Right::Read),
+    "entries" => (PrimitiveObject::entries as PrimitiveMethod, Right::Read),
+    "query" => (PrimitiveObject::jmespath_query as PrimitiveMethod, Right::Read),
 };
 
 const FUNCTIONS_WRITE: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map! {
@@ -121,6 +169,13 @@ const FUNCTIONS_WRITE: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_ma
     "insert" => (PrimitiveObject::insert as PrimitiveMethod, Right::Write),
     "assign" => (PrimitiveObject::assign as PrimitiveMethod, Right::Write),
     "remove" => (PrimitiveObject::remove as PrimitiveMethod, Right::Write),
+    "set_path" => (PrimitiveObject::set_path as PrimitiveMethod, Right::Write),
+};
+
+// methods calling a closure need the message data and sender to execute it
+const FUNCTIONS_CLOSURE: phf::Map<&'static str, (PrimitiveClosureMethod, Right)> = phf_map! {
+    "map_values" => (PrimitiveObject::map_values as PrimitiveClosureMethod, Right::Read),
+    "filter" => (PrimitiveObject::filter as PrimitiveClosureMethod, Right::Read),
 };
 
 type PrimitiveMethod = fn(
@@ -132,6 +187,21 @@ type PrimitiveMethod = fn(
     content_type: &str,
 ) -> Result<Literal, ErrorInfo>;
 
+type PrimitiveClosureMethod = fn(
+    object: &mut PrimitiveObject,
+    args: &HashMap<String, Literal>,
+    additional_info: &Option<HashMap<String, Literal>>,
+    data: &mut Data,
+    interval: Interval,
+    msg_data: &mut MessageData,
+    sender: &Option<mpsc::Sender<MSG>>,
+) -> Result<Literal, ErrorInfo>;
+
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
 #[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub struct PrimitiveObject {
     pub value: HashMap<String, Literal>,
@@ -274,7 +344,7 @@ impl PrimitiveObject {
         Ok(result)
     }
 
-    fn query(
+    fn oauth2(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
@@ -282,34 +352,109 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "query(parameters: object) => http object";
+        let mut params = vec![];
 
-        if args.len() != 1 {
-            return Err(gen_error_info(
-                Position::new(interval, &data.context.flow),
-                format!("usage: {}", usage),
-            ));
+        for index in 0..4 {
+            match args.get(&format!("arg{}", index)) {
+                Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveString => {
+                    params.push(lit.primitive.to_string())
+                }
+                // the scope is optional
+                None if index == 3 => {}
+                _ => {
+                    return Err(gen_error_info(
+                        Position::new(interval, &data.context.flow),
+                        ERROR_HTTP_OAUTH2.to_owned(),
+                    ))
+                }
+            }
         }
 
-        let literal = match args.get("arg0") {
-            Some(res) => res,
+        let bot_id = data
+            .context
+            .api_info
+            .as_ref()
+            .map(|api_info| api_info.client.bot_id.as_str());
+        let client = oauth2::register_client(
+            &data.context.oauth2_clients,
+            bot_id,
+            &params[0],
+            &params[1],
+            &params[2],
+            params.get(3).map(|scope| scope.as_str()),
+        );
+
+        // the client secret stays in the OAuth2 client, only its public information is kept
+        let mut oauth2 = HashMap::new();
+        oauth2.insert(
+            "token_url".to_owned(),
+            PrimitiveString::get_literal(&params[0], interval),
+        );
+        oauth2.insert(
+            "client_id".to_owned(),
+            PrimitiveString::get_literal(&params[1], interval),
+        );
+        if let Some(scope) = params.get(3) {
+            oauth2.insert(
+                "scope".to_owned(),
+                PrimitiveString::get_literal(scope, interval),
+            );
+        }
+        oauth2.insert(
+            "client".to_owned(),
+            PrimitiveString::get_literal(&client, interval),
+        );
+
+        let mut object = object.to_owned();
+        object.value.insert(
+            "oauth2".to_owned(),
+            PrimitiveObject::get_literal(&oauth2, interval),
+        );
+
+        let mut result = PrimitiveObject::get_literal(&object.value, interval);
+
+        result.set_content_type("http");
+
+        Ok(result)
+    }
+
+    fn http_timeout(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "timeout(milliseconds: Int) => http object";
+
+        let timeout = match args.get("arg0") {
+            Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveInt => {
+                match Literal::get_value::<i64>(
+                    &lit.primitive,
+                    &data.context.flow,
+                    interval,
+                    format!("usage: {}", usage),
+                )? {
+                    timeout if *timeout > 0 => lit.to_owned(),
+                    _ => {
+                        return Err(gen_error_info(
+                            Position::new(interval, &data.context.flow),
+                            format!("usage: {}", usage),
+                        ))
+                    }
+                }
+            }
             _ => {
                 return Err(gen_error_info(
                     Position::new(interval, &data.context.flow),
                     format!("usage: {}", usage),
-                ));
+                ))
             }
         };
 
         let mut object = object.to_owned();
-
-        let header = Literal::get_value::<HashMap<String, Literal>>(
-            &literal.primitive,
-            &data.context.flow,
-            interval,
-            ERROR_HTTP_QUERY.to_owned(),
-        )?;
-        insert_to_object(header, &mut object, "query", &data.context.flow, literal);
+        object.value.insert("timeout".to_owned(), timeout);
 
         let mut result = PrimitiveObject::get_literal(&object.value, interval);
 
@@ -318,7 +463,7 @@ impl PrimitiveObject {
         Ok(result)
     }
 
-    fn get_http(
+    fn retry(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
@@ -326,23 +471,74 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "get() => http object";
+        let usage = "retry(count: Int, backoff_milliseconds: Int = 500, non_idempotent: Boolean = false) => http object";
 
-        if !args.is_empty() {
+        let mut params = vec![];
+
+        for index in 0..2 {
+            match args.get(&format!("arg{}", index)) {
+                Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveInt => {
+                    let value = Literal::get_value::<i64>(
+                        &lit.primitive,
+                        &data.context.flow,
+                        interval,
+                        format!("usage: {}", usage),
+                    )?;
+                    params.push(*value);
+                }
+                // the backoff is optional
+                None if index == 1 => params.push(500),
+                _ => {
+                    return Err(gen_error_info(
+                        Position::new(interval, &data.context.flow),
+                        format!("usage: {}", usage),
+                    ))
+                }
+            }
+        }
+
+        if !(0..=10).contains(&params[0]) || params[1] < 0 {
             return Err(gen_error_info(
                 Position::new(interval, &data.context.flow),
-                format!("usage: {}", usage),
+                format!("usage: {}, count must be between 0 and 10", usage),
             ));
         }
 
-        let mut object = object.to_owned();
+        // POST and PATCH calls are only retried with this option
+        let non_idempotent = match args.get("arg2") {
+            Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveBoolean => {
+                lit.primitive.as_bool()
+            }
+            None => false,
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    format!("usage: {}", usage),
+                ))
+            }
+        };
 
-        object.value.insert(
-            "method".to_owned(),
-            PrimitiveString::get_literal("get", interval),
+        let mut retry = HashMap::new();
+        retry.insert(
+            "count".to_owned(),
+            PrimitiveInt::get_literal(params[0], interval),
         );
+        retry.insert(
+            "backoff".to_owned(),
+            PrimitiveInt::get_literal(params[1], interval),
+        );
+        if non_idempotent {
+            retry.insert(
+                "non_idempotent".to_owned(),
+                PrimitiveBoolean::get_literal(true, interval),
+            );
+        }
 
-        object.value.remove("body");
+        let mut object = object.to_owned();
+        object.value.insert(
+            "retry".to_owned(),
+            PrimitiveObject::get_literal(&retry, interval),
+        );
 
         let mut result = PrimitiveObject::get_literal(&object.value, interval);
 
@@ -351,79 +547,118 @@ impl PrimitiveObject {
         Ok(result)
     }
 
-    fn post(
+    fn set_body_type(
         object: &mut PrimitiveObject,
-        args: &HashMap<String, Literal>,
-        _additional_info: &Option<HashMap<String, Literal>>,
-        _data: &mut Data,
+        body_type: &str,
+        content_type: &str,
+        flow_name: &str,
         interval: Interval,
-        _content_type: &str,
-    ) -> Result<Literal, ErrorInfo> {
-        match args.get("arg0") {
-            Some(body) => object.value.insert("body".to_owned(), body.to_owned()),
-            _ => object.value.remove("body"),
-        };
-
+    ) -> Literal {
         let mut object = object.to_owned();
 
         object.value.insert(
-            "method".to_owned(),
-            PrimitiveString::get_literal("post", interval),
+            "body_type".to_owned(),
+            PrimitiveString::get_literal(body_type, interval),
+        );
+
+        let mut header = HashMap::new();
+        header.insert(
+            "Content-Type".to_owned(),
+            PrimitiveString::get_literal(content_type, interval),
         );
+        let literal = PrimitiveObject::get_literal(&header, interval);
+
+        insert_to_object(&header, &mut object, "header", flow_name, &literal);
 
         let mut result = PrimitiveObject::get_literal(&object.value, interval);
 
         result.set_content_type("http");
 
-        Ok(result)
+        result
     }
 
-    fn put(
+    fn form(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
-        _data: &mut Data,
+        data: &mut Data,
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        match args.get("arg0") {
-            Some(body) => object.value.insert("body".to_owned(), body.to_owned()),
-            _ => object.value.remove("body"),
-        };
+        let usage = "form() => http object";
 
-        let mut object = object.to_owned();
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
 
-        object.value.insert(
-            "method".to_owned(),
-            PrimitiveString::get_literal("put", interval),
-        );
+        Ok(PrimitiveObject::set_body_type(
+            object,
+            "form",
+            "application/x-www-form-urlencoded",
+            &data.context.flow,
+            interval,
+        ))
+    }
 
-        let mut result = PrimitiveObject::get_literal(&object.value, interval);
+    fn multipart(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "multipart() => http object";
 
-        result.set_content_type("http");
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
 
-        Ok(result)
+        // the boundary is added to the Content-Type when the request is sent
+        Ok(PrimitiveObject::set_body_type(
+            object,
+            "multipart",
+            "multipart/form-data",
+            &data.context.flow,
+            interval,
+        ))
     }
 
-    fn delete(
+    fn response_type(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
-        _data: &mut Data,
+        data: &mut Data,
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        match args.get("arg0") {
-            Some(body) => object.value.insert("body".to_owned(), body.to_owned()),
-            _ => object.value.remove("body"),
+        let usage = "response_type(type: \"json\" | \"text\" | \"binary\") => http object";
+
+        let response_type = match args.get("arg0") {
+            Some(lit)
+                if lit.primitive.get_type() == PrimitiveType::PrimitiveString
+                    && ["json", "text", "binary"].contains(&lit.primitive.to_string().as_str()) =>
+            {
+                lit.to_owned()
+            }
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    format!("usage: {}", usage),
+                ))
+            }
         };
 
         let mut object = object.to_owned();
-
-        object.value.insert(
-            "method".to_owned(),
-            PrimitiveString::get_literal("delete", interval),
-        );
+        object
+            .value
+            .insert("response_type".to_owned(), response_type);
 
         let mut result = PrimitiveObject::get_literal(&object.value, interval);
 
@@ -432,27 +667,43 @@ impl PrimitiveObject {
         Ok(result)
     }
 
-    fn patch(
+    fn max_size(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
-        _data: &mut Data,
+        data: &mut Data,
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let body = match args.get("arg0") {
-            Some(res) => res.to_owned(),
-            _ => PrimitiveNull::get_literal(Interval::default()),
+        let usage = "max_size(bytes: Int) => http object";
+
+        let max_size = match args.get("arg0") {
+            Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveInt => {
+                match Literal::get_value::<i64>(
+                    &lit.primitive,
+                    &data.context.flow,
+                    interval,
+                    format!("usage: {}", usage),
+                )? {
+                    max_size if *max_size > 0 => lit.to_owned(),
+                    _ => {
+                        return Err(gen_error_info(
+                            Position::new(interval, &data.context.flow),
+                            format!("usage: {}", usage),
+                        ))
+                    }
+                }
+            }
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    format!("usage: {}", usage),
+                ))
+            }
         };
 
         let mut object = object.to_owned();
-
-        object.value.insert(
-            "method".to_owned(),
-            PrimitiveString::get_literal("patch", interval),
-        );
-
-        object.value.insert("body".to_owned(), body);
+        object.value.insert("max_size".to_owned(), max_size);
 
         let mut result = PrimitiveObject::get_literal(&object.value, interval);
 
@@ -461,7 +712,7 @@ impl PrimitiveObject {
         Ok(result)
     }
 
-    fn send(
+    fn query(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
@@ -469,49 +720,220 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "send() => http object";
+        let usage = "query(parameters: object) => http object";
 
-        if !args.is_empty() {
+        if args.len() != 1 {
             return Err(gen_error_info(
                 Position::new(interval, &data.context.flow),
                 format!("usage: {}", usage),
             ));
         }
 
-        if let Some(literal) = object.value.get("method") {
-            let method = match Literal::get_value::<String>(
-                &literal.primitive,
-                &data.context.flow,
-                interval,
-                ERROR_HTTP_UNKNOWN_METHOD.to_string(),
-            ) {
-                Ok(delete) if delete == "delete" => "delete",
-                Ok(put) if put == "put" => "put",
-                Ok(patch) if patch == "patch" => "patch",
-                Ok(post) if post == "post" => "post",
-                Ok(get) if get == "get" => "get",
-                _ => {
-                    return Err(gen_error_info(
-                        Position::new(interval, &data.context.flow),
-                        ERROR_HTTP_UNKNOWN_METHOD.to_string(),
-                    ))
-                }
-            };
-
-            let (value, response_info) =
-                http_request(&object.value, method, &data.context.flow, interval, false)?;
-            let mut literal = json_to_literal(&value, interval, &data.context.flow)?;
-            // add additional information about the http request response: status and headers
-            literal.add_info_block(response_info);
-
-            return Ok(literal);
-        }
-
-        Err(gen_error_info(
-            Position::new(interval, &data.context.flow),
-            ERROR_HTTP_SEND.to_owned(),
-        ))
-    }
+        let literal = match args.get("arg0") {
+            Some(res) => res,
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    format!("usage: {}", usage),
+                ));
+            }
+        };
+
+        let mut object = object.to_owned();
+
+        let header = Literal::get_value::<HashMap<String, Literal>>(
+            &literal.primitive,
+            &data.context.flow,
+            interval,
+            ERROR_HTTP_QUERY.to_owned(),
+        )?;
+        insert_to_object(header, &mut object, "query", &data.context.flow, literal);
+
+        let mut result = PrimitiveObject::get_literal(&object.value, interval);
+
+        result.set_content_type("http");
+
+        Ok(result)
+    }
+
+    fn get_http(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "get() => http object";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let mut object = object.to_owned();
+
+        object.value.insert(
+            "method".to_owned(),
+            PrimitiveString::get_literal("get", interval),
+        );
+
+        object.value.remove("body");
+
+        let mut result = PrimitiveObject::get_literal(&object.value, interval);
+
+        result.set_content_type("http");
+
+        Ok(result)
+    }
+
+    fn post(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        _data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        match args.get("arg0") {
+            Some(body) => object.value.insert("body".to_owned(), body.to_owned()),
+            _ => object.value.remove("body"),
+        };
+
+        let mut object = object.to_owned();
+
+        object.value.insert(
+            "method".to_owned(),
+            PrimitiveString::get_literal("post", interval),
+        );
+
+        let mut result = PrimitiveObject::get_literal(&object.value, interval);
+
+        result.set_content_type("http");
+
+        Ok(result)
+    }
+
+    fn put(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        _data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        match args.get("arg0") {
+            Some(body) => object.value.insert("body".to_owned(), body.to_owned()),
+            _ => object.value.remove("body"),
+        };
+
+        let mut object = object.to_owned();
+
+        object.value.insert(
+            "method".to_owned(),
+            PrimitiveString::get_literal("put", interval),
+        );
+
+        let mut result = PrimitiveObject::get_literal(&object.value, interval);
+
+        result.set_content_type("http");
+
+        Ok(result)
+    }
+
+    fn delete(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        _data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        match args.get("arg0") {
+            Some(body) => object.value.insert("body".to_owned(), body.to_owned()),
+            _ => object.value.remove("body"),
+        };
+
+        let mut object = object.to_owned();
+
+        object.value.insert(
+            "method".to_owned(),
+            PrimitiveString::get_literal("delete", interval),
+        );
+
+        let mut result = PrimitiveObject::get_literal(&object.value, interval);
+
+        result.set_content_type("http");
+
+        Ok(result)
+    }
+
+    fn patch(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        _data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let body = match args.get("arg0") {
+            Some(res) => res.to_owned(),
+            _ => PrimitiveNull::get_literal(Interval::default()),
+        };
+
+        let mut object = object.to_owned();
+
+        object.value.insert(
+            "method".to_owned(),
+            PrimitiveString::get_literal("patch", interval),
+        );
+
+        object.value.insert("body".to_owned(), body);
+
+        let mut result = PrimitiveObject::get_literal(&object.value, interval);
+
+        result.set_content_type("http");
+
+        Ok(result)
+    }
+
+    fn send(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "send() => http object";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let method = get_method(&object.value, &data.context.flow, interval)?;
+
+        let (value, response_info) = http_request(
+            &object.value,
+            method,
+            &data.context.flow,
+            interval,
+            false,
+            data.event.http_mock.as_ref(),
+            &data.context.oauth2_clients,
+            data.network_policies,
+        )?;
+        let mut literal = json_to_literal(&value, interval, &data.context.flow)?;
+        // add additional information about the http request response: status and headers
+        literal.add_info_block(response_info);
+
+        Ok(literal)
+    }
 }
 
 impl PrimitiveObject {
@@ -1018,7 +1440,7 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "add(time_in_seconds: int) => Time Object";
+        let usage = "add(time_in_seconds: int || iso_8601_duration: string) => Time Object";
 
         let mut final_time = 0;
 
@@ -1053,6 +1475,33 @@ impl PrimitiveObject {
 
                 Ok(lit)
             }
+            Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveString => {
+                let duration = match tools_time::parse_iso_duration(&lit.primitive.to_string()) {
+                    Some(duration) => duration,
+                    None => {
+                        return Err(gen_error_info(
+                            Position::new(interval, &data.context.flow),
+                            ERROR_TIME_DURATION.to_owned(),
+                        ))
+                    }
+                };
+                let final_time = tools_time::add_iso_duration(
+                    &object.value,
+                    &duration,
+                    data,
+                    interval,
+                    &format!("usage: {}", usage),
+                )?;
+
+                object.value.insert(
+                    "milliseconds".to_owned(),
+                    PrimitiveInt::get_literal(final_time, interval),
+                );
+                let mut lit = PrimitiveObject::get_literal(&object.value, interval);
+                lit.set_content_type("time");
+
+                Ok(lit)
+            }
             _ => {
                 return Err(gen_error_info(
                     Position::new(interval, &data.context.flow),
@@ -1070,7 +1519,7 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "sub(time_in_seconds: int) => Time Object";
+        let usage = "sub(time_in_seconds: int || iso_8601_duration: string) => Time Object";
 
         let mut final_time = 0;
 
@@ -1105,6 +1554,35 @@ impl PrimitiveObject {
 
                 Ok(lit)
             }
+            Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveString => {
+                let duration = match tools_time::parse_iso_duration(&lit.primitive.to_string()) {
+                    Some(duration) => duration,
+                    None => {
+                        return Err(gen_error_info(
+                            Position::new(interval, &data.context.flow),
+                            ERROR_TIME_DURATION.to_owned(),
+                        ))
+                    }
+                };
+                let duration = duration.negate();
+
+                let final_time = tools_time::add_iso_duration(
+                    &object.value,
+                    &duration,
+                    data,
+                    interval,
+                    &format!("usage: {}", usage),
+                )?;
+
+                object.value.insert(
+                    "milliseconds".to_owned(),
+                    PrimitiveInt::get_literal(final_time, interval),
+                );
+                let mut lit = PrimitiveObject::get_literal(&object.value, interval);
+                lit.set_content_type("time");
+
+                Ok(lit)
+            }
             _ => {
                 return Err(gen_error_info(
                     Position::new(interval, &data.context.flow),
@@ -1257,7 +1735,7 @@ impl PrimitiveObject {
 }
 
 impl PrimitiveObject {
-    fn jwt_sign(
+    fn time_diff(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
@@ -1265,32 +1743,272 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let mut headers = jsonwebtoken::Header::default();
+        let other = tools_time::get_time_arg(args, 0, data, interval, ERROR_TIME_DIFF)?;
+        let unit = match args.get("arg1") {
+            Some(unit) => unit.primitive.to_string(),
+            None => "seconds".to_owned(),
+        };
 
-        match args.get("arg0") {
-            Some(algo) if algo.primitive.get_type() == PrimitiveType::PrimitiveString => {
-                headers.alg = tools_jwt::get_algorithm(algo, &data.context.flow, interval)?;
-            }
+        let date = tools_time::get_local_date(&object.value, data, interval, ERROR_TIME_DIFF)?;
+
+        match tools_time::get_diff(date, other, &unit) {
+            Some(diff) => Ok(PrimitiveInt::get_literal(diff, interval)),
+            None => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                ERROR_TIME_DIFF.to_owned(),
+            )),
+        }
+    }
+
+    fn start_of(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let unit = match args.get("arg0") {
+            Some(unit) if args.len() == 1 => unit.primitive.to_string(),
             _ => {
                 return Err(gen_error_info(
                     Position::new(interval, &data.context.flow),
-                    ERROR_JWT_SIGN_ALGO.to_string(),
+                    ERROR_TIME_START_OF.to_owned(),
                 ))
             }
-        }
+        };
 
-        let claims = match object.value.get("jwt") {
-            Some(literal) => literal.primitive.to_json(),
+        let date = tools_time::get_local_date(&object.value, data, interval, ERROR_TIME_START_OF)?;
+        let start = match tools_time::get_start_of(date, &unit) {
+            Some(start) => start,
             None => {
                 return Err(gen_error_info(
                     Position::new(interval, &data.context.flow),
-                    ERROR_JWT_SIGN_CLAIMS.to_string(),
+                    ERROR_TIME_START_OF.to_owned(),
                 ))
             }
         };
 
-        let key = match args.get("arg1") {
-            Some(key) if key.primitive.get_type() == PrimitiveType::PrimitiveString => {
+        let millis = tools_time::local_to_milliseconds(&object.value, start, data, interval)?;
+
+        Ok(tools_time::gen_time_literal(
+            &object.value,
+            millis,
+            interval,
+        ))
+    }
+
+    fn end_of(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let unit = match args.get("arg0") {
+            Some(unit) if args.len() == 1 => unit.primitive.to_string(),
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_TIME_END_OF.to_owned(),
+                ))
+            }
+        };
+
+        let date = tools_time::get_local_date(&object.value, data, interval, ERROR_TIME_END_OF)?;
+        let next_start = match tools_time::get_start_of(date, &unit)
+            .and_then(|start| tools_time::get_next_start_of(start, &unit))
+        {
+            Some(next_start) => next_start,
+            None => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_TIME_END_OF.to_owned(),
+                ))
+            }
+        };
+
+        // the end of a period is the last millisecond before the next one starts
+        let millis =
+            tools_time::local_to_milliseconds(&object.value, next_start, data, interval)? - 1;
+
+        Ok(tools_time::gen_time_literal(
+            &object.value,
+            millis,
+            interval,
+        ))
+    }
+
+    fn weekday(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "weekday() => int (1 for monday to 7 for sunday)";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let date = tools_time::get_local_date(&object.value, data, interval, usage)?;
+
+        Ok(PrimitiveInt::get_literal(
+            date.weekday().number_from_monday() as i64,
+            interval,
+        ))
+    }
+
+    fn iso_week(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "iso_week() => int";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let date = tools_time::get_local_date(&object.value, data, interval, usage)?;
+
+        Ok(PrimitiveInt::get_literal(
+            date.iso_week().week() as i64,
+            interval,
+        ))
+    }
+
+    fn is_before(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let other = tools_time::get_time_arg(args, 0, data, interval, ERROR_TIME_COMPARE)?;
+        let millis =
+            tools_time::get_milliseconds(&object.value, data, interval, ERROR_TIME_COMPARE)?;
+
+        Ok(PrimitiveBoolean::get_literal(millis < other, interval))
+    }
+
+    fn is_after(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let other = tools_time::get_time_arg(args, 0, data, interval, ERROR_TIME_COMPARE)?;
+        let millis =
+            tools_time::get_milliseconds(&object.value, data, interval, ERROR_TIME_COMPARE)?;
+
+        Ok(PrimitiveBoolean::get_literal(millis > other, interval))
+    }
+
+    fn is_business_hours(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let schedule = match args.get("arg0") {
+            Some(res) if res.primitive.get_type() == PrimitiveType::PrimitiveObject => {
+                Literal::get_value::<HashMap<String, Literal>>(
+                    &res.primitive,
+                    &data.context.flow,
+                    interval,
+                    ERROR_TIME_BUSINESS_HOURS.to_owned(),
+                )?
+            }
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_TIME_BUSINESS_HOURS.to_owned(),
+                ))
+            }
+        };
+        let holidays = match args.get("arg1") {
+            Some(res) => Literal::get_value::<Vec<Literal>>(
+                &res.primitive,
+                &data.context.flow,
+                interval,
+                ERROR_TIME_BUSINESS_HOURS.to_owned(),
+            )?
+            .to_owned(),
+            None => vec![],
+        };
+
+        // the schedule can be defined in its own timezone
+        let mut time = object.value.clone();
+        if let Some(timezone) = schedule.get("timezone") {
+            time.remove("offset");
+            time.insert("timezone".to_owned(), timezone.to_owned());
+        }
+
+        let date = tools_time::get_local_date(&time, data, interval, ERROR_TIME_BUSINESS_HOURS)?;
+
+        match tools_time::is_business_hours(date, schedule, &holidays) {
+            Some(is_open) => Ok(PrimitiveBoolean::get_literal(is_open, interval)),
+            None => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                ERROR_TIME_BUSINESS_HOURS.to_owned(),
+            )),
+        }
+    }
+}
+
+impl PrimitiveObject {
+    fn jwt_sign(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let mut headers = jsonwebtoken::Header::default();
+
+        match args.get("arg0") {
+            Some(algo) if algo.primitive.get_type() == PrimitiveType::PrimitiveString => {
+                headers.alg = tools_jwt::get_algorithm(algo, &data.context.flow, interval)?;
+            }
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_JWT_SIGN_ALGO.to_string(),
+                ))
+            }
+        }
+
+        let claims = match object.value.get("jwt") {
+            Some(literal) => literal.primitive.to_json(),
+            None => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_JWT_SIGN_CLAIMS.to_string(),
+                ))
+            }
+        };
+
+        let key = match args.get("arg1") {
+            Some(key) if key.primitive.get_type() == PrimitiveType::PrimitiveString => {
                 let key = Literal::get_value::<String>(
                     &key.primitive,
                     &data.context.flow,
@@ -1431,13 +2149,9 @@ impl PrimitiveObject {
             }
         }
 
-        match args.get("arg1") {
+        let algo = match args.get("arg1") {
             Some(algo) if algo.primitive.get_type() == PrimitiveType::PrimitiveString => {
-                validation.algorithms = vec![tools_jwt::get_algorithm(
-                    algo,
-                    &data.context.flow,
-                    interval,
-                )?];
+                tools_jwt::get_verify_algorithm(algo, &data.context.flow, interval)?
             }
             _ => {
                 return Err(gen_error_info(
@@ -1446,6 +2160,7 @@ impl PrimitiveObject {
                 ))
             }
         };
+        validation.algorithms = vec![algo];
 
         let key = match args.get("arg2") {
             Some(key) if key.primitive.get_type() == PrimitiveType::PrimitiveString => {
@@ -1456,7 +2171,7 @@ impl PrimitiveObject {
                     ERROR_JWT_SECRET.to_owned(),
                 )?;
 
-                jsonwebtoken::DecodingKey::from_secret(key.as_ref())
+                tools_jwt::get_decoding_key(algo, key, &data.context.flow, interval)?
             }
             _ => {
                 return Err(gen_error_info(
@@ -1478,6 +2193,111 @@ impl PrimitiveObject {
             }
         }
     }
+
+    fn jwt_verify_jwks(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let flow_name = &data.context.flow;
+
+        let token = match object.value.get("jwt") {
+            Some(literal) => Literal::get_value::<String>(
+                &literal.primitive,
+                flow_name,
+                interval,
+                ERROR_JWT_TOKEN.to_owned(),
+            )?,
+            None => {
+                return Err(gen_error_info(
+                    Position::new(interval, flow_name),
+                    ERROR_JWT_TOKEN.to_string(),
+                ))
+            }
+        };
+
+        let source = match args.get("arg0") {
+            Some(lit) => tools_jwt::get_jwks_source(lit, data.files, flow_name, interval)?,
+            None => {
+                return Err(gen_error_info(
+                    Position::new(interval, flow_name),
+                    ERROR_JWT_JWKS.to_string(),
+                ))
+            }
+        };
+
+        let algorithms = match args.get("arg1") {
+            Some(lit) => tools_jwt::get_jwks_algorithms(lit, flow_name, interval)?,
+            None => {
+                return Err(gen_error_info(
+                    Position::new(interval, flow_name),
+                    ERROR_JWT_JWKS_ALGO.to_string(),
+                ))
+            }
+        };
+
+        let header = match jsonwebtoken::decode_header(token) {
+            Ok(header) => header,
+            Err(_) => {
+                return Err(gen_error_info(
+                    Position::new(interval, flow_name),
+                    ERROR_JWT_JWKS_HEADER.to_string(),
+                ))
+            }
+        };
+
+        if !algorithms.contains(&header.alg) {
+            return Err(gen_error_info(
+                Position::new(interval, flow_name),
+                ERROR_JWT_JWKS_TOKEN_ALGO.to_string(),
+            ));
+        }
+
+        let jwks = tools_jwt::get_jwks(&source, false, data.network_policies, flow_name, interval)?;
+        let key = match tools_jwt::get_jwks_decoding_key(&jwks, &header) {
+            Some(key) => key,
+            // the keys may have been rotated since the key set was cached
+            None if matches!(source, tools_jwt::JwksSource::Url(_)) => {
+                let jwks =
+                    tools_jwt::get_jwks(&source, true, data.network_policies, flow_name, interval)?;
+
+                match tools_jwt::get_jwks_decoding_key(&jwks, &header) {
+                    Some(key) => key,
+                    None => {
+                        return Err(gen_error_info(
+                            Position::new(interval, flow_name),
+                            ERROR_JWT_JWKS_KEY.to_string(),
+                        ))
+                    }
+                }
+            }
+            None => {
+                return Err(gen_error_info(
+                    Position::new(interval, flow_name),
+                    ERROR_JWT_JWKS_KEY.to_string(),
+                ))
+            }
+        };
+
+        // the algorithm of the header is one of the allowed algorithms
+        let mut validation = jsonwebtoken::Validation::new(header.alg);
+        if let Some(lit) = args.get("arg2") {
+            tools_jwt::get_validation(lit, flow_name, interval, &mut validation)?
+        }
+
+        match jsonwebtoken::decode::<serde_json::Value>(token, &key, &validation) {
+            Ok(token_message) => {
+                tools_jwt::token_data_to_literal(token_message, flow_name, interval)
+            }
+            Err(e) => Err(gen_error_info(
+                Position::new(interval, flow_name),
+                format!("Invalid JWT verify {:?}", e.kind()),
+            )),
+        }
+    }
 }
 
 impl PrimitiveObject {
@@ -1697,47 +2517,212 @@ impl PrimitiveObject {
 
         Ok(PrimitiveString::get_literal(&value, interval))
     }
-}
 
-impl PrimitiveObject {
-    fn base64_encode(
+    fn encrypt(
         object: &mut PrimitiveObject,
-        _args: &HashMap<String, Literal>,
+        args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
         data: &mut Data,
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "Base64(\"...\").encode() => String";
+        let usage = "encrypt(key: base64 String) => String";
 
-        let string = match object.value.get("string") {
-            Some(lit) => lit.primitive.to_string(),
-            _ => {
-                return Err(gen_error_info(
-                    Position::new(interval, &data.context.flow),
-                    format!("usage: {}", usage),
-                ))
-            }
-        };
+        let value = get_crypto_value(object, data, interval)?;
+        let key = get_crypto_arg(args, 0, usage, data, interval)?;
 
-        let result = base64::encode(string.as_bytes());
+        let encrypted =
+            tools_crypto::aes_gcm_encrypt(value.as_bytes(), &key, &data.context.flow, interval)?;
 
-        Ok(PrimitiveString::get_literal(&result, interval))
+        Ok(PrimitiveString::get_literal(&encrypted, interval))
     }
 
-    fn base64_decode(
+    fn decrypt(
         object: &mut PrimitiveObject,
-        _args: &HashMap<String, Literal>,
+        args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
         data: &mut Data,
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "Base64(\"...\").decode() => String";
+        let usage = "decrypt(key: base64 String) => String";
 
-        let string = match object.value.get("string") {
-            Some(lit) => lit.primitive.to_string(),
-            _ => {
+        let value = get_crypto_value(object, data, interval)?;
+        let key = get_crypto_arg(args, 0, usage, data, interval)?;
+
+        let decrypted = tools_crypto::aes_gcm_decrypt(&value, &key, &data.context.flow, interval)?;
+
+        match String::from_utf8(decrypted) {
+            Ok(decrypted) => Ok(PrimitiveString::get_literal(&decrypted, interval)),
+            Err(_) => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                ERROR_CRYPTO_DECRYPT.to_owned(),
+            )),
+        }
+    }
+
+    fn sign(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage =
+            "sign(private_key: PEM String, algorithm: String = \"sha256\") => base64 String";
+
+        let value = get_crypto_value(object, data, interval)?;
+        let private_key = get_crypto_arg(args, 0, usage, data, interval)?;
+        let algo = get_crypto_option(args, 1, "sha256", usage, data, interval)?;
+
+        let signature = tools_crypto::sign(
+            value.as_bytes(),
+            &private_key,
+            &algo,
+            &data.context.flow,
+            interval,
+        )?;
+
+        Ok(PrimitiveString::get_literal(&signature, interval))
+    }
+
+    fn verify_signature(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "verify(signature: base64 String, public_key: PEM String, algorithm: String = \"sha256\") => Boolean";
+
+        let value = get_crypto_value(object, data, interval)?;
+        let signature = get_crypto_arg(args, 0, usage, data, interval)?;
+        let public_key = get_crypto_arg(args, 1, usage, data, interval)?;
+        let algo = get_crypto_option(args, 2, "sha256", usage, data, interval)?;
+
+        let verified = tools_crypto::verify(
+            value.as_bytes(),
+            &signature,
+            &public_key,
+            &algo,
+            &data.context.flow,
+            interval,
+        )?;
+
+        Ok(PrimitiveBoolean::get_literal(verified, interval))
+    }
+
+    fn hash_password(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "hash_password(algorithm: String = \"scrypt\") => String";
+
+        let password = get_crypto_value(object, data, interval)?;
+        let algo = get_crypto_option(args, 0, "scrypt", usage, data, interval)?;
+
+        let hash = tools_crypto::hash_password(&password, &algo, &data.context.flow, interval)?;
+
+        Ok(PrimitiveString::get_literal(&hash, interval))
+    }
+
+    fn verify_password(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "verify_password(hash: String) => Boolean";
+
+        let password = get_crypto_value(object, data, interval)?;
+        let hash = get_crypto_arg(args, 0, usage, data, interval)?;
+
+        let verified =
+            tools_crypto::verify_password(&password, &hash, &data.context.flow, interval)?;
+
+        Ok(PrimitiveBoolean::get_literal(verified, interval))
+    }
+
+    fn random_bytes(
+        _object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let size = match args.get("arg0") {
+            Some(size) if size.primitive.get_type() == PrimitiveType::PrimitiveInt => {
+                *Literal::get_value::<i64>(
+                    &size.primitive,
+                    &data.context.flow,
+                    interval,
+                    ERROR_CRYPTO_RANDOM.to_owned(),
+                )?
+            }
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_CRYPTO_RANDOM.to_owned(),
+                ))
+            }
+        };
+        let encoding = get_crypto_option(args, 1, "hex", ERROR_CRYPTO_RANDOM, data, interval)?;
+
+        let bytes = tools_crypto::random_bytes(size, &data.context.flow, interval)?;
+        let value = tools_crypto::digest_data(&encoding, &bytes, &data.context.flow, interval)?;
+
+        Ok(PrimitiveString::get_literal(&value, interval))
+    }
+}
+
+impl PrimitiveObject {
+    fn base64_encode(
+        object: &mut PrimitiveObject,
+        _args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "Base64(\"...\").encode() => String";
+
+        let string = match object.value.get("string") {
+            Some(lit) => lit.primitive.to_string(),
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    format!("usage: {}", usage),
+                ))
+            }
+        };
+
+        let result = base64::encode(string.as_bytes());
+
+        Ok(PrimitiveString::get_literal(&result, interval))
+    }
+
+    fn base64_decode(
+        object: &mut PrimitiveObject,
+        _args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "Base64(\"...\").decode() => String";
+
+        let string = match object.value.get("string") {
+            Some(lit) => lit.primitive.to_string(),
+            _ => {
                 return Err(gen_error_info(
                     Position::new(interval, &data.context.flow),
                     format!("usage: {}", usage),
@@ -1819,6 +2804,242 @@ impl PrimitiveObject {
     }
 }
 
+impl PrimitiveObject {
+    fn knowledge_search(
+        _object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "Knowledge.search(query: String, top = 3) => Array";
+
+        let query = match args.get("arg0") {
+            Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveString => {
+                lit.primitive.to_string()
+            }
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    format!("usage: {}", usage),
+                ))
+            }
+        };
+
+        let top = match args.get("arg1") {
+            Some(lit) => *Literal::get_value::<i64>(
+                &lit.primitive,
+                &data.context.flow,
+                interval,
+                format!("usage: {}", usage),
+            )?,
+            None => 3,
+        };
+
+        let knowledge = match data.knowledge {
+            Some(knowledge) => knowledge,
+            None => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_KNOWLEDGE_MISSING.to_owned(),
+                ))
+            }
+        };
+
+        let results: Vec<Literal> = knowledge
+            .search(&query, top.max(0) as usize)
+            .into_iter()
+            .map(|(entry, score)| {
+                let mut map = HashMap::new();
+
+                if let Some(id) = &entry.id {
+                    map.insert("id".to_owned(), PrimitiveString::get_literal(id, interval));
+                }
+                map.insert(
+                    "question".to_owned(),
+                    PrimitiveString::get_literal(&entry.question, interval),
+                );
+                map.insert(
+                    "answer".to_owned(),
+                    PrimitiveString::get_literal(&entry.answer, interval),
+                );
+                map.insert(
+                    "score".to_owned(),
+                    PrimitiveFloat::get_literal(score, interval),
+                );
+
+                PrimitiveObject::get_literal(&map, interval)
+            })
+            .collect();
+
+        Ok(PrimitiveArray::get_literal(&results, interval))
+    }
+}
+
+impl PrimitiveObject {
+    fn set_llm_option(
+        object: &mut PrimitiveObject,
+        key: &str,
+        value: Literal,
+        interval: Interval,
+    ) -> Literal {
+        let mut object = object.to_owned();
+
+        object.value.insert(key.to_owned(), value);
+
+        let mut result = PrimitiveObject::get_literal(&object.value, interval);
+        result.set_content_type("llm");
+
+        result
+    }
+
+    fn llm_system(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "system(prompt: String) => llm object";
+
+        match args.get("arg0") {
+            Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveString => Ok(
+                PrimitiveObject::set_llm_option(object, "system", lit.to_owned(), interval),
+            ),
+            _ => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            )),
+        }
+    }
+
+    fn llm_temperature(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "temperature(value: Float) => llm object";
+
+        match args.get("arg0") {
+            Some(lit)
+                if lit.primitive.get_type() == PrimitiveType::PrimitiveFloat
+                    || lit.primitive.get_type() == PrimitiveType::PrimitiveInt =>
+            {
+                Ok(PrimitiveObject::set_llm_option(
+                    object,
+                    "temperature",
+                    lit.to_owned(),
+                    interval,
+                ))
+            }
+            _ => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            )),
+        }
+    }
+
+    fn llm_max_tokens(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "max_tokens(value: Int) => llm object";
+
+        match args.get("arg0") {
+            Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveInt => Ok(
+                PrimitiveObject::set_llm_option(object, "max_tokens", lit.to_owned(), interval),
+            ),
+            _ => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            )),
+        }
+    }
+
+    fn llm_json(
+        object: &mut PrimitiveObject,
+        _args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        _data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let value = PrimitiveBoolean::get_literal(true, interval);
+
+        Ok(PrimitiveObject::set_llm_option(
+            object, "json", value, interval,
+        ))
+    }
+
+    fn llm_timeout(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "timeout(milliseconds: Int) => llm object";
+
+        match args.get("arg0") {
+            Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveInt => Ok(
+                PrimitiveObject::set_llm_option(object, "timeout", lit.to_owned(), interval),
+            ),
+            _ => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            )),
+        }
+    }
+
+    fn llm_disable_ssl_verify(
+        object: &mut PrimitiveObject,
+        _args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        _data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let value = PrimitiveBoolean::get_literal(true, interval);
+
+        Ok(PrimitiveObject::set_llm_option(
+            object,
+            "disable_ssl_verify",
+            value,
+            interval,
+        ))
+    }
+
+    fn llm_send(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "send() => completion";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        llm_request(&object.value, data, interval)
+    }
+}
+
 impl PrimitiveObject {
     fn get_type(
         _object: &mut PrimitiveObject,
@@ -1930,10 +3151,10 @@ impl PrimitiveObject {
             ));
         }
 
-        let is_match = args.iter().find(|(_name, arg)| match_obj(lit, arg));
+        let is_match = args.iter().find_map(|(_name, arg)| find_match(lit, arg));
 
         match is_match {
-            Some((_, lit)) => Ok(lit.to_owned()),
+            Some(lit) => Ok(lit.to_owned()),
             None => Ok(PrimitiveNull::get_literal(interval)),
         }
     }
@@ -1968,7 +3189,7 @@ impl PrimitiveObject {
             }
         };
 
-        let is_match = array.iter().find(|&arg| match_obj(lit, arg));
+        let is_match = array.iter().find_map(|arg| find_match(lit, arg));
 
         match is_match {
             Some(lit) => Ok(lit.to_owned()),
@@ -1998,15 +3219,213 @@ impl PrimitiveObject {
         Ok(PrimitiveBoolean::get_literal(false, interval))
     }
 
-    fn is_int(
-        _object: &mut PrimitiveObject,
+    fn is_int(
+        _object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "is_int() => boolean";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        Ok(PrimitiveBoolean::get_literal(false, interval))
+    }
+
+    fn is_float(
+        _object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "is_float() => boolean";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        Ok(PrimitiveBoolean::get_literal(false, interval))
+    }
+
+    fn type_of(
+        _object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "type_of() => string";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        Ok(PrimitiveString::get_literal("object", interval))
+    }
+
+    fn get_info(
+        _object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        literal::get_info(args, additional_info, interval, data)
+    }
+
+    fn is_error(
+        _object: &mut PrimitiveObject,
+        _args: &HashMap<String, Literal>,
+        additional_info: &Option<HashMap<String, Literal>>,
+        _data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        match additional_info {
+            Some(map) if map.contains_key("error") => {
+                Ok(PrimitiveBoolean::get_literal(true, interval))
+            }
+            _ => Ok(PrimitiveBoolean::get_literal(false, interval)),
+        }
+    }
+
+    fn to_xml(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "to_xml() => string";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        match serde_xml_rs::to_string(&object.to_json()) {
+            Ok(string) => Ok(PrimitiveString::get_literal(&string, interval)),
+            Err(_) => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("Object can not be converted to xml"),
+            )),
+        }
+    }
+
+    fn to_yaml(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "to_yaml() => string";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        match serde_yaml::to_string(&object.to_json()) {
+            Ok(string) => Ok(PrimitiveString::get_literal(&string, interval)),
+            Err(_) => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("Object can not be converted to yaml"),
+            )),
+        }
+    }
+
+    fn to_string(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "to_string() => string";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        Ok(PrimitiveString::get_literal(&object.to_string(), interval))
+    }
+
+    fn contains(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "contains(key: string) => boolean";
+
+        if args.len() != 1 {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let key = match args.get("arg0") {
+            Some(res) if res.primitive.get_type() == PrimitiveType::PrimitiveString => {
+                Literal::get_value::<String>(
+                    &res.primitive,
+                    &data.context.flow,
+                    interval,
+                    ERROR_OBJECT_CONTAINS.to_owned(),
+                )?
+            }
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_OBJECT_CONTAINS.to_owned(),
+                ));
+            }
+        };
+
+        let result = object.value.contains_key(key);
+
+        Ok(PrimitiveBoolean::get_literal(result, interval))
+    }
+
+    fn is_empty(
+        object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
         data: &mut Data,
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "is_int() => boolean";
+        let usage = "is_empty() => boolean";
 
         if !args.is_empty() {
             return Err(gen_error_info(
@@ -2015,18 +3434,20 @@ impl PrimitiveObject {
             ));
         }
 
-        Ok(PrimitiveBoolean::get_literal(false, interval))
+        let result = object.value.is_empty();
+
+        Ok(PrimitiveBoolean::get_literal(result, interval))
     }
 
-    fn is_float(
-        _object: &mut PrimitiveObject,
+    fn length(
+        object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
         data: &mut Data,
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "is_float() => boolean";
+        let usage = "length() => int";
 
         if !args.is_empty() {
             return Err(gen_error_info(
@@ -2035,18 +3456,20 @@ impl PrimitiveObject {
             ));
         }
 
-        Ok(PrimitiveBoolean::get_literal(false, interval))
+        let result = object.value.len();
+
+        Ok(PrimitiveInt::get_literal(result as i64, interval))
     }
 
-    fn type_of(
-        _object: &mut PrimitiveObject,
+    fn keys(
+        object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
         data: &mut Data,
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "type_of() => string";
+        let usage = "keys() => array";
 
         if !args.is_empty() {
             return Err(gen_error_info(
@@ -2055,37 +3478,16 @@ impl PrimitiveObject {
             ));
         }
 
-        Ok(PrimitiveString::get_literal("object", interval))
-    }
-
-    fn get_info(
-        _object: &mut PrimitiveObject,
-        args: &HashMap<String, Literal>,
-        additional_info: &Option<HashMap<String, Literal>>,
-        data: &mut Data,
-        interval: Interval,
-        _content_type: &str,
-    ) -> Result<Literal, ErrorInfo> {
-        literal::get_info(args, additional_info, interval, data)
-    }
+        let mut result = Vec::new();
 
-    fn is_error(
-        _object: &mut PrimitiveObject,
-        _args: &HashMap<String, Literal>,
-        additional_info: &Option<HashMap<String, Literal>>,
-        _data: &mut Data,
-        interval: Interval,
-        _content_type: &str,
-    ) -> Result<Literal, ErrorInfo> {
-        match additional_info {
-            Some(map) if map.contains_key("error") => {
-                Ok(PrimitiveBoolean::get_literal(true, interval))
-            }
-            _ => Ok(PrimitiveBoolean::get_literal(false, interval)),
+        for key in object.value.keys() {
+            result.push(PrimitiveString::get_literal(key, interval));
         }
+
+        Ok(PrimitiveArray::get_literal(&result, interval))
     }
 
-    fn to_xml(
+    fn values(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
@@ -2093,7 +3495,7 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "to_xml() => string";
+        let usage = "values() => array";
 
         if !args.is_empty() {
             return Err(gen_error_info(
@@ -2102,16 +3504,16 @@ impl PrimitiveObject {
             ));
         }
 
-        match serde_xml_rs::to_string(&object.to_json()) {
-            Ok(string) => Ok(PrimitiveString::get_literal(&string, interval)),
-            Err(_) => Err(gen_error_info(
-                Position::new(interval, &data.context.flow),
-                format!("Object can not be converted to xml"),
-            )),
+        let mut result = Vec::new();
+
+        for value in object.value.values() {
+            result.push(value.to_owned());
         }
+
+        Ok(PrimitiveArray::get_literal(&result, interval))
     }
 
-    fn to_yaml(
+    fn get_generics(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
@@ -2119,25 +3521,41 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "to_yaml() => string";
+        let usage = "get(key: string) => primitive";
 
-        if !args.is_empty() {
+        if args.len() != 1 {
             return Err(gen_error_info(
                 Position::new(interval, &data.context.flow),
                 format!("usage: {}", usage),
             ));
         }
 
-        match serde_yaml::to_string(&object.to_json()) {
-            Ok(string) => Ok(PrimitiveString::get_literal(&string, interval)),
-            Err(_) => Err(gen_error_info(
-                Position::new(interval, &data.context.flow),
-                format!("Object can not be converted to yaml"),
-            )),
+        let key = match args.get("arg0") {
+            Some(res) if res.primitive.get_type() == PrimitiveType::PrimitiveString => {
+                Literal::get_value::<String>(
+                    &res.primitive,
+                    &data.context.flow,
+                    interval,
+                    ERROR_OBJECT_GET_GENERICS.to_owned(),
+                )?
+            }
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_OBJECT_GET_GENERICS.to_owned(),
+                ));
+            }
+        };
+
+        match object.value.get(key) {
+            Some(res) => Ok(res.to_owned()),
+            None => Ok(PrimitiveNull::get_literal(interval)),
         }
     }
+}
 
-    fn to_string(
+impl PrimitiveObject {
+    fn clear_values(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
@@ -2145,7 +3563,7 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "to_string() => string";
+        let usage = "clear_values() => null";
 
         if !args.is_empty() {
             return Err(gen_error_info(
@@ -2154,10 +3572,22 @@ impl PrimitiveObject {
             ));
         }
 
-        Ok(PrimitiveString::get_literal(&object.to_string(), interval))
+        let mut vector: Vec<String> = Vec::new();
+
+        for key in object.value.keys() {
+            vector.push(key.to_owned());
+        }
+
+        for key in vector.iter() {
+            object
+                .value
+                .insert(key.to_owned(), PrimitiveNull::get_literal(interval));
+        }
+
+        Ok(PrimitiveNull::get_literal(interval))
     }
 
-    fn contains(
+    fn insert(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
@@ -2165,9 +3595,9 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "contains(key: string) => boolean";
+        let usage = "insert(key: string, value: primitive) => null";
 
-        if args.len() != 1 {
+        if args.len() != 2 {
             return Err(gen_error_info(
                 Position::new(interval, &data.context.flow),
                 format!("usage: {}", usage),
@@ -2180,23 +3610,33 @@ impl PrimitiveObject {
                     &res.primitive,
                     &data.context.flow,
                     interval,
-                    ERROR_OBJECT_CONTAINS.to_owned(),
+                    ERROR_OBJECT_INSERT.to_owned(),
                 )?
             }
             _ => {
                 return Err(gen_error_info(
                     Position::new(interval, &data.context.flow),
-                    ERROR_OBJECT_CONTAINS.to_owned(),
+                    ERROR_OBJECT_INSERT.to_owned(),
                 ));
             }
         };
 
-        let result = object.value.contains_key(key);
+        let value = match args.get("arg1") {
+            Some(res) => res,
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    format!("usage: {}", usage),
+                ));
+            }
+        };
 
-        Ok(PrimitiveBoolean::get_literal(result, interval))
+        object.value.insert(key.to_owned(), value.to_owned());
+
+        Ok(PrimitiveNull::get_literal(interval))
     }
 
-    fn is_empty(
+    fn assign(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
@@ -2204,21 +3644,40 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "is_empty() => boolean";
+        let usage = "assign(obj: Object) => null";
 
-        if !args.is_empty() {
+        if args.len() != 1 {
             return Err(gen_error_info(
                 Position::new(interval, &data.context.flow),
                 format!("usage: {}", usage),
             ));
         }
 
-        let result = object.value.is_empty();
+        let obj = match args.get("arg0") {
+            Some(res) if res.primitive.get_type() == PrimitiveType::PrimitiveObject => {
+                Literal::get_value::<HashMap<String, Literal>>(
+                    &res.primitive,
+                    &data.context.flow,
+                    interval,
+                    ERROR_OBJECT_ASSIGN.to_owned(),
+                )?
+            }
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_OBJECT_ASSIGN.to_owned(),
+                ));
+            }
+        };
 
-        Ok(PrimitiveBoolean::get_literal(result, interval))
+        object
+            .value
+            .extend(obj.into_iter().map(|(k, v)| (k.clone(), v.clone())));
+
+        Ok(PrimitiveNull::get_literal(interval))
     }
 
-    fn length(
+    fn remove(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
@@ -2226,21 +3685,41 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "length() => int";
+        let usage = "remove(key: string) => primitive";
 
-        if !args.is_empty() {
+        if args.len() != 1 {
             return Err(gen_error_info(
                 Position::new(interval, &data.context.flow),
                 format!("usage: {}", usage),
             ));
         }
 
-        let result = object.value.len();
+        let key = match args.get("arg0") {
+            Some(res) if res.primitive.get_type() == PrimitiveType::PrimitiveString => {
+                Literal::get_value::<String>(
+                    &res.primitive,
+                    &data.context.flow,
+                    interval,
+                    ERROR_OBJECT_REMOVE.to_owned(),
+                )?
+            }
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_OBJECT_REMOVE.to_owned(),
+                ));
+            }
+        };
 
-        Ok(PrimitiveInt::get_literal(result as i64, interval))
+        match object.value.remove(key) {
+            Some(value) => Ok(value),
+            None => Ok(PrimitiveNull::get_literal(interval)),
+        }
     }
+}
 
-    fn keys(
+impl PrimitiveObject {
+    fn get_path(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
@@ -2248,25 +3727,27 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "keys() => array";
+        let usage = "get_path(path: string, default: primitive) => primitive";
 
-        if !args.is_empty() {
+        if args.is_empty() || args.len() > 2 {
             return Err(gen_error_info(
                 Position::new(interval, &data.context.flow),
                 format!("usage: {}", usage),
             ));
         }
 
-        let mut result = Vec::new();
+        let path = get_path_arg(args, ERROR_OBJECT_GET_PATH, data, interval)?;
 
-        for key in object.value.keys() {
-            result.push(PrimitiveString::get_literal(key, interval));
+        match get_path_value(&object.value, &path) {
+            Some(value) => Ok(value.to_owned()),
+            None => match args.get("arg1") {
+                Some(default) => Ok(default.to_owned()),
+                None => Ok(PrimitiveNull::get_literal(interval)),
+            },
         }
-
-        Ok(PrimitiveArray::get_literal(&result, interval))
     }
 
-    fn values(
+    fn deep_merge(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
@@ -2274,25 +3755,39 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "values() => array";
+        let usage = "deep_merge(obj: Object) => Object";
 
-        if !args.is_empty() {
+        if args.len() != 1 {
             return Err(gen_error_info(
                 Position::new(interval, &data.context.flow),
                 format!("usage: {}", usage),
             ));
         }
 
-        let mut result = Vec::new();
+        let obj = match args.get("arg0") {
+            Some(res) if res.primitive.get_type() == PrimitiveType::PrimitiveObject => {
+                Literal::get_value::<HashMap<String, Literal>>(
+                    &res.primitive,
+                    &data.context.flow,
+                    interval,
+                    ERROR_OBJECT_DEEP_MERGE.to_owned(),
+                )?
+            }
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_OBJECT_DEEP_MERGE.to_owned(),
+                ));
+            }
+        };
 
-        for value in object.value.values() {
-            result.push(value.to_owned());
-        }
+        let mut result = object.value.clone();
+        merge_objects(&mut result, obj);
 
-        Ok(PrimitiveArray::get_literal(&result, interval))
+        Ok(PrimitiveObject::get_literal(&result, interval))
     }
 
-    fn get_generics(
+    fn pick(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
@@ -2300,41 +3795,39 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "get(key: string) => primitive";
+        let keys = get_keys_arg(args, ERROR_OBJECT_PICK, data, interval)?;
 
-        if args.len() != 1 {
-            return Err(gen_error_info(
-                Position::new(interval, &data.context.flow),
-                format!("usage: {}", usage),
-            ));
-        }
+        let result: HashMap<String, Literal> = object
+            .value
+            .iter()
+            .filter(|(key, _)| keys.contains(key))
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect();
+
+        Ok(PrimitiveObject::get_literal(&result, interval))
+    }
+
+    fn omit(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let keys = get_keys_arg(args, ERROR_OBJECT_OMIT, data, interval)?;
 
-        let key = match args.get("arg0") {
-            Some(res) if res.primitive.get_type() == PrimitiveType::PrimitiveString => {
-                Literal::get_value::<String>(
-                    &res.primitive,
-                    &data.context.flow,
-                    interval,
-                    ERROR_OBJECT_GET_GENERICS.to_owned(),
-                )?
-            }
-            _ => {
-                return Err(gen_error_info(
-                    Position::new(interval, &data.context.flow),
-                    ERROR_OBJECT_GET_GENERICS.to_owned(),
-                ));
-            }
-        };
+        let result: HashMap<String, Literal> = object
+            .value
+            .iter()
+            .filter(|(key, _)| !keys.contains(key))
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect();
 
-        match object.value.get(key) {
-            Some(res) => Ok(res.to_owned()),
-            None => Ok(PrimitiveNull::get_literal(interval)),
-        }
+        Ok(PrimitiveObject::get_literal(&result, interval))
     }
-}
 
-impl PrimitiveObject {
-    fn clear_values(
+    fn entries(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
@@ -2342,7 +3835,7 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "clear_values() => null";
+        let usage = "entries() => [[key, value]]";
 
         if !args.is_empty() {
             return Err(gen_error_info(
@@ -2351,22 +3844,25 @@ impl PrimitiveObject {
             ));
         }
 
-        let mut vector: Vec<String> = Vec::new();
+        let mut keys: Vec<&String> = object.value.keys().collect();
+        keys.sort();
 
-        for key in object.value.keys() {
-            vector.push(key.to_owned());
-        }
+        let result: Vec<Literal> = keys
+            .into_iter()
+            .map(|key| {
+                let entry = [
+                    PrimitiveString::get_literal(key, interval),
+                    object.value[key].to_owned(),
+                ];
 
-        for key in vector.iter() {
-            object
-                .value
-                .insert(key.to_owned(), PrimitiveNull::get_literal(interval));
-        }
+                PrimitiveArray::get_literal(&entry, interval)
+            })
+            .collect();
 
-        Ok(PrimitiveNull::get_literal(interval))
+        Ok(PrimitiveArray::get_literal(&result, interval))
     }
 
-    fn insert(
+    fn jmespath_query(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
@@ -2374,7 +3870,20 @@ impl PrimitiveObject {
         interval: Interval,
         _content_type: &str,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "insert(key: string, value: primitive) => null";
+        tools_query::query(&object.to_json(), args, data, interval)
+    }
+}
+
+impl PrimitiveObject {
+    fn set_path(
+        object: &mut PrimitiveObject,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        data: &mut Data,
+        interval: Interval,
+        _content_type: &str,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "set_path(path: string, value: primitive) => null";
 
         if args.len() != 2 {
             return Err(gen_error_info(
@@ -2383,123 +3892,430 @@ impl PrimitiveObject {
             ));
         }
 
-        let key = match args.get("arg0") {
-            Some(res) if res.primitive.get_type() == PrimitiveType::PrimitiveString => {
-                Literal::get_value::<String>(
-                    &res.primitive,
-                    &data.context.flow,
-                    interval,
-                    ERROR_OBJECT_INSERT.to_owned(),
-                )?
-            }
-            _ => {
+        let path = get_path_arg(args, ERROR_OBJECT_SET_PATH, data, interval)?;
+        let value = match args.get("arg1") {
+            Some(value) => value.to_owned(),
+            None => {
                 return Err(gen_error_info(
                     Position::new(interval, &data.context.flow),
-                    ERROR_OBJECT_INSERT.to_owned(),
+                    ERROR_OBJECT_SET_PATH.to_owned(),
                 ));
             }
         };
 
-        let value = match args.get("arg1") {
-            Some(res) => res,
-            _ => {
-                return Err(gen_error_info(
-                    Position::new(interval, &data.context.flow),
-                    format!("usage: {}", usage),
-                ));
+        let is_set = match path.split_first() {
+            Some((PathSegment::Key(key), rest)) => {
+                set_object_path(&mut object.value, key, rest, value, interval)
             }
+            _ => false,
         };
 
-        object.value.insert(key.to_owned(), value.to_owned());
-
-        Ok(PrimitiveNull::get_literal(interval))
+        match is_set {
+            true => Ok(PrimitiveNull::get_literal(interval)),
+            false => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                ERROR_OBJECT_SET_PATH.to_owned(),
+            )),
+        }
     }
+}
 
-    fn assign(
+impl PrimitiveObject {
+    fn map_values(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
         data: &mut Data,
         interval: Interval,
-        _content_type: &str,
+        msg_data: &mut MessageData,
+        sender: &Option<mpsc::Sender<MSG>>,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "assign(obj: Object) => null";
-
-        if args.len() != 1 {
-            return Err(gen_error_info(
-                Position::new(interval, &data.context.flow),
-                format!("usage: {}", usage),
-            ));
-        }
+        let usage = "map_values(fn) expect one argument of type [Closure]";
 
-        let obj = match args.get("arg0") {
-            Some(res) if res.primitive.get_type() == PrimitiveType::PrimitiveObject => {
-                Literal::get_value::<HashMap<String, Literal>>(
-                    &res.primitive,
-                    &data.context.flow,
-                    interval,
-                    ERROR_OBJECT_ASSIGN.to_owned(),
-                )?
-            }
-            _ => {
-                return Err(gen_error_info(
-                    Position::new(interval, &data.context.flow),
-                    ERROR_OBJECT_ASSIGN.to_owned(),
-                ));
-            }
-        };
+        let closure = get_closure_arg(args, usage, data, interval)?;
+        let entries = keyed_values(&object.value, interval);
+        let results = exec_closure_on_values(closure, &entries, interval, data, msg_data, sender)?;
 
-        object
-            .value
-            .extend(obj.into_iter().map(|(k, v)| (k.clone(), v.clone())));
+        let result: HashMap<String, Literal> = entries
+            .into_iter()
+            .zip(results.into_iter())
+            .map(|((_, key), value)| (key.primitive.to_string(), value))
+            .collect();
 
-        Ok(PrimitiveNull::get_literal(interval))
+        Ok(PrimitiveObject::get_literal(&result, interval))
     }
 
-    fn remove(
+    fn filter(
         object: &mut PrimitiveObject,
         args: &HashMap<String, Literal>,
         _additional_info: &Option<HashMap<String, Literal>>,
         data: &mut Data,
         interval: Interval,
-        _content_type: &str,
+        msg_data: &mut MessageData,
+        sender: &Option<mpsc::Sender<MSG>>,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "remove(key: string) => primitive";
+        let usage = "filter(fn) expect one argument of type [Closure]";
 
-        if args.len() != 1 {
+        let closure = get_closure_arg(args, usage, data, interval)?;
+        let entries = keyed_values(&object.value, interval);
+        let results = exec_closure_on_values(closure, &entries, interval, data, msg_data, sender)?;
+
+        let result: HashMap<String, Literal> = entries
+            .into_iter()
+            .zip(results.iter())
+            .filter(|(_, result)| result.primitive.as_bool())
+            .map(|((value, key), _)| (key.primitive.to_string(), value))
+            .collect();
+
+        Ok(PrimitiveObject::get_literal(&result, interval))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTION
+////////////////////////////////////////////////////////////////////////////////
+
+fn get_crypto_value(
+    object: &PrimitiveObject,
+    data: &Data,
+    interval: Interval,
+) -> Result<String, ErrorInfo> {
+    match object.value.get("value") {
+        Some(literal) => Literal::get_value::<String>(
+            &literal.primitive,
+            &data.context.flow,
+            interval,
+            ERROR_HASH.to_owned(),
+        )
+        .map(|value| value.to_owned()),
+        None => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            ERROR_HASH.to_owned(),
+        )),
+    }
+}
+
+fn get_crypto_arg(
+    args: &HashMap<String, Literal>,
+    index: usize,
+    usage: &str,
+    data: &Data,
+    interval: Interval,
+) -> Result<String, ErrorInfo> {
+    match args.get(&format!("arg{}", index)) {
+        Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveString => {
+            Ok(lit.primitive.to_string())
+        }
+        _ => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            format!("usage: {}", usage),
+        )),
+    }
+}
+
+fn get_crypto_option(
+    args: &HashMap<String, Literal>,
+    index: usize,
+    default: &str,
+    usage: &str,
+    data: &Data,
+    interval: Interval,
+) -> Result<String, ErrorInfo> {
+    match args.get(&format!("arg{}", index)) {
+        None => Ok(default.to_owned()),
+        Some(_) => get_crypto_arg(args, index, usage, data, interval),
+    }
+}
+
+fn get_path_arg(
+    args: &HashMap<String, Literal>,
+    error: &str,
+    data: &Data,
+    interval: Interval,
+) -> Result<Vec<PathSegment>, ErrorInfo> {
+    let path = match args.get("arg0") {
+        Some(res) if res.primitive.get_type() == PrimitiveType::PrimitiveString => {
+            Literal::get_value::<String>(
+                &res.primitive,
+                &data.context.flow,
+                interval,
+                error.to_owned(),
+            )?
+        }
+        _ => {
             return Err(gen_error_info(
                 Position::new(interval, &data.context.flow),
-                format!("usage: {}", usage),
+                error.to_owned(),
             ));
         }
+    };
 
-        let key = match args.get("arg0") {
-            Some(res) if res.primitive.get_type() == PrimitiveType::PrimitiveString => {
-                Literal::get_value::<String>(
-                    &res.primitive,
-                    &data.context.flow,
-                    interval,
-                    ERROR_OBJECT_REMOVE.to_owned(),
-                )?
+    match parse_path(path) {
+        Some(path) => Ok(path),
+        None => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            format!("{}: {}", ERROR_OBJECT_PATH_FORMAT, path),
+        )),
+    }
+}
+
+/**
+ * Split a path like "user.addresses[0].city" in keys and array indexes
+ */
+fn parse_path(path: &str) -> Option<Vec<PathSegment>> {
+    let mut segments = vec![];
+    let mut key = String::new();
+    let mut chars = path.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                if !key.is_empty() {
+                    segments.push(PathSegment::Key(std::mem::take(&mut key)));
+                } else if !matches!(segments.last(), Some(PathSegment::Index(_))) {
+                    return None;
+                }
             }
-            _ => {
-                return Err(gen_error_info(
-                    Position::new(interval, &data.context.flow),
-                    ERROR_OBJECT_REMOVE.to_owned(),
-                ));
+            '[' => {
+                if !key.is_empty() {
+                    segments.push(PathSegment::Key(std::mem::take(&mut key)));
+                }
+
+                let mut index = String::new();
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(c) => index.push(c),
+                        None => return None,
+                    }
+                }
+                segments.push(PathSegment::Index(index.trim().parse().ok()?));
             }
+            _ => key.push(c),
+        }
+    }
+
+    if !key.is_empty() {
+        segments.push(PathSegment::Key(key));
+    } else if path.ends_with('.') || segments.is_empty() {
+        return None;
+    }
+
+    Some(segments)
+}
+
+fn get_path_value<'a>(
+    object: &'a HashMap<String, Literal>,
+    path: &[PathSegment],
+) -> Option<&'a Literal> {
+    let (first, rest) = path.split_first()?;
+    let mut current = match first {
+        PathSegment::Key(key) => object.get(key)?,
+        PathSegment::Index(_) => return None,
+    };
+
+    for segment in rest.iter() {
+        current = match segment {
+            PathSegment::Key(key) => current
+                .primitive
+                .as_any()
+                .downcast_ref::<PrimitiveObject>()?
+                .value
+                .get(key)?,
+            PathSegment::Index(index) => current
+                .primitive
+                .as_any()
+                .downcast_ref::<PrimitiveArray>()?
+                .value
+                .get(*index)?,
         };
+    }
 
-        match object.value.remove(key) {
-            Some(value) => Ok(value),
-            None => Ok(PrimitiveNull::get_literal(interval)),
+    Some(current)
+}
+
+fn gen_path_container(segment: &PathSegment, interval: Interval) -> Literal {
+    match segment {
+        PathSegment::Key(_) => PrimitiveObject::get_literal(&HashMap::new(), interval),
+        PathSegment::Index(_) => PrimitiveArray::get_literal(&[], interval),
+    }
+}
+
+/**
+ * Missing keys are created along the path, an array index can replace a value
+ * or append one at the end of the array.
+ */
+fn set_object_path(
+    object: &mut HashMap<String, Literal>,
+    key: &str,
+    rest: &[PathSegment],
+    value: Literal,
+    interval: Interval,
+) -> bool {
+    let next = match rest.first() {
+        Some(next) => next,
+        None => {
+            object.insert(key.to_owned(), value);
+            return true;
         }
+    };
+
+    let entry = object
+        .entry(key.to_owned())
+        .or_insert_with(|| gen_path_container(next, interval));
+    if entry.primitive.get_type() == PrimitiveType::PrimitiveNull {
+        *entry = gen_path_container(next, interval);
     }
+
+    set_literal_path(entry, rest, value, interval)
 }
 
-////////////////////////////////////////////////////////////////////////////////
-// PRIVATE FUNCTION
-////////////////////////////////////////////////////////////////////////////////
+fn set_literal_path(
+    literal: &mut Literal,
+    path: &[PathSegment],
+    value: Literal,
+    interval: Interval,
+) -> bool {
+    let (first, rest) = match path.split_first() {
+        Some(split) => split,
+        None => {
+            *literal = value;
+            return true;
+        }
+    };
+
+    match first {
+        PathSegment::Key(key) => {
+            match literal
+                .primitive
+                .get_mut_value()
+                .downcast_mut::<HashMap<String, Literal>>()
+            {
+                Some(object) => set_object_path(object, key, rest, value, interval),
+                None => false,
+            }
+        }
+        PathSegment::Index(index) => {
+            let array = match literal
+                .primitive
+                .get_mut_value()
+                .downcast_mut::<Vec<Literal>>()
+            {
+                Some(array) => array,
+                None => return false,
+            };
+
+            if *index < array.len() {
+                set_literal_path(&mut array[*index], rest, value, interval)
+            } else if *index == array.len() {
+                let mut new_value = match rest.first() {
+                    Some(next) => gen_path_container(next, interval),
+                    None => value.clone(),
+                };
+                if !rest.is_empty() && !set_literal_path(&mut new_value, rest, value, interval) {
+                    return false;
+                }
+                array.push(new_value);
+                true
+            } else {
+                false
+            }
+        }
+    }
+}
+
+fn merge_objects(dst: &mut HashMap<String, Literal>, src: &HashMap<String, Literal>) {
+    for (key, value) in src.iter() {
+        match (
+            dst.get_mut(key),
+            value.primitive.as_any().downcast_ref::<PrimitiveObject>(),
+        ) {
+            (Some(current), Some(src_object)) => {
+                match current
+                    .primitive
+                    .get_mut_value()
+                    .downcast_mut::<HashMap<String, Literal>>()
+                {
+                    Some(dst_object) => merge_objects(dst_object, &src_object.value),
+                    None => *current = value.to_owned(),
+                }
+            }
+            _ => {
+                dst.insert(key.to_owned(), value.to_owned());
+            }
+        }
+    }
+}
+
+fn get_keys_arg(
+    args: &HashMap<String, Literal>,
+    error: &str,
+    data: &Data,
+    interval: Interval,
+) -> Result<Vec<String>, ErrorInfo> {
+    let keys = match args.get("arg0") {
+        Some(res)
+            if args.len() == 1 && res.primitive.get_type() == PrimitiveType::PrimitiveArray =>
+        {
+            Literal::get_value::<Vec<Literal>>(
+                &res.primitive,
+                &data.context.flow,
+                interval,
+                error.to_owned(),
+            )?
+        }
+        _ => {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                error.to_owned(),
+            ));
+        }
+    };
+
+    keys.iter()
+        .map(|key| {
+            Literal::get_value::<String>(
+                &key.primitive,
+                &data.context.flow,
+                interval,
+                error.to_owned(),
+            )
+            .map(|key| key.to_owned())
+        })
+        .collect()
+}
+
+fn get_closure_arg<'a>(
+    args: &'a HashMap<String, Literal>,
+    usage: &str,
+    data: &Data,
+    interval: Interval,
+) -> Result<&'a PrimitiveClosure, ErrorInfo> {
+    match args.get("arg0") {
+        Some(lit) => Literal::get_value::<PrimitiveClosure>(
+            &lit.primitive,
+            &data.context.flow,
+            interval,
+            format!("usage: {}", usage),
+        ),
+        None => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            format!("usage: {}", usage),
+        )),
+    }
+}
+
+fn keyed_values(object: &HashMap<String, Literal>, interval: Interval) -> Vec<(Literal, Literal)> {
+    object
+        .iter()
+        .map(|(key, value)| {
+            (
+                value.to_owned(),
+                PrimitiveString::get_literal(key, interval),
+            )
+        })
+        .collect()
+}
 
 fn insert_to_object(
     src: &HashMap<String, Literal>,
@@ -2734,26 +4550,47 @@ impl Primitive for PrimitiveObject {
         let jwt = vec![FUNCTIONS_JWT];
         let crypto = vec![FUNCTIONS_CRYPTO];
         let time = vec![FUNCTIONS_TIME];
+        let knowledge = vec![FUNCTIONS_KNOWLEDGE];
+        let llm = vec![FUNCTIONS_LLM, FUNCTIONS_READ];
         let generics = vec![FUNCTIONS_READ, FUNCTIONS_WRITE];
 
         let mut is_event = false;
 
-        let (content_type, vector) = match content_type {
+        // the closure methods are available wherever the read methods are
+        let (content_type, vector, closures) = match content_type {
             ContentType::Event(event_type) => {
                 is_event = true;
 
-                (event_type.as_ref(), event)
+                (event_type.as_ref(), event, false)
             }
-            ContentType::Http => ("", http),
-            ContentType::Smtp => ("", smtp),
-            ContentType::Base64 => ("", base64),
-            ContentType::Hex => ("", hex),
-            ContentType::Jwt => ("", jwt),
-            ContentType::Crypto => ("", crypto),
-            ContentType::Time => ("", time),
-            ContentType::Primitive => ("", generics),
+            ContentType::Http => ("", http, true),
+            ContentType::Smtp => ("", smtp, false),
+            ContentType::Base64 => ("", base64, false),
+            ContentType::Hex => ("", hex, false),
+            ContentType::Jwt => ("", jwt, false),
+            ContentType::Crypto => ("", crypto, false),
+            ContentType::Time => ("", time, false),
+            ContentType::Knowledge => ("", knowledge, false),
+            ContentType::Llm => ("", llm, true),
+            ContentType::Primitive => ("", generics, true),
         };
 
+        if closures {
+            if let Some((f, right)) = FUNCTIONS_CLOSURE.get(name) {
+                let result = f(
+                    self,
+                    args,
+                    additional_info,
+                    data,
+                    interval,
+                    msg_data,
+                    sender,
+                )?;
+
+                return Ok((result, *right));
+            }
+        }
+
         for function in vector.iter() {
             if let Some((f, right)) = function.get(name) {
                 if *mem_type == MemoryType::Constant && *right == Right::Write {
@@ -2,6 +2,7 @@ use crate::data::error_info::ErrorInfo;
 use crate::data::position::Position;
 use crate::data::primitive::array::PrimitiveArray;
 use crate::data::primitive::boolean::PrimitiveBoolean;
+use crate::data::primitive::closure::{exec_closure_on_values, PrimitiveClosure};
 use crate::data::primitive::float::PrimitiveFloat;
 use crate::data::primitive::int::PrimitiveInt;
 use crate::data::primitive::null::PrimitiveNull;
@@ -14,8 +15,9 @@ use crate::data::{literal, literal::ContentType};
 use crate::error_format::*;
 use crate::interpreter::json_to_literal;
 // use http::Uri;
+use once_cell::sync::Lazy;
 use phf::phf_map;
-use regex::Regex;
+use regex::{Captures, Regex};
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::{collections::HashMap, sync::mpsc};
@@ -59,12 +61,17 @@ const FUNCTIONS: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map! {
     "contains" => (PrimitiveString::contains as PrimitiveMethod, Right::Read),
     "contains_regex" => (PrimitiveString::contains_regex as PrimitiveMethod, Right::Read),
     "replace_regex" => (PrimitiveString::replace_regex as PrimitiveMethod, Right::Read),
+    "captures" => (PrimitiveString::captures as PrimitiveMethod, Right::Read),
+    "captures_all" => (PrimitiveString::captures_all as PrimitiveMethod, Right::Read),
     "replace_all" => (PrimitiveString::replace_all as PrimitiveMethod, Right::Read),
     "replace" => (PrimitiveString::replace as PrimitiveMethod, Right::Read),
 
     "ends_with" => (PrimitiveString::ends_with as PrimitiveMethod, Right::Read),
     "ends_with_regex" => (PrimitiveString::ends_with_regex as PrimitiveMethod, Right::Read),
     "from_json" => (PrimitiveString::from_json as PrimitiveMethod, Right::Read),
+    "from_xml" => (PrimitiveString::from_xml as PrimitiveMethod, Right::Read),
+    "from_yaml" => (PrimitiveString::from_yaml as PrimitiveMethod, Right::Read),
+    "from_csv" => (PrimitiveString::from_csv as PrimitiveMethod, Right::Read),
     "is_empty" => (PrimitiveString::is_empty as PrimitiveMethod, Right::Read),
     "length" => (PrimitiveString::length as PrimitiveMethod, Right::Read),
     "match" => (PrimitiveString::do_match as PrimitiveMethod, Right::Read),
@@ -94,6 +101,9 @@ const FUNCTIONS: phf::Map<&'static str, (PrimitiveMethod, Right)> = phf_map! {
     "to_float" =>(PrimitiveString::to_float as PrimitiveMethod, Right::Read),
 };
 
+// used by is_email and by the email fields of the forms
+pub static EMAIL_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[^@]+@[^@]+\.[^@]+$").unwrap());
+
 #[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub struct PrimitiveString {
     pub value: String,
@@ -103,6 +113,109 @@ pub struct PrimitiveString {
 // PRIVATE FUNCTIONS
 ////////////////////////////////////////////////////////////////////////////////
 
+// attributes are prefixed with "@" and the text of an element with attributes is in "$text"
+fn get_xml_config() -> quickxml_to_serde::Config {
+    quickxml_to_serde::Config::new_with_custom_values(
+        true,
+        "@",
+        "$text",
+        quickxml_to_serde::NullValue::Ignore,
+    )
+}
+
+fn get_regex(
+    args: &HashMap<String, Literal>,
+    data: &Data,
+    interval: Interval,
+    error: &str,
+) -> Result<Regex, ErrorInfo> {
+    let regex = match args.get("arg0") {
+        Some(res) if res.primitive.get_type() == PrimitiveType::PrimitiveString => {
+            Literal::get_value::<String>(
+                &res.primitive,
+                &data.context.flow,
+                interval,
+                error.to_owned(),
+            )?
+        }
+        _ => {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                error.to_owned(),
+            ));
+        }
+    };
+
+    match Regex::new(regex) {
+        Ok(regex) => Ok(regex),
+        Err(err) => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            format!("{}: {}", ERROR_STRING_VALID_REGEX, err),
+        )),
+    }
+}
+
+/**
+ * Groups of a match, the first one being the whole match. Each group has its index,
+ * its name, its value and its offsets in bytes (start, end) and in chars (char_start, char_end).
+ * Groups that did not participate in the match are null.
+ */
+fn captures_to_literal(
+    regex: &Regex,
+    captures: &Captures,
+    text: &str,
+    interval: Interval,
+) -> Literal {
+    let groups: Vec<Literal> = regex
+        .capture_names()
+        .enumerate()
+        .map(|(index, name)| match captures.get(index) {
+            Some(group) => {
+                let char_start = text[..group.start()].chars().count();
+                let char_end = char_start + group.as_str().chars().count();
+
+                let mut object = HashMap::new();
+                object.insert(
+                    "index".to_owned(),
+                    PrimitiveInt::get_literal(index as i64, interval),
+                );
+                object.insert(
+                    "name".to_owned(),
+                    match name {
+                        Some(name) => PrimitiveString::get_literal(name, interval),
+                        None => PrimitiveNull::get_literal(interval),
+                    },
+                );
+                object.insert(
+                    "value".to_owned(),
+                    PrimitiveString::get_literal(group.as_str(), interval),
+                );
+                object.insert(
+                    "start".to_owned(),
+                    PrimitiveInt::get_literal(group.start() as i64, interval),
+                );
+                object.insert(
+                    "end".to_owned(),
+                    PrimitiveInt::get_literal(group.end() as i64, interval),
+                );
+                object.insert(
+                    "char_start".to_owned(),
+                    PrimitiveInt::get_literal(char_start as i64, interval),
+                );
+                object.insert(
+                    "char_end".to_owned(),
+                    PrimitiveInt::get_literal(char_end as i64, interval),
+                );
+
+                PrimitiveObject::get_literal(&object, interval)
+            }
+            None => PrimitiveNull::get_literal(interval),
+        })
+        .collect();
+
+    PrimitiveArray::get_literal(&groups, interval)
+}
+
 fn encode_value(pairs: &Parse) -> String {
     let mut vec = vec![];
 
@@ -275,9 +388,7 @@ impl PrimitiveString {
             ));
         }
 
-        let email_regex = Regex::new(r"^[^@]+@[^@]+\.[^@]+$").unwrap();
-
-        let result = email_regex.is_match(&string.value);
+        let result = EMAIL_REGEX.is_match(&string.value);
 
         Ok(PrimitiveBoolean::get_literal(result, interval))
     }
@@ -373,15 +484,8 @@ impl PrimitiveString {
 
         let value = string.to_string();
 
-        let config = quickxml_to_serde::Config::new_with_custom_values(
-            true,
-            "@",
-            "$text",
-            quickxml_to_serde::NullValue::Ignore,
-        );
-
         let xml: Option<serde_json::Value> =
-            quickxml_to_serde::xml_string_to_json(value.clone(), &config).ok();
+            quickxml_to_serde::xml_string_to_json(value.clone(), &get_xml_config()).ok();
 
         let yaml: Option<serde_json::Value> = serde_yaml::from_str(&value).ok();
 
@@ -845,10 +949,11 @@ impl PrimitiveString {
         _additional_info: &Option<HashMap<String, Literal>>,
         interval: Interval,
         data: &mut Data,
-        _msg_data: &mut MessageData,
-        _sender: &Option<mpsc::Sender<MSG>>,
+        msg_data: &mut MessageData,
+        sender: &Option<mpsc::Sender<MSG>>,
     ) -> Result<Literal, ErrorInfo> {
-        let usage = "replace_regex(regex: string, replace_by: string) => string";
+        let usage =
+            "replace_regex(regex: string, replace_by: string || fn(groups, index)) => string";
 
         if args.len() != 2 {
             return Err(gen_error_info(
@@ -857,47 +962,118 @@ impl PrimitiveString {
             ));
         }
 
-        let (regex, replace_by) = match (args.get("arg0"), args.get("arg1")) {
-            (Some(old), Some(new))
-                if old.primitive.get_type() == PrimitiveType::PrimitiveString
-                    && new.primitive.get_type() == PrimitiveType::PrimitiveString =>
-            {
-                (
-                    Literal::get_value::<String>(
-                        &old.primitive,
-                        &data.context.flow,
-                        interval,
-                        ERROR_STRING_REPLACE_REGEX.to_owned(),
-                    )?,
-                    Literal::get_value::<String>(
-                        &new.primitive,
-                        &data.context.flow,
-                        interval,
-                        ERROR_STRING_REPLACE_REGEX.to_owned(),
-                    )?,
-                )
-            }
-            _ => {
-                return Err(gen_error_info(
-                    Position::new(interval, &data.context.flow),
+        let regex = get_regex(args, data, interval, ERROR_STRING_REPLACE_REGEX)?;
+
+        match args.get("arg1") {
+            // groups can be referenced in the replacement: "$1", "$name" or "${name}"
+            Some(new) if new.primitive.get_type() == PrimitiveType::PrimitiveString => {
+                let replace_by = Literal::get_value::<String>(
+                    &new.primitive,
+                    &data.context.flow,
+                    interval,
                     ERROR_STRING_REPLACE_REGEX.to_owned(),
-                ));
-            }
-        };
+                )?;
 
-        let reg = match Regex::new(regex) {
-            Ok(res) => res,
-            Err(_) => {
-                return Err(gen_error_info(
-                    Position::new(interval, &data.context.flow),
+                let new_string = regex.replace_all(&string.value, replace_by.as_str());
+
+                Ok(PrimitiveString::get_literal(&new_string, interval))
+            }
+            // the closure is called with the groups of each match and returns its replacement
+            Some(new) if new.primitive.get_type() == PrimitiveType::PrimitiveClosure => {
+                let closure = Literal::get_value::<PrimitiveClosure>(
+                    &new.primitive,
+                    &data.context.flow,
+                    interval,
                     ERROR_STRING_REPLACE_REGEX.to_owned(),
-                ));
+                )?;
+
+                let matches: Vec<(Literal, Literal)> = regex
+                    .captures_iter(&string.value)
+                    .enumerate()
+                    .map(|(index, captures)| {
+                        (
+                            captures_to_literal(&regex, &captures, &string.value, interval),
+                            PrimitiveInt::get_literal(index as i64, interval),
+                        )
+                    })
+                    .collect();
+
+                let replacements =
+                    exec_closure_on_values(closure, &matches, interval, data, msg_data, sender)?;
+                let mut replacements = replacements.iter();
+
+                let new_string =
+                    regex.replace_all(&string.value, |_: &Captures| match replacements.next() {
+                        Some(replacement) => replacement.primitive.to_string(),
+                        None => String::new(),
+                    });
+
+                Ok(PrimitiveString::get_literal(&new_string, interval))
             }
-        };
+            _ => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                ERROR_STRING_REPLACE_REGEX.to_owned(),
+            )),
+        }
+    }
 
-        let new_string = reg.replace_all(&string.value, replace_by);
+    fn captures(
+        string: &mut PrimitiveString,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "captures(regex: string) => [groups]";
 
-        Ok(PrimitiveString::get_literal(&new_string, interval))
+        if args.len() != 1 {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let regex = get_regex(args, data, interval, ERROR_STRING_CAPTURES)?;
+
+        match regex.captures(&string.value) {
+            Some(captures) => Ok(captures_to_literal(
+                &regex,
+                &captures,
+                &string.value,
+                interval,
+            )),
+            None => Ok(PrimitiveArray::get_literal(&[], interval)),
+        }
+    }
+
+    fn captures_all(
+        string: &mut PrimitiveString,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "captures_all(regex: string) => [[groups]]";
+
+        if args.len() != 1 {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        let regex = get_regex(args, data, interval, ERROR_STRING_CAPTURES)?;
+
+        let matches: Vec<Literal> = regex
+            .captures_iter(&string.value)
+            .map(|captures| captures_to_literal(&regex, &captures, &string.value, interval))
+            .collect();
+
+        Ok(PrimitiveArray::get_literal(&matches, interval))
     }
 
     fn ends_with(
@@ -1025,6 +1201,121 @@ impl PrimitiveString {
         json_to_literal(&object, interval, &data.context.flow)
     }
 
+    fn from_xml(
+        string: &mut PrimitiveString,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "from_xml() => object";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        if let Err(err) = check_xml(&string.value) {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("{}: {}", ERROR_STRING_FROM_XML, err),
+            ));
+        }
+
+        match quickxml_to_serde::xml_string_to_json(string.value.clone(), &get_xml_config()) {
+            Ok(object) => json_to_literal(&object, interval, &data.context.flow),
+            Err(err) => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("{}: {}", ERROR_STRING_FROM_XML, err),
+            )),
+        }
+    }
+
+    fn from_yaml(
+        string: &mut PrimitiveString,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let usage = "from_yaml() => object";
+
+        if !args.is_empty() {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("usage: {}", usage),
+            ));
+        }
+
+        match serde_yaml::from_str::<serde_json::Value>(&string.value) {
+            Ok(object) => json_to_literal(&object, interval, &data.context.flow),
+            Err(err) => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("{}: {}", ERROR_STRING_FROM_YAML, err),
+            )),
+        }
+    }
+
+    fn from_csv(
+        string: &mut PrimitiveString,
+        args: &HashMap<String, Literal>,
+        _additional_info: &Option<HashMap<String, Literal>>,
+        interval: Interval,
+        data: &mut Data,
+        _msg_data: &mut MessageData,
+        _sender: &Option<mpsc::Sender<MSG>>,
+    ) -> Result<Literal, ErrorInfo> {
+        let options = match args.get("arg0") {
+            Some(res)
+                if args.len() == 1
+                    && res.primitive.get_type() == PrimitiveType::PrimitiveObject =>
+            {
+                Literal::get_value::<HashMap<String, Literal>>(
+                    &res.primitive,
+                    &data.context.flow,
+                    interval,
+                    ERROR_STRING_FROM_CSV.to_owned(),
+                )?
+                .to_owned()
+            }
+            None if args.is_empty() => HashMap::new(),
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_STRING_FROM_CSV.to_owned(),
+                ));
+            }
+        };
+
+        let delimiter = match get_csv_delimiter(&options) {
+            Some(delimiter) => delimiter,
+            None => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_STRING_FROM_CSV.to_owned(),
+                ));
+            }
+        };
+        let header = match options.get("header") {
+            Some(header) => header.primitive.as_bool(),
+            None => true,
+        };
+
+        match csv_to_json(&string.value, delimiter, header) {
+            Ok(rows) => json_to_literal(&rows, interval, &data.context.flow),
+            Err(err) => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("[from_csv] {}", err),
+            )),
+        }
+    }
+
     fn is_empty(
         string: &mut PrimitiveString,
         args: &HashMap<String, Literal>,
@@ -1,6 +1,8 @@
 use crate::data::primitive::{PrimitiveString, PrimitiveType};
 use crate::data::{Literal, Position};
 use crate::error_format::*;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
 
 ////////////////////////////////////////////////////////////////////////////////
 // DATA STRUCTURE
@@ -71,3 +73,277 @@ pub fn check_division_by_zero_f64(lhs: f64, rhs: f64) -> Result<f64, String> {
 
     Ok(lhs)
 }
+
+pub fn get_levenshtein_distance(lhs: &str, rhs: &str) -> usize {
+    let lhs: Vec<char> = lhs.chars().collect();
+    let rhs: Vec<char> = rhs.chars().collect();
+
+    let mut previous: Vec<usize> = (0..=rhs.len()).collect();
+    let mut current = vec![0; rhs.len() + 1];
+
+    for (i, lhs_char) in lhs.iter().enumerate() {
+        current[0] = i + 1;
+
+        for (j, rhs_char) in rhs.iter().enumerate() {
+            let cost = if lhs_char == rhs_char { 0 } else { 1 };
+
+            current[j + 1] = std::cmp::min(
+                std::cmp::min(previous[j + 1] + 1, current[j] + 1),
+                previous[j] + cost,
+            );
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[rhs.len()]
+}
+
+pub fn get_similarity(lhs: &str, rhs: &str) -> f64 {
+    let len = std::cmp::max(lhs.chars().count(), rhs.chars().count());
+
+    if len == 0 {
+        return 1.0;
+    }
+
+    1.0 - (get_levenshtein_distance(lhs, rhs) as f64 / len as f64)
+}
+
+fn fold_accent(c: char) -> &'static str {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        'æ' => "ae",
+        'œ' => "oe",
+        'ß' => "ss",
+        _ => "",
+    }
+}
+
+fn is_combining_mark(c: char) -> bool {
+    ('\u{0300}'..='\u{036F}').contains(&c)
+}
+
+/**
+ * Lowercase the text, replace punctuation by spaces and collapse whitespaces.
+ * If fold_accents is set, accented latin letters are replaced by their base letter
+ * and combining marks (decomposed accents) are removed.
+ */
+pub fn normalize_text(text: &str, fold_accents: bool) -> String {
+    let mut result = String::with_capacity(text.len());
+
+    for c in text.to_lowercase().chars() {
+        match c {
+            c if is_combining_mark(c) => {
+                if !fold_accents {
+                    result.push(c)
+                }
+            }
+            c if fold_accents && !fold_accent(c).is_empty() => result.push_str(fold_accent(c)),
+            c if c.is_alphanumeric() => result.push(c),
+            _ => result.push(' '),
+        }
+    }
+
+    get_tokens(&result).join(" ")
+}
+
+pub fn get_tokens(text: &str) -> Vec<&str> {
+    text.split_whitespace().collect()
+}
+
+/**
+ * Split a CSV text into rows of fields.
+ * Fields can be quoted with double quotes, a double quote inside a quoted field is escaped by doubling it.
+ */
+pub fn parse_csv(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => row.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+
+                // skip empty lines
+                match row.as_slice() {
+                    [field] if field.is_empty() => row.clear(),
+                    _ => rows.push(std::mem::take(&mut row)),
+                }
+            }
+            c => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows
+}
+
+/**
+ * Convert CSV rows to JSON. With a header, each row is converted to an object whose keys
+ * are the columns of the header, missing fields at the end of a row are set to null
+ * and a row with more fields than the header is an error.
+ * Without a header each row is an array of fields.
+ */
+pub fn csv_to_json(content: &str, delimiter: char, header: bool) -> Result<Value, String> {
+    let mut rows = parse_csv(content, delimiter).into_iter();
+
+    if !header {
+        return Ok(Value::Array(
+            rows.map(|row| Value::Array(row.into_iter().map(Value::String).collect()))
+                .collect(),
+        ));
+    }
+
+    let header = match rows.next() {
+        Some(header) => header,
+        None => return Ok(Value::Array(vec![])),
+    };
+
+    let mut objects = vec![];
+    for (index, row) in rows.enumerate() {
+        if row.len() > header.len() {
+            return Err(format!(
+                "{} [row {} has {} fields, the header has {} columns]",
+                ERROR_CSV_ROW_LENGTH,
+                index + 1,
+                row.len(),
+                header.len()
+            ));
+        }
+
+        let mut row = row.into_iter();
+        let object: Map<String, Value> = header
+            .iter()
+            .map(|column| {
+                let value = match row.next() {
+                    Some(value) => Value::String(value),
+                    None => Value::Null,
+                };
+
+                (column.trim().to_owned(), value)
+            })
+            .collect();
+
+        objects.push(Value::Object(object));
+    }
+
+    Ok(Value::Array(objects))
+}
+
+/**
+ * Check that an XML document is well-formed: the elements are closed in order
+ * and there is a single root element.
+ */
+pub fn check_xml(content: &str) -> Result<(), String> {
+    use quick_xml::events::Event;
+
+    let mut reader = quick_xml::Reader::from_str(content);
+    let mut buf = vec![];
+    let mut depth = 0;
+    let mut roots = 0;
+
+    loop {
+        match reader.read_event(&mut buf) {
+            Ok(Event::Start(_)) => {
+                if depth == 0 {
+                    roots += 1;
+                }
+                depth += 1;
+            }
+            Ok(Event::End(_)) => depth -= 1,
+            Ok(Event::Empty(_)) if depth == 0 => roots += 1,
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(err) => return Err(format!("{} at position {}", err, reader.buffer_position())),
+        }
+
+        buf.clear();
+    }
+
+    match (depth, roots) {
+        (0, 1) => Ok(()),
+        (0, _) => Err("the document must have a single root element".to_owned()),
+        _ => Err("unclosed element at the end of the document".to_owned()),
+    }
+}
+
+/**
+ * Join rows of fields into a CSV text, fields containing the delimiter,
+ * a double quote, a line feed or a carriage return are quoted.
+ */
+pub fn rows_to_csv(rows: &[Vec<String>], delimiter: char) -> String {
+    let mut csv = String::new();
+
+    for row in rows.iter() {
+        let fields: Vec<String> = row
+            .iter()
+            .map(|field| {
+                if field.contains(delimiter)
+                    || field.contains('"')
+                    || field.contains('\n')
+                    || field.contains('\r')
+                {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.to_owned()
+                }
+            })
+            .collect();
+
+        csv.push_str(&fields.join(&delimiter.to_string()));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/**
+ * The delimiter option of from_csv and to_csv must be a single character, "," by default.
+ */
+pub fn get_csv_delimiter(options: &HashMap<String, Literal>) -> Option<char> {
+    match options.get("delimiter") {
+        Some(delimiter) if delimiter.primitive.get_type() == PrimitiveType::PrimitiveString => {
+            let delimiter = delimiter.primitive.to_string();
+            let mut chars = delimiter.chars();
+
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(c),
+                _ => None,
+            }
+        }
+        Some(_) => None,
+        None => Some(','),
+    }
+}
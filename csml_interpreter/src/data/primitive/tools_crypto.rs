@@ -46,3 +46,361 @@ pub fn digest_data(
         )),
     }
 }
+
+// AES-GCM nonce and authentication tag sizes, the ciphertext is encoded as nonce + data + tag
+const GCM_NONCE_SIZE: usize = 12;
+const GCM_TAG_SIZE: usize = 16;
+const MAX_RANDOM_BYTES: usize = 1024;
+
+// password hashes have a fixed cost, and the hashes to verify a bounded one,
+// as the hash is computed in the thread of the interpreter
+const PASSWORD_SALT_SIZE: usize = 16;
+const PASSWORD_HASH_SIZE: usize = 32;
+const MAX_PASSWORD_HASH_SIZE: usize = 64;
+const SCRYPT_LOG_N: u64 = 15;
+const SCRYPT_R: u64 = 8;
+const SCRYPT_P: u64 = 1;
+const MAX_SCRYPT_LOG_N: u64 = 16;
+const MAX_SCRYPT_R: u64 = 8;
+const MAX_SCRYPT_P: u64 = 2;
+const MAX_SCRYPT_MEMORY: u64 = 256 * 1024 * 1024;
+const PBKDF2_ITERATIONS: u64 = 600_000;
+const MAX_PBKDF2_ITERATIONS: u64 = 1_000_000;
+
+fn get_aes_gcm_cipher(
+    key: &str,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<(openssl::symm::Cipher, Vec<u8>), ErrorInfo> {
+    let key = base64::decode(key).unwrap_or_default();
+
+    match key.len() {
+        16 => Ok((openssl::symm::Cipher::aes_128_gcm(), key)),
+        24 => Ok((openssl::symm::Cipher::aes_192_gcm(), key)),
+        32 => Ok((openssl::symm::Cipher::aes_256_gcm(), key)),
+        _ => Err(gen_error_info(
+            Position::new(interval, flow_name),
+            ERROR_CRYPTO_KEY.to_owned(),
+        )),
+    }
+}
+
+fn get_public_key(
+    pem: &str,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<openssl::pkey::PKey<openssl::pkey::Public>, ErrorInfo> {
+    let pem = pem.as_bytes();
+
+    // public keys can also be given as a certificate
+    openssl::pkey::PKey::public_key_from_pem(pem)
+        .or_else(|_| openssl::x509::X509::from_pem(pem).and_then(|cert| cert.public_key()))
+        .map_err(|_| {
+            gen_error_info(
+                Position::new(interval, flow_name),
+                ERROR_CRYPTO_PUBLIC_KEY.to_owned(),
+            )
+        })
+}
+
+/**
+ * AES-GCM encryption with a random nonce, the key is a base64 String of 16, 24 or 32 bytes.
+ */
+pub fn aes_gcm_encrypt(
+    value: &[u8],
+    key: &str,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<String, ErrorInfo> {
+    let (cipher, key) = get_aes_gcm_cipher(key, flow_name, interval)?;
+
+    let mut nonce = [0; GCM_NONCE_SIZE];
+    let mut tag = [0; GCM_TAG_SIZE];
+
+    let encrypted = openssl::rand::rand_bytes(&mut nonce).and_then(|_| {
+        openssl::symm::encrypt_aead(cipher, &key, Some(&nonce), &[], value, &mut tag)
+    });
+
+    match encrypted {
+        Ok(encrypted) => Ok(base64::encode(
+            [&nonce[..], &encrypted[..], &tag[..]].concat(),
+        )),
+        Err(e) => Err(gen_error_info(
+            Position::new(interval, flow_name),
+            format!("{}", e),
+        )),
+    }
+}
+
+pub fn aes_gcm_decrypt(
+    value: &str,
+    key: &str,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<Vec<u8>, ErrorInfo> {
+    let (cipher, key) = get_aes_gcm_cipher(key, flow_name, interval)?;
+
+    let error = || {
+        gen_error_info(
+            Position::new(interval, flow_name),
+            ERROR_CRYPTO_DECRYPT.to_owned(),
+        )
+    };
+
+    let value = base64::decode(value).map_err(|_| error())?;
+    if value.len() < GCM_NONCE_SIZE + GCM_TAG_SIZE {
+        return Err(error());
+    }
+
+    let (nonce, value) = value.split_at(GCM_NONCE_SIZE);
+    let (encrypted, tag) = value.split_at(value.len() - GCM_TAG_SIZE);
+
+    openssl::symm::decrypt_aead(cipher, &key, Some(nonce), &[], encrypted, tag).map_err(|_| error())
+}
+
+/**
+ * RSA (PKCS#1 v1.5) or ECDSA signature depending on the PEM private key, encoded in base64.
+ */
+pub fn sign(
+    value: &[u8],
+    private_key: &str,
+    algo: &str,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<String, ErrorInfo> {
+    let algo = get_hash_algorithm(algo, flow_name, interval)?;
+
+    let key = openssl::pkey::PKey::private_key_from_pem(private_key.as_bytes()).map_err(|_| {
+        gen_error_info(
+            Position::new(interval, flow_name),
+            ERROR_CRYPTO_PRIVATE_KEY.to_owned(),
+        )
+    })?;
+
+    let signature = openssl::sign::Signer::new(algo, &key).and_then(|mut signer| {
+        signer.update(value)?;
+        signer.sign_to_vec()
+    });
+
+    match signature {
+        Ok(signature) => Ok(base64::encode(signature)),
+        Err(e) => Err(gen_error_info(
+            Position::new(interval, flow_name),
+            format!("{}", e),
+        )),
+    }
+}
+
+pub fn verify(
+    value: &[u8],
+    signature: &str,
+    public_key: &str,
+    algo: &str,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<bool, ErrorInfo> {
+    let algo = get_hash_algorithm(algo, flow_name, interval)?;
+    let key = get_public_key(public_key, flow_name, interval)?;
+
+    let signature = match base64::decode(signature) {
+        Ok(signature) => signature,
+        Err(_) => return Ok(false),
+    };
+
+    // a malformed signature is an invalid signature, not an error of the flow
+    let verified = openssl::sign::Verifier::new(algo, &key).and_then(|mut verifier| {
+        verifier.update(value)?;
+        verifier.verify(&signature)
+    });
+
+    Ok(verified.unwrap_or(false))
+}
+
+pub fn random_bytes(size: i64, flow_name: &str, interval: Interval) -> Result<Vec<u8>, ErrorInfo> {
+    if size < 1 || size as usize > MAX_RANDOM_BYTES {
+        return Err(gen_error_info(
+            Position::new(interval, flow_name),
+            ERROR_CRYPTO_RANDOM.to_owned(),
+        ));
+    }
+
+    let mut bytes = vec![0; size as usize];
+
+    match openssl::rand::rand_bytes(&mut bytes) {
+        Ok(_) => Ok(bytes),
+        Err(e) => Err(gen_error_info(
+            Position::new(interval, flow_name),
+            format!("{}", e),
+        )),
+    }
+}
+
+fn scrypt_hash(
+    password: &str,
+    salt: &[u8],
+    (log_n, r, p): (u64, u64, u64),
+    size: usize,
+) -> Result<Vec<u8>, String> {
+    let mut hash = vec![0; size];
+
+    openssl::pkcs5::scrypt(
+        password.as_bytes(),
+        salt,
+        1 << log_n,
+        r,
+        p,
+        MAX_SCRYPT_MEMORY,
+        &mut hash,
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(hash)
+}
+
+fn pbkdf2_hash(
+    password: &str,
+    salt: &[u8],
+    iterations: u64,
+    size: usize,
+) -> Result<Vec<u8>, String> {
+    let mut hash = vec![0; size];
+
+    openssl::pkcs5::pbkdf2_hmac(
+        password.as_bytes(),
+        salt,
+        iterations as usize,
+        openssl::hash::MessageDigest::sha256(),
+        &mut hash,
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(hash)
+}
+
+fn encode_phc(algo: &str, params: &str, salt: &[u8], hash: &[u8]) -> String {
+    format!(
+        "${}${}${}${}",
+        algo,
+        params,
+        base64::encode_config(salt, base64::STANDARD_NO_PAD),
+        base64::encode_config(hash, base64::STANDARD_NO_PAD)
+    )
+}
+
+fn get_phc_param(params: &str, name: &str) -> Option<u64> {
+    params
+        .split(',')
+        .find_map(|param| match param.split_once('=') {
+            Some((key, value)) if key == name => value.parse::<u64>().ok(),
+            _ => None,
+        })
+}
+
+/**
+ * Expected and computed hashes of the password, None if the hash is not a supported
+ * PHC string or if its cost is above the limits.
+ */
+fn hash_like(password: &str, phc: &str) -> Option<(Vec<u8>, Vec<u8>)> {
+    let (algo, params, salt, expected) = match phc.split('$').collect::<Vec<&str>>()[..] {
+        ["", algo, params, salt, expected] => (algo, params, salt, expected),
+        _ => return None,
+    };
+
+    let salt = base64::decode_config(salt, base64::STANDARD_NO_PAD).ok()?;
+    let expected = base64::decode_config(expected, base64::STANDARD_NO_PAD).ok()?;
+    if expected.is_empty() || expected.len() > MAX_PASSWORD_HASH_SIZE {
+        return None;
+    }
+
+    let hash = match algo {
+        "scrypt" => {
+            let log_n = get_phc_param(params, "ln")?;
+            let r = get_phc_param(params, "r")?;
+            let p = get_phc_param(params, "p")?;
+
+            if log_n == 0 || log_n > MAX_SCRYPT_LOG_N {
+                return None;
+            }
+            if r == 0 || r > MAX_SCRYPT_R || p == 0 || p > MAX_SCRYPT_P {
+                return None;
+            }
+
+            scrypt_hash(password, &salt, (log_n, r, p), expected.len()).ok()?
+        }
+        "pbkdf2-sha256" => {
+            let iterations = get_phc_param(params, "i")?;
+
+            if iterations == 0 || iterations > MAX_PBKDF2_ITERATIONS {
+                return None;
+            }
+
+            pbkdf2_hash(password, &salt, iterations, expected.len()).ok()?
+        }
+        _ => return None,
+    };
+
+    Some((expected, hash))
+}
+
+/**
+ * Passwords are hashed with a random salt with scrypt or PBKDF2-HMAC-SHA256,
+ * in the PHC string format.
+ */
+pub fn hash_password(
+    password: &str,
+    algo: &str,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<String, ErrorInfo> {
+    let mut salt = [0; PASSWORD_SALT_SIZE];
+    if let Err(e) = openssl::rand::rand_bytes(&mut salt) {
+        return Err(gen_error_info(
+            Position::new(interval, flow_name),
+            format!("{}", e),
+        ));
+    }
+
+    let hash = match algo {
+        "scrypt" => scrypt_hash(
+            password,
+            &salt,
+            (SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P),
+            PASSWORD_HASH_SIZE,
+        )
+        .map(|hash| {
+            let params = format!("ln={},r={},p={}", SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P);
+
+            encode_phc("scrypt", &params, &salt, &hash)
+        }),
+        "pbkdf2" => {
+            pbkdf2_hash(password, &salt, PBKDF2_ITERATIONS, PASSWORD_HASH_SIZE).map(|hash| {
+                let params = format!("i={}", PBKDF2_ITERATIONS);
+
+                encode_phc("pbkdf2-sha256", &params, &salt, &hash)
+            })
+        }
+        _ => {
+            return Err(gen_error_info(
+                Position::new(interval, flow_name),
+                format!("'{}' {}", algo, ERROR_CRYPTO_PASSWORD_ALGO),
+            ))
+        }
+    };
+
+    hash.map_err(|e| gen_error_info(Position::new(interval, flow_name), e))
+}
+
+pub fn verify_password(
+    password: &str,
+    hash: &str,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<bool, ErrorInfo> {
+    match hash_like(password, hash) {
+        Some((expected, hash)) => Ok(openssl::memcmp::eq(&expected, &hash)),
+        None => Err(gen_error_info(
+            Position::new(interval, flow_name),
+            ERROR_CRYPTO_PASSWORD_HASH.to_owned(),
+        )),
+    }
+}
@@ -1,10 +1,20 @@
-use crate::data::{ast::Interval, position::Position, primitive::PrimitiveString, Literal};
+use crate::data::{
+    ast::Interval,
+    network_policy,
+    position::Position,
+    primitive::{PrimitiveString, PrimitiveType},
+    BotFiles, Literal, NetworkPolicy,
+};
 use crate::error_format::*;
-use crate::interpreter::json_to_literal;
+use crate::interpreter::{builtins::http_builtin::get_agent, json_to_literal};
 
+use once_cell::sync::Lazy;
 use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
 use std::iter::FromIterator;
 use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 use super::PrimitiveObject;
 
@@ -226,13 +236,19 @@ pub fn get_validation(
     for (key, value) in map.iter() {
         match key.as_ref() {
             "leeway" => {
-                validation.leeway = Literal::get_value::<u64>(
+                let leeway = Literal::get_value::<i64>(
                     &value.primitive,
                     flow_name,
                     interval,
                     "JWT Validation 'leeway' must be of type Int".to_owned(),
-                )?
-                .to_owned()
+                )?;
+
+                validation.leeway = u64::try_from(*leeway).map_err(|_| {
+                    gen_error_info(
+                        Position::new(interval, flow_name),
+                        "JWT Validation 'leeway' must be a positive Int".to_owned(),
+                    )
+                })?;
             }
             "validate_exp" => {
                 validation.validate_exp = Literal::get_value::<bool>(
@@ -253,14 +269,27 @@ pub fn get_validation(
                 .to_owned()
             }
             "aud" => {
-                let vec = Literal::get_value::<Vec<String>>(
-                    &value.primitive,
-                    flow_name,
-                    interval,
-                    "JWT Validation 'aud' must be of type Boolean".to_owned(),
-                )?;
+                let aud = match value.primitive.get_type() {
+                    PrimitiveType::PrimitiveString => vec![value.primitive.to_string()],
+                    PrimitiveType::PrimitiveArray => {
+                        let vec = Literal::get_value::<Vec<Literal>>(
+                            &value.primitive,
+                            flow_name,
+                            interval,
+                            "JWT Validation 'aud' must be of type String or Array".to_owned(),
+                        )?;
+
+                        vec.iter().map(|aud| aud.primitive.to_string()).collect()
+                    }
+                    _ => {
+                        return Err(gen_error_info(
+                            Position::new(interval, flow_name),
+                            "JWT Validation 'aud' must be of type String or Array".to_owned(),
+                        ))
+                    }
+                };
 
-                validation.aud = Some(HashSet::from_iter(vec.iter().cloned()));
+                validation.aud = Some(HashSet::from_iter(aud));
             }
             "iss" => {
                 let mut iss = HashSet::new();
@@ -268,7 +297,7 @@ pub fn get_validation(
                     &value.primitive,
                     flow_name,
                     interval,
-                    "JWT Validation 'validate_nbf' must be of type Boolean".to_owned(),
+                    "JWT Validation 'iss' must be of type String".to_owned(),
                 )?
                 .to_owned();
 
@@ -282,7 +311,7 @@ pub fn get_validation(
                         &value.primitive,
                         flow_name,
                         interval,
-                        "JWT Validation 'validate_nbf' must be of type Boolean".to_owned(),
+                        "JWT Validation 'sub' must be of type String".to_owned(),
                     )?
                     .to_owned(),
                 )
@@ -293,3 +322,313 @@ pub fn get_validation(
 
     Ok(())
 }
+
+////////////////////////////////////////////////////////////////////////////////
+// JWKS
+////////////////////////////////////////////////////////////////////////////////
+
+// key sets fetched from a URL are kept for this duration, keys rotated in the meantime
+// are found by fetching the key set again when no key matches the 'kid' of the token
+const JWKS_CACHE_TTL: Duration = Duration::from_secs(600);
+// a key set is fetched again at most once in this interval, so tokens with an unknown
+// 'kid' can not make the bot call the identity provider on every message
+const JWKS_MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+const JWKS_FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+static JWKS_CACHE: Lazy<Mutex<HashMap<String, (Instant, serde_json::Value)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/**
+ * Source of the JSON Web Key Set: a literal, the name of a bot file or a URL.
+ */
+pub enum JwksSource {
+    Value(serde_json::Value),
+    Url(String),
+}
+
+fn fetch_jwks(
+    url: &str,
+    refresh: bool,
+    policies: &[NetworkPolicy],
+) -> Result<serde_json::Value, String> {
+    if let Ok(cache) = JWKS_CACHE.lock() {
+        if let Some((fetched_at, jwks)) = cache.get(url) {
+            let max_age = match refresh {
+                true => JWKS_MIN_REFRESH_INTERVAL,
+                false => JWKS_CACHE_TTL,
+            };
+
+            if fetched_at.elapsed() < max_age {
+                return Ok(jwks.to_owned());
+            }
+        }
+    }
+
+    network_policy::check_url(policies, url)
+        .map_err(|e| format!("{}: {}", ERROR_NETWORK_POLICY, e))?;
+
+    let request = match get_agent(false, policies) {
+        Some(agent) => agent.get(url),
+        None => ureq::get(url),
+    };
+
+    let jwks: serde_json::Value = request
+        .timeout(JWKS_FETCH_TIMEOUT)
+        .call()
+        .map_err(|e| e.to_string())?
+        .into_json()
+        .map_err(|e| e.to_string())?;
+
+    if let Ok(mut cache) = JWKS_CACHE.lock() {
+        cache.insert(url.to_owned(), (Instant::now(), jwks.to_owned()));
+    }
+
+    Ok(jwks)
+}
+
+fn decode_base64_url(value: &serde_json::Value) -> Option<Vec<u8>> {
+    base64::decode_config(value.as_str()?, base64::URL_SAFE_NO_PAD).ok()
+}
+
+/**
+ * Public key of the JWK in PEM format, from its x5c certificate or from its parameters
+ * (n and e for RSA, crv, x and y for EC, crv and x for OKP).
+ */
+fn jwk_to_pem(jwk: &serde_json::Value) -> Option<Vec<u8>> {
+    use openssl::{bn::BigNum, ec::EcGroup, ec::EcKey, nid::Nid, pkey::Id, pkey::PKey, rsa::Rsa};
+
+    if let Some(cert) = jwk["x5c"].get(0).and_then(|cert| cert.as_str()) {
+        let der = base64::decode(cert).ok()?;
+        let cert = openssl::x509::X509::from_der(&der).ok()?;
+
+        return cert.public_key().ok()?.public_key_to_pem().ok();
+    }
+
+    let key = match jwk["kty"].as_str()? {
+        "RSA" => {
+            let n = BigNum::from_slice(&decode_base64_url(&jwk["n"])?).ok()?;
+            let e = BigNum::from_slice(&decode_base64_url(&jwk["e"])?).ok()?;
+
+            PKey::from_rsa(Rsa::from_public_components(n, e).ok()?).ok()?
+        }
+        "EC" => {
+            let curve = match jwk["crv"].as_str()? {
+                "P-256" => Nid::X9_62_PRIME256V1,
+                "P-384" => Nid::SECP384R1,
+                _ => return None,
+            };
+            let group = EcGroup::from_curve_name(curve).ok()?;
+            let x = BigNum::from_slice(&decode_base64_url(&jwk["x"])?).ok()?;
+            let y = BigNum::from_slice(&decode_base64_url(&jwk["y"])?).ok()?;
+
+            PKey::from_ec_key(EcKey::from_public_key_affine_coordinates(&group, &x, &y).ok()?)
+                .ok()?
+        }
+        "OKP" if jwk["crv"].as_str()? == "Ed25519" => {
+            PKey::public_key_from_raw_bytes(&decode_base64_url(&jwk["x"])?, Id::ED25519).ok()?
+        }
+        _ => return None,
+    };
+
+    key.public_key_to_pem().ok()
+}
+
+fn get_key_type(algo: jsonwebtoken::Algorithm) -> Option<&'static str> {
+    match algo {
+        jsonwebtoken::Algorithm::RS256
+        | jsonwebtoken::Algorithm::RS384
+        | jsonwebtoken::Algorithm::RS512
+        | jsonwebtoken::Algorithm::PS256
+        | jsonwebtoken::Algorithm::PS384
+        | jsonwebtoken::Algorithm::PS512 => Some("RSA"),
+        jsonwebtoken::Algorithm::ES256 | jsonwebtoken::Algorithm::ES384 => Some("EC"),
+        jsonwebtoken::Algorithm::EdDSA => Some("OKP"),
+        _ => None,
+    }
+}
+
+pub fn get_jwks_source(
+    lit: &Literal,
+    files: Option<&BotFiles>,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<JwksSource, ErrorInfo> {
+    match lit.primitive.get_type() {
+        PrimitiveType::PrimitiveObject | PrimitiveType::PrimitiveArray => {
+            Ok(JwksSource::Value(lit.primitive.to_json()))
+        }
+        PrimitiveType::PrimitiveString => {
+            let source = lit.primitive.to_string();
+
+            if source.starts_with("https://") || source.starts_with("http://") {
+                return Ok(JwksSource::Url(source));
+            }
+
+            match files.and_then(|files| files.get(&source)) {
+                Some(file) => Ok(JwksSource::Value(file.primitive.to_json())),
+                None => Err(gen_error_info(
+                    Position::new(interval, flow_name),
+                    format!("{} [{}]", ERROR_FROM_FILE_MISSING, source),
+                )),
+            }
+        }
+        _ => Err(gen_error_info(
+            Position::new(interval, flow_name),
+            ERROR_JWT_JWKS.to_owned(),
+        )),
+    }
+}
+
+/**
+ * Algorithms allowed for the token, the algorithm of the header is not trusted
+ * as it is set by the issuer of the token.
+ */
+pub fn get_jwks_algorithms(
+    lit: &Literal,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<Vec<jsonwebtoken::Algorithm>, ErrorInfo> {
+    let algos = match lit.primitive.get_type() {
+        PrimitiveType::PrimitiveString => vec![lit.to_owned()],
+        PrimitiveType::PrimitiveArray => Literal::get_value::<Vec<Literal>>(
+            &lit.primitive,
+            flow_name,
+            interval,
+            ERROR_JWT_JWKS_ALGO.to_owned(),
+        )?
+        .to_owned(),
+        _ => {
+            return Err(gen_error_info(
+                Position::new(interval, flow_name),
+                ERROR_JWT_JWKS_ALGO.to_owned(),
+            ))
+        }
+    };
+
+    let algos = algos
+        .iter()
+        .map(
+            |algo| match jsonwebtoken::Algorithm::from_str(&algo.primitive.to_string()) {
+                Ok(algo) if get_key_type(algo).is_some() => Ok(algo),
+                _ => Err(gen_error_info(
+                    Position::new(interval, flow_name),
+                    ERROR_JWT_JWKS_ALGO.to_owned(),
+                )),
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match algos.is_empty() {
+        true => Err(gen_error_info(
+            Position::new(interval, flow_name),
+            ERROR_JWT_JWKS_ALGO.to_owned(),
+        )),
+        false => Ok(algos),
+    }
+}
+
+pub fn get_jwks(
+    source: &JwksSource,
+    refresh: bool,
+    policies: &[NetworkPolicy],
+    flow_name: &str,
+    interval: Interval,
+) -> Result<serde_json::Value, ErrorInfo> {
+    match source {
+        JwksSource::Value(jwks) => Ok(jwks.to_owned()),
+        JwksSource::Url(url) => fetch_jwks(url, refresh, policies).map_err(|e| {
+            gen_error_info(
+                Position::new(interval, flow_name),
+                format!("{} [{}]: {}", ERROR_JWT_JWKS_FETCH, url, e),
+            )
+        }),
+    }
+}
+
+/**
+ * The key is selected by the 'kid' of the token, a key set without kid can only be used
+ * if a single key matches the algorithm of the token.
+ */
+pub fn get_jwks_decoding_key(
+    jwks: &serde_json::Value,
+    header: &jsonwebtoken::Header,
+) -> Option<jsonwebtoken::DecodingKey> {
+    let key_type = get_key_type(header.alg)?;
+    let algo = jwt_algorithm_to_str(&header.alg);
+
+    let keys = match (jwks.get("keys"), jwks) {
+        (Some(serde_json::Value::Array(keys)), _) => keys.to_owned(),
+        (_, serde_json::Value::Array(keys)) => keys.to_owned(),
+        (_, jwk) => vec![jwk.to_owned()],
+    };
+
+    let mut candidates = keys.iter().filter(|jwk| {
+        jwk["kty"].as_str() == Some(key_type)
+            && jwk["use"].as_str().map_or(true, |key_use| key_use == "sig")
+            && jwk["alg"]
+                .as_str()
+                .map_or(true, |key_algo| key_algo == algo)
+            && match &header.kid {
+                Some(kid) => jwk["kid"].as_str() == Some(kid.as_str()),
+                None => true,
+            }
+    });
+
+    let jwk = match (candidates.next(), candidates.next()) {
+        (Some(jwk), None) => jwk,
+        (Some(jwk), Some(_)) if header.kid.is_some() => jwk,
+        _ => return None,
+    };
+
+    let pem = jwk_to_pem(jwk)?;
+
+    match key_type {
+        "RSA" => jsonwebtoken::DecodingKey::from_rsa_pem(&pem).ok(),
+        "EC" => jsonwebtoken::DecodingKey::from_ec_pem(&pem).ok(),
+        _ => jsonwebtoken::DecodingKey::from_ed_pem(&pem).ok(),
+    }
+}
+
+/**
+ * HS algorithms use the key as a shared secret, the other ones a public key in PEM format.
+ */
+pub fn get_decoding_key(
+    algo: jsonwebtoken::Algorithm,
+    key: &str,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<jsonwebtoken::DecodingKey, ErrorInfo> {
+    let key = match get_key_type(algo) {
+        None => Ok(jsonwebtoken::DecodingKey::from_secret(key.as_ref())),
+        Some("RSA") => jsonwebtoken::DecodingKey::from_rsa_pem(key.as_bytes()),
+        Some("EC") => jsonwebtoken::DecodingKey::from_ec_pem(key.as_bytes()),
+        Some(_) => jsonwebtoken::DecodingKey::from_ed_pem(key.as_bytes()),
+    };
+
+    key.map_err(|_| {
+        gen_error_info(
+            Position::new(interval, flow_name),
+            ERROR_JWT_PUBLIC_KEY.to_owned(),
+        )
+    })
+}
+
+pub fn get_verify_algorithm(
+    lit: &Literal,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<jsonwebtoken::Algorithm, ErrorInfo> {
+    let algo = Literal::get_value::<String>(
+        &lit.primitive,
+        flow_name,
+        interval,
+        ERROR_JWT_VERIFY_ALGO.to_owned(),
+    )?;
+
+    jsonwebtoken::Algorithm::from_str(algo).map_err(|_| {
+        gen_error_info(
+            Position::new(interval, flow_name),
+            ERROR_JWT_VERIFY_ALGO.to_owned(),
+        )
+    })
+}
@@ -0,0 +1,485 @@
+use crate::data::{
+    ast::Interval,
+    error_info::ErrorInfo,
+    position::Position,
+    primitive::{Data, PrimitiveString, PrimitiveType},
+    Literal,
+};
+use crate::error_format::*;
+use std::collections::HashMap;
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+/**
+ * Number and date formatting data of a locale, taken from the CLDR.
+ * Days start on monday.
+ */
+pub struct Locale {
+    pub code: &'static str,
+    pub decimal: &'static str,
+    pub group: &'static str,
+    // currency pattern where "¤" is the symbol and "#" the formatted number
+    pub currency: &'static str,
+    pub months: [&'static str; 12],
+    pub short_months: [&'static str; 12],
+    pub days: [&'static str; 7],
+    pub short_days: [&'static str; 7],
+}
+
+const LOCALES: &[Locale] = &[
+    Locale {
+        code: "en",
+        decimal: ".",
+        group: ",",
+        currency: "¤#",
+        months: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        short_months: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+        days: [
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+            "Sunday",
+        ],
+        short_days: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+    },
+    Locale {
+        code: "fr",
+        decimal: ",",
+        group: "\u{202f}",
+        currency: "#\u{a0}¤",
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        short_months: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        days: [
+            "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+        ],
+        short_days: ["lun.", "mar.", "mer.", "jeu.", "ven.", "sam.", "dim."],
+    },
+    Locale {
+        code: "de",
+        decimal: ",",
+        group: ".",
+        currency: "#\u{a0}¤",
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        short_months: [
+            "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.", "Nov.",
+            "Dez.",
+        ],
+        days: [
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+            "Sonntag",
+        ],
+        short_days: ["Mo.", "Di.", "Mi.", "Do.", "Fr.", "Sa.", "So."],
+    },
+    Locale {
+        code: "es",
+        decimal: ",",
+        group: ".",
+        currency: "#\u{a0}¤",
+        months: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        short_months: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+        ],
+        days: [
+            "lunes",
+            "martes",
+            "miércoles",
+            "jueves",
+            "viernes",
+            "sábado",
+            "domingo",
+        ],
+        short_days: ["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+    },
+    Locale {
+        code: "it",
+        decimal: ",",
+        group: ".",
+        currency: "#\u{a0}¤",
+        months: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        short_months: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        days: [
+            "lunedì",
+            "martedì",
+            "mercoledì",
+            "giovedì",
+            "venerdì",
+            "sabato",
+            "domenica",
+        ],
+        short_days: ["lun", "mar", "mer", "gio", "ven", "sab", "dom"],
+    },
+    Locale {
+        code: "pt",
+        decimal: ",",
+        group: ".",
+        currency: "¤\u{a0}#",
+        months: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        short_months: [
+            "jan.", "fev.", "mar.", "abr.", "mai.", "jun.", "jul.", "ago.", "set.", "out.", "nov.",
+            "dez.",
+        ],
+        days: [
+            "segunda-feira",
+            "terça-feira",
+            "quarta-feira",
+            "quinta-feira",
+            "sexta-feira",
+            "sábado",
+            "domingo",
+        ],
+        short_days: ["seg.", "ter.", "qua.", "qui.", "sex.", "sáb.", "dom."],
+    },
+    Locale {
+        code: "nl",
+        decimal: ",",
+        group: ".",
+        currency: "¤\u{a0}#",
+        months: [
+            "januari",
+            "februari",
+            "maart",
+            "april",
+            "mei",
+            "juni",
+            "juli",
+            "augustus",
+            "september",
+            "oktober",
+            "november",
+            "december",
+        ],
+        short_months: [
+            "jan.", "feb.", "mrt.", "apr.", "mei", "jun.", "jul.", "aug.", "sep.", "okt.", "nov.",
+            "dec.",
+        ],
+        days: [
+            "maandag",
+            "dinsdag",
+            "woensdag",
+            "donderdag",
+            "vrijdag",
+            "zaterdag",
+            "zondag",
+        ],
+        short_days: ["ma", "di", "wo", "do", "vr", "za", "zo"],
+    },
+];
+
+// ISO 4217 code, symbol and number of decimals, other codes are displayed as is with 2 decimals
+const CURRENCIES: &[(&str, &str, u32)] = &[
+    ("USD", "$", 2),
+    ("EUR", "€", 2),
+    ("GBP", "£", 2),
+    ("JPY", "¥", 0),
+    ("CHF", "CHF", 2),
+    ("CAD", "CA$", 2),
+    ("AUD", "A$", 2),
+    ("CNY", "CN¥", 2),
+    ("INR", "₹", 2),
+    ("BRL", "R$", 2),
+    ("MXN", "MX$", 2),
+    ("KRW", "₩", 0),
+];
+
+const DEFAULT_LOCALE: &str = "en";
+
+// decimals asked to format_number, floats have no more significant digits
+pub const MAX_FLOAT_DECIMALS: u32 = 20;
+pub const MAX_DECIMALS: u32 = 100;
+
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+fn get_string_arg(
+    args: &HashMap<String, Literal>,
+    key: &str,
+    data: &Data,
+    interval: Interval,
+    error: &str,
+) -> Result<Option<String>, ErrorInfo> {
+    match args.get(key) {
+        Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveString => {
+            Ok(Some(lit.primitive.to_string()))
+        }
+        Some(_) => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            error.to_owned(),
+        )),
+        None => Ok(None),
+    }
+}
+
+fn get_locale_arg(
+    args: &HashMap<String, Literal>,
+    key: &str,
+    data: &Data,
+    interval: Interval,
+    error: &str,
+) -> Result<&'static Locale, ErrorInfo> {
+    let code = get_string_arg(args, key, data, interval, error)?
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_owned());
+
+    match get_locale(&code) {
+        Some(locale) => Ok(locale),
+        None => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            format!("{} [{}]", ERROR_LOCALE, code),
+        )),
+    }
+}
+
+/**
+ * Localize a plain number like "-1234.50" with the separators of the locale
+ */
+fn localize_number(plain: &str, locale: &Locale) -> String {
+    let (sign, plain) = match plain.strip_prefix('-') {
+        Some(plain) => ("-", plain),
+        None => ("", plain),
+    };
+    let (integer, fraction) = match plain.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (plain, None),
+    };
+
+    let mut grouped = String::new();
+    for (index, c) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            grouped.push_str(locale.group);
+        }
+        grouped.push(c);
+    }
+
+    match fraction {
+        Some(fraction) => format!("{}{}{}{}", sign, grouped, locale.decimal, fraction),
+        None => format!("{}{}", sign, grouped),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PUBLIC FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+/**
+ * Find a locale by its code, "fr-CA" or "fr_CA" fall back to "fr"
+ */
+pub fn get_locale(code: &str) -> Option<&'static Locale> {
+    let code = code.to_lowercase().replace('_', "-");
+    let language = code.split('-').next().unwrap_or_default();
+
+    LOCALES
+        .iter()
+        .find(|locale| locale.code == code)
+        .or_else(|| LOCALES.iter().find(|locale| locale.code == language))
+}
+
+/**
+ * number.format_number(locale = "en", decimals): the number is given as a plain string
+ * by to_plain with the requested number of decimals, up to max_decimals.
+ */
+pub fn format_number(
+    args: &HashMap<String, Literal>,
+    data: &Data,
+    interval: Interval,
+    max_decimals: u32,
+    to_plain: &dyn Fn(Option<u32>) -> String,
+) -> Result<Literal, ErrorInfo> {
+    let locale = get_locale_arg(args, "arg0", data, interval, ERROR_FORMAT_NUMBER)?;
+
+    let decimals = match args.get("arg1") {
+        Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveInt => {
+            match lit.primitive.to_string().parse::<u32>() {
+                Ok(decimals) if decimals <= max_decimals => Some(decimals),
+                _ => {
+                    return Err(gen_error_info(
+                        Position::new(interval, &data.context.flow),
+                        ERROR_FORMAT_NUMBER.to_owned(),
+                    ))
+                }
+            }
+        }
+        Some(_) => {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                ERROR_FORMAT_NUMBER.to_owned(),
+            ))
+        }
+        None => None,
+    };
+
+    Ok(PrimitiveString::get_literal(
+        &localize_number(&to_plain(decimals), locale),
+        interval,
+    ))
+}
+
+/**
+ * number.format_currency(code, locale = "en"), rounded to the decimals of the currency
+ */
+pub fn format_currency(
+    args: &HashMap<String, Literal>,
+    data: &Data,
+    interval: Interval,
+    to_plain: &dyn Fn(Option<u32>) -> String,
+) -> Result<Literal, ErrorInfo> {
+    let code = match get_string_arg(args, "arg0", data, interval, ERROR_FORMAT_CURRENCY)? {
+        Some(code) if code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic()) => {
+            code.to_uppercase()
+        }
+        _ => {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                ERROR_FORMAT_CURRENCY.to_owned(),
+            ))
+        }
+    };
+    let locale = get_locale_arg(args, "arg1", data, interval, ERROR_FORMAT_CURRENCY)?;
+
+    let (symbol, decimals) = match CURRENCIES.iter().find(|(iso, ..)| *iso == code) {
+        Some((_, symbol, decimals)) => (symbol.to_string(), *decimals),
+        None => (code.to_owned(), 2),
+    };
+
+    let number = localize_number(&to_plain(Some(decimals)), locale);
+    let (sign, number) = match number.strip_prefix('-') {
+        Some(number) => ("-", number),
+        None => ("", number.as_str()),
+    };
+
+    let formatted = locale.currency.replace('¤', &symbol).replace('#', number);
+
+    Ok(PrimitiveString::get_literal(
+        &format!("{}{}", sign, formatted),
+        interval,
+    ))
+}
+
+/**
+ * Replace the month and day names of a strftime pattern (%B, %b, %h, %A, %a)
+ * with the names of the locale
+ */
+pub fn localize_date_pattern(
+    pattern: &str,
+    month0: usize,
+    weekday: usize,
+    locale: &Locale,
+) -> String {
+    let mut result = String::new();
+    let mut chars = pattern.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('B') => result.push_str(locale.months[month0]),
+            Some('b') | Some('h') => result.push_str(locale.short_months[month0]),
+            Some('A') => result.push_str(locale.days[weekday]),
+            Some('a') => result.push_str(locale.short_days[weekday]),
+            Some(other) => {
+                result.push('%');
+                result.push(other);
+            }
+            None => result.push('%'),
+        }
+    }
+
+    result
+}
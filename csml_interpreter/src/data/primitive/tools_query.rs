@@ -0,0 +1,59 @@
+use crate::data::primitive::PrimitiveType;
+use crate::data::{ast::Interval, position::Position, Data, Literal};
+use crate::error_format::*;
+use crate::interpreter::json_to_literal;
+
+use std::collections::HashMap;
+
+/**
+ * Evaluate a JMESPath expression over the JSON representation of a literal:
+ * object.query("users[?age > `18`].name")
+ */
+pub fn query(
+    value: &serde_json::Value,
+    args: &HashMap<String, Literal>,
+    data: &Data,
+    interval: Interval,
+) -> Result<Literal, ErrorInfo> {
+    let expression = match args.get("arg0") {
+        Some(res)
+            if args.len() == 1 && res.primitive.get_type() == PrimitiveType::PrimitiveString =>
+        {
+            res.primitive.to_string()
+        }
+        _ => {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                ERROR_QUERY.to_owned(),
+            ));
+        }
+    };
+
+    let expression = match jmespath::compile(&expression) {
+        Ok(expression) => expression,
+        Err(err) => {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("{} {}", ERROR_QUERY_EXPRESSION, err),
+            ));
+        }
+    };
+
+    let result = match expression.search(value) {
+        Ok(result) => result,
+        Err(err) => {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("{} {}", ERROR_QUERY_EXPRESSION, err),
+            ));
+        }
+    };
+
+    match serde_json::to_value(&*result) {
+        Ok(result) => json_to_literal(&result, interval, &data.context.flow),
+        Err(err) => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            format!("{} {}", ERROR_QUERY_EXPRESSION, err),
+        )),
+    }
+}
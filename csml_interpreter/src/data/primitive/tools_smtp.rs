@@ -1,6 +1,6 @@
 use crate::data::{
-    ast::Interval, error_info::ErrorInfo, position::Position, primitive::Data,
-    primitive::PrimitiveType, Literal,
+    ast::Interval, error_info::ErrorInfo, network_policy, position::Position, primitive::Data,
+    primitive::PrimitiveType, Literal, NetworkService,
 };
 use crate::error_format::*;
 use lettre::{
@@ -209,6 +209,41 @@ pub fn format_email(
     }
 }
 
+/**
+ * lettre connects to the server on its own, so its addresses are checked before
+ */
+fn check_network_policy(
+    smtp_server: &str,
+    port: u16,
+    is_tls: bool,
+    data: &Data,
+    interval: Interval,
+) -> Result<(), ErrorInfo> {
+    if !is_tls && !network_policy::allows_disable_ssl_verify(data.network_policies) {
+        return Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            ERROR_NETWORK_POLICY_SMTP_TLS.to_owned(),
+        ));
+    }
+
+    if data.network_policies.is_empty() {
+        return Ok(());
+    }
+
+    match network_policy::resolve(
+        data.network_policies,
+        NetworkService::Smtp,
+        smtp_server,
+        port,
+    ) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            format!("{} [{}]: {}", ERROR_NETWORK_POLICY, smtp_server, err),
+        )),
+    }
+}
+
 pub fn get_mailer(
     object: &mut HashMap<String, Literal>,
     data: &Data,
@@ -254,6 +289,8 @@ pub fn get_mailer(
         Err(_) => true,
     };
 
+    check_network_policy(smtp_server, port, is_tls, data, interval)?;
+
     match is_tls {
         true => {
             let smtp_builder = match starttls {
@@ -3,12 +3,53 @@ use crate::data::{
     error_info::ErrorInfo,
     position::Position,
     primitive::PrimitiveType,
-    primitive::{Data, PrimitiveInt, PrimitiveNull, PrimitiveObject},
+    primitive::{tools_locale, Data, PrimitiveArray, PrimitiveInt, PrimitiveObject},
     Literal,
 };
 use crate::error_format::*;
-use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    SecondsFormat, TimeZone, Timelike, Utc,
+};
+use chrono_tz::Tz;
 use std::collections::HashMap;
+use std::convert::TryFrom;
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+// names of the days in a business hours schedule, starting on monday
+const WEEKDAYS: [[&str; 2]; 7] = [
+    ["monday", "mon"],
+    ["tuesday", "tue"],
+    ["wednesday", "wed"],
+    ["thursday", "thu"],
+    ["friday", "fri"],
+    ["saturday", "sat"],
+    ["sunday", "sun"],
+];
+
+/**
+ * ISO 8601 duration, months and days are applied on the calendar
+ * and the rest as a fixed amount of time.
+ */
+#[derive(Debug, PartialEq)]
+pub struct IsoDuration {
+    pub months: i64,
+    pub days: i64,
+    pub milliseconds: i64,
+}
+
+impl IsoDuration {
+    pub fn negate(self) -> Self {
+        Self {
+            months: -self.months,
+            days: -self.days,
+            milliseconds: -self.milliseconds,
+        }
+    }
+}
 
 ////////////////////////////////////////////////////////////////////////////////
 // PRIVATE FUNCTIONS
@@ -41,6 +82,94 @@ fn get_date_string(
     }
 }
 
+fn get_timezone(
+    object: &HashMap<String, Literal>,
+    data: &Data,
+    interval: Interval,
+) -> Result<Option<Tz>, ErrorInfo> {
+    match object.get("timezone") {
+        Some(timezone) => {
+            let tz_name = timezone.primitive.to_string();
+
+            match tz_name.parse::<Tz>() {
+                Ok(tz) => Ok(Some(tz)),
+                Err(_) => Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    format!("{} {}", ERROR_TIME_TIMEZONE, tz_name),
+                )),
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+fn get_offset(object: &HashMap<String, Literal>) -> FixedOffset {
+    match object.get("offset") {
+        Some(offset) => match offset.primitive.to_string().parse::<i32>() {
+            Ok(offset) => FixedOffset::east(offset),
+            Err(_) => FixedOffset::east(0),
+        },
+        None => FixedOffset::east(0),
+    }
+}
+
+fn checked_add_units(total: i64, value: f64, unit: i64) -> Option<i64> {
+    // values too large for an i64 are saturated by the cast, then rejected
+    if value >= i64::MAX as f64 {
+        return None;
+    }
+
+    total.checked_add((value as i64).checked_mul(unit)?)
+}
+
+/**
+ * None when the month is out of the range of the calendar
+ */
+fn get_month_start(year: i32, month0: i64) -> Option<NaiveDate> {
+    let total = (year as i64).checked_mul(12)?.checked_add(month0)?;
+    let year = i32::try_from(total.div_euclid(12)).ok()?;
+
+    NaiveDate::from_ymd_opt(year, total.rem_euclid(12) as u32 + 1, 1)
+}
+
+fn add_months(date: NaiveDate, months: i64) -> Option<NaiveDate> {
+    let month0 = (date.month0() as i64).checked_add(months)?;
+    let month_start = get_month_start(date.year(), month0)?;
+    let next_month_start = get_month_start(date.year(), month0.checked_add(1)?)?;
+    let days_in_month = (next_month_start - month_start).num_days() as u32;
+
+    month_start.with_day(date.day().min(days_in_month))
+}
+
+fn get_time_of_day(value: &Literal) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(&value.primitive.to_string(), "%H:%M").ok()
+}
+
+/**
+ * A day of a schedule is either one range ["09:00", "18:00"]
+ * or a list of ranges [["09:00", "12:00"], ["14:00", "18:00"]].
+ */
+fn get_day_ranges(day: &Literal) -> Option<Vec<(NaiveTime, NaiveTime)>> {
+    let array = day.primitive.as_any().downcast_ref::<PrimitiveArray>()?;
+
+    let ranges: Vec<&Literal> = match array.value.first() {
+        Some(first) if first.primitive.get_type() == PrimitiveType::PrimitiveString => vec![day],
+        _ => array.value.iter().collect(),
+    };
+
+    ranges
+        .into_iter()
+        .map(|range| {
+            let range = range.primitive.as_any().downcast_ref::<PrimitiveArray>()?;
+
+            match range.value.as_slice() {
+                [start, end] => Some((get_time_of_day(start)?, get_time_of_day(end)?)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // PUBLIC FUNCTIONS
 ////////////////////////////////////////////////////////////////////////////////
@@ -178,22 +307,377 @@ where
     Tz: TimeZone,
     Tz::Offset: core::fmt::Display,
 {
-    match args.len() {
-        0 => Ok(date.to_rfc3339_opts(SecondsFormat::Millis, use_z)),
-        _ => {
-            let format_lit = match args.get("arg0") {
-                Some(res) => res.to_owned(),
-                _ => PrimitiveNull::get_literal(Interval::default()),
+    let format_lit = match args.get("arg0") {
+        Some(res) => res.to_owned(),
+        None => return Ok(date.to_rfc3339_opts(SecondsFormat::Millis, use_z)),
+    };
+
+    let format = Literal::get_value::<String>(
+        &format_lit.primitive,
+        &data.context.flow,
+        interval,
+        "format parameter must be of type string".to_string(),
+    )?;
+
+    // month and day names are localized with Time().format("%A %d %B", locale = "fr")
+    match args.get("arg1") {
+        Some(locale) => {
+            let code = locale.primitive.to_string();
+            let locale = match tools_locale::get_locale(&code) {
+                Some(locale) => locale,
+                None => {
+                    return Err(gen_error_info(
+                        Position::new(interval, &data.context.flow),
+                        format!("{} [{}]", ERROR_LOCALE, code),
+                    ))
+                }
             };
 
-            let format = Literal::get_value::<String>(
-                &format_lit.primitive,
+            let format = tools_locale::localize_date_pattern(
+                format,
+                date.month0() as usize,
+                date.weekday().num_days_from_monday() as usize,
+                locale,
+            );
+
+            Ok(date.format(&format).to_string())
+        }
+        None => Ok(date.format(format).to_string()),
+    }
+}
+
+pub fn get_milliseconds(
+    object: &HashMap<String, Literal>,
+    data: &Data,
+    interval: Interval,
+    error: &str,
+) -> Result<i64, ErrorInfo> {
+    match object.get("milliseconds") {
+        Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveInt => {
+            let millis = Literal::get_value::<i64>(
+                &lit.primitive,
                 &data.context.flow,
                 interval,
-                "format parameter must be of type string".to_string(),
+                error.to_owned(),
             )?;
 
-            Ok(date.format(format).to_string())
+            Ok(*millis)
+        }
+        _ => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            error.to_owned(),
+        )),
+    }
+}
+
+/**
+ * Milliseconds of a Time object given as argument: other.is_before(Time())
+ */
+pub fn get_time_arg(
+    args: &HashMap<String, Literal>,
+    index: usize,
+    data: &Data,
+    interval: Interval,
+    error: &str,
+) -> Result<i64, ErrorInfo> {
+    match args.get(&format!("arg{}", index)) {
+        Some(lit) if lit.content_type == "time" => {
+            let object = Literal::get_value::<HashMap<String, Literal>>(
+                &lit.primitive,
+                &data.context.flow,
+                interval,
+                error.to_owned(),
+            )?;
+
+            get_milliseconds(object, data, interval, error)
+        }
+        _ => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            error.to_owned(),
+        )),
+    }
+}
+
+/**
+ * Date of a Time object in its own timezone or offset, UTC by default
+ */
+pub fn get_local_date(
+    object: &HashMap<String, Literal>,
+    data: &Data,
+    interval: Interval,
+    error: &str,
+) -> Result<DateTime<FixedOffset>, ErrorInfo> {
+    let millis = get_milliseconds(object, data, interval, error)?;
+
+    match get_timezone(object, data, interval)? {
+        Some(tz) => {
+            let date = tz.timestamp_millis(millis);
+
+            Ok(date.with_timezone(&date.offset().fix()))
         }
+        None => Ok(get_offset(object).timestamp_millis(millis)),
     }
 }
+
+/**
+ * Convert a local date and time of the Time object timezone to milliseconds,
+ * a local time skipped by a daylight saving change is moved one hour later.
+ */
+pub fn local_to_milliseconds(
+    object: &HashMap<String, Literal>,
+    naive: NaiveDateTime,
+    data: &Data,
+    interval: Interval,
+) -> Result<i64, ErrorInfo> {
+    let date = match get_timezone(object, data, interval)? {
+        Some(tz) => tz
+            .from_local_datetime(&naive)
+            .earliest()
+            .or_else(|| {
+                tz.from_local_datetime(&(naive + Duration::hours(1)))
+                    .earliest()
+            })
+            .map(|date| date.timestamp_millis()),
+        None => get_offset(object)
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|date| date.timestamp_millis()),
+    };
+
+    match date {
+        Some(date) => Ok(date),
+        None => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            ERROR_TIME_INVALID_DATE.to_owned(),
+        )),
+    }
+}
+
+/**
+ * New Time object keeping the timezone and offset of the original one
+ */
+pub fn gen_time_literal(
+    object: &HashMap<String, Literal>,
+    millis: i64,
+    interval: Interval,
+) -> Literal {
+    let mut object = object.to_owned();
+    object.insert(
+        "milliseconds".to_owned(),
+        PrimitiveInt::get_literal(millis, interval),
+    );
+
+    let mut lit = PrimitiveObject::get_literal(&object, interval);
+    lit.set_content_type("time");
+
+    lit
+}
+
+/**
+ * Parse an ISO 8601 duration like "P1Y2M10DT2H30M" or "PT1.5S", a leading "-" negates it
+ */
+pub fn parse_iso_duration(duration: &str) -> Option<IsoDuration> {
+    let (sign, duration) = match duration.strip_prefix('-') {
+        Some(duration) => (-1, duration),
+        None => (1, duration),
+    };
+    let duration = duration.strip_prefix('P')?;
+
+    let mut months = 0;
+    let mut days = 0;
+    let mut milliseconds = 0.0;
+    let mut in_time = false;
+    let mut is_empty = true;
+    let mut number = String::new();
+
+    for c in duration.chars() {
+        match c {
+            'T' if !in_time && number.is_empty() => in_time = true,
+            '0'..='9' | '.' => number.push(c),
+            ',' => number.push('.'),
+            designator => {
+                let value = number.parse::<f64>().ok()?;
+                number.clear();
+                is_empty = false;
+
+                // only the time components can be fractional
+                if !in_time && value.fract() != 0.0 {
+                    return None;
+                }
+
+                match (in_time, designator) {
+                    (false, 'Y') => months = checked_add_units(months, value, 12)?,
+                    (false, 'M') => months = checked_add_units(months, value, 1)?,
+                    (false, 'W') => days = checked_add_units(days, value, 7)?,
+                    (false, 'D') => days = checked_add_units(days, value, 1)?,
+                    (true, 'H') => milliseconds += value * 3_600_000.0,
+                    (true, 'M') => milliseconds += value * 60_000.0,
+                    (true, 'S') => milliseconds += value * 1_000.0,
+                    _ => return None,
+                }
+            }
+        }
+    }
+
+    if is_empty || !number.is_empty() || duration.ends_with('T') {
+        return None;
+    }
+
+    // beyond this amount of milliseconds the duration does not fit in an Int
+    if milliseconds.round() >= i64::MAX as f64 {
+        return None;
+    }
+
+    let duration = IsoDuration {
+        months,
+        days,
+        milliseconds: milliseconds.round() as i64,
+    };
+
+    match sign {
+        -1 => Some(duration.negate()),
+        _ => Some(duration),
+    }
+}
+
+/**
+ * Add a duration to a Time object, months and days keep the local time of the day.
+ * A result out of the range of the calendar is an error.
+ */
+pub fn add_iso_duration(
+    object: &HashMap<String, Literal>,
+    duration: &IsoDuration,
+    data: &Data,
+    interval: Interval,
+    error: &str,
+) -> Result<i64, ErrorInfo> {
+    let out_of_range = || {
+        gen_error_info(
+            Position::new(interval, &data.context.flow),
+            ERROR_TIME_DURATION_RANGE.to_owned(),
+        )
+    };
+
+    let mut millis = get_milliseconds(object, data, interval, error)?;
+
+    if duration.months != 0 || duration.days != 0 {
+        let local = get_local_date(object, data, interval, error)?.naive_local();
+        let days = duration
+            .days
+            .checked_mul(86_400_000)
+            .map(Duration::milliseconds)
+            .ok_or_else(out_of_range)?;
+        let date = add_months(local.date(), duration.months)
+            .and_then(|date| date.checked_add_signed(days))
+            .ok_or_else(out_of_range)?;
+
+        millis = local_to_milliseconds(object, date.and_time(local.time()), data, interval)?;
+    }
+
+    millis
+        .checked_add(duration.milliseconds)
+        .ok_or_else(out_of_range)
+}
+
+/**
+ * Difference between two dates in the given unit, truncated toward zero.
+ * Months and years are counted on the calendar of the first date.
+ */
+pub fn get_diff(lhs: DateTime<FixedOffset>, rhs_millis: i64, unit: &str) -> Option<i64> {
+    let diff = lhs.timestamp_millis() - rhs_millis;
+
+    let divisor = match unit {
+        "millisecond" | "milliseconds" | "ms" => 1,
+        "second" | "seconds" | "s" => 1_000,
+        "minute" | "minutes" => 60_000,
+        "hour" | "hours" => 3_600_000,
+        "day" | "days" => 86_400_000,
+        "week" | "weeks" => 604_800_000,
+        "month" | "months" | "year" | "years" => {
+            let rhs = lhs.offset().timestamp_millis(rhs_millis);
+
+            let mut months =
+                (lhs.year() - rhs.year()) as i64 * 12 + lhs.month() as i64 - rhs.month() as i64;
+
+            // the last month is only counted once complete
+            let lhs_rest = (lhs.day(), lhs.num_seconds_from_midnight(), lhs.nanosecond());
+            let rhs_rest = (rhs.day(), rhs.num_seconds_from_midnight(), rhs.nanosecond());
+            if months > 0 && lhs_rest < rhs_rest {
+                months -= 1;
+            } else if months < 0 && lhs_rest > rhs_rest {
+                months += 1;
+            }
+
+            return match unit {
+                "month" | "months" => Some(months),
+                _ => Some(months / 12),
+            };
+        }
+        _ => return None,
+    };
+
+    Some(diff / divisor)
+}
+
+/**
+ * Local start of the day, week (starting on monday), month or year of a date
+ */
+pub fn get_start_of(date: DateTime<FixedOffset>, unit: &str) -> Option<NaiveDateTime> {
+    let date = date.naive_local().date();
+
+    let start = match unit {
+        "day" => date,
+        "week" => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+        "month" => NaiveDate::from_ymd(date.year(), date.month(), 1),
+        "year" => NaiveDate::from_ymd(date.year(), 1, 1),
+        _ => return None,
+    };
+
+    Some(start.and_hms(0, 0, 0))
+}
+
+pub fn get_next_start_of(start: NaiveDateTime, unit: &str) -> Option<NaiveDateTime> {
+    let date = start.date();
+
+    let next = match unit {
+        "day" => date + Duration::days(1),
+        "week" => date + Duration::days(7),
+        "month" => add_months(date, 1)?,
+        "year" => add_months(date, 12)?,
+        _ => return None,
+    };
+
+    Some(next.and_hms(0, 0, 0))
+}
+
+/**
+ * Check if a date is within the opening hours of the weekly schedule and not a holiday.
+ * Holidays are dates formatted as "%Y-%m-%d" in the timezone of the date.
+ */
+pub fn is_business_hours(
+    date: DateTime<FixedOffset>,
+    schedule: &HashMap<String, Literal>,
+    holidays: &[Literal],
+) -> Option<bool> {
+    let day = date.format("%Y-%m-%d").to_string();
+    if holidays
+        .iter()
+        .any(|holiday| holiday.primitive.to_string() == day)
+    {
+        return Some(false);
+    }
+
+    let names = WEEKDAYS[date.weekday().num_days_from_monday() as usize];
+    let ranges = match names.iter().find_map(|name| schedule.get(*name)) {
+        Some(day) => get_day_ranges(day)?,
+        None => return Some(false),
+    };
+
+    let time = date.time();
+
+    Some(
+        ranges
+            .iter()
+            .any(|(start, end)| *start <= time && time < *end),
+    )
+}
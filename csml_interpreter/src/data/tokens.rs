@@ -88,10 +88,18 @@ pub const FORGET: &str = "forget";
 pub const _METADATA: &str = "_metadata";
 pub const _MEMORY: &str = "_memory";
 pub const _ENV: &str = "_env";
+pub const KNOWLEDGE: &str = "Knowledge";
 pub const BREAK: &str = "break";
 pub const CONTINUE: &str = "continue";
 pub const RETURN: &str = "return";
 
+pub const FORM: &str = "form";
+pub const FORM_ASK: &str = "ask";
+pub const FORM_VALIDATE: &str = "validate";
+pub const FORM_RETRIES: &str = "retries";
+pub const FORM_ERROR: &str = "error";
+pub const FORM_SKIP: &str = "skip";
+
 pub const FN_SCOPE_REJECTED: &[&str] =
     &[SAY, GOTO, REMEMBER, FORGET, USE, HOLD, HOLD_SECURE, BREAK];
 
@@ -105,8 +113,11 @@ pub const TEXT_TYPE: &str = "text";
 pub const STRING: &str = "string";
 pub const INT: &str = "int";
 pub const FLOAT: &str = "float";
+pub const DECIMAL: &str = "decimal";
 pub const BOOLEAN: &str = "boolean";
 pub const CLOSURE: &str = "closure";
+pub const EMAIL: &str = "email";
+pub const DATE: &str = "date";
 
 pub const TYPES: &[&str] = &[
     CLOSURE,
@@ -116,24 +127,28 @@ pub const TYPES: &[&str] = &[
     STRING,
     INT,
     FLOAT,
+    DECIMAL,
     BOOLEAN,
     NULL,
 ];
 
+// types of answers that can be validated by a form field
+pub const FORM_TYPES: &[&str] = &[TEXT_TYPE, INT, FLOAT, EMAIL, DATE];
+
 pub const RESERVED: &[&str] = &[
     FOREACH, WHILE, IF, ELSE, IMPORT, CONST, INSERT, AS, IN, DO, FROM, EVENT, FLOW, FILE, STEP,
     SAY, USE, HOLD, GOTO, MATCH, _METADATA, _MEMORY, _ENV, DEFAULT, REMEMBER, FORGET, TRUE, FALSE,
-    NULL, BREAK, COMPONENT,
+    NULL, BREAK, COMPONENT, FORM,
 ];
 
 pub const UTILISATION_RESERVED: &[&str] = &[
     FOREACH, WHILE, IF, ELSE, IMPORT, CONST, INSERT, AS, DO, FLOW, STEP, SAY, USE, HOLD, GOTO,
-    MATCH, REMEMBER, FORGET, BREAK, COMPONENT,
+    MATCH, REMEMBER, FORGET, BREAK, COMPONENT, FORM,
 ];
 
 pub const ASSIGNATION_RESERVED: &[&str] = &[
     FOREACH, WHILE, IF, ELSE, IMPORT, AS, DO, EVENT, FLOW, STEP, SAY, USE, HOLD, GOTO, MATCH,
-    REMEMBER, FORGET, _METADATA, _MEMORY, _ENV, TRUE, FALSE, NULL, BREAK, COMPONENT,
+    REMEMBER, FORGET, _METADATA, _MEMORY, _ENV, TRUE, FALSE, NULL, BREAK, COMPONENT, FORM,
 ];
 
 pub const TYPING: &str = "Typing";
@@ -158,6 +173,7 @@ pub const FN: &str = "Fn";
 pub const APP: &str = "App";
 pub const HTTP: &str = "HTTP";
 pub const SMTP: &str = "SMTP";
+pub const LLM: &str = "LLM";
 pub const JWT: &str = "JWT";
 pub const CRYPTO: &str = "Crypto";
 pub const BASE64: &str = "Base64";
@@ -167,12 +183,39 @@ pub const DEBUG: &str = "Debug";
 pub const UUID: &str = "UUID";
 pub const TIME: &str = "Time";
 pub const EXISTS: &str = "Exists";
+pub const EXTRACT: &str = "Extract";
+pub const TRANSLATE: &str = "T";
+pub const TEMPLATE: &str = "Template";
+pub const DECIMAL_BUILT_IN: &str = "Decimal";
 
 pub const OBJECT: &str = "Object";
 
 pub const BUILT_IN: &[&str] = &[
-    ONE_OF, SHUFFLE, LENGTH, FIND, RANDOM, FLOOR, FN, APP, HTTP, OBJECT, DEBUG, UUID, BASE64, HEX,
-    JWT, CRYPTO, TIME, SMTP, EXISTS,
+    ONE_OF,
+    SHUFFLE,
+    LENGTH,
+    FIND,
+    RANDOM,
+    FLOOR,
+    FN,
+    APP,
+    HTTP,
+    OBJECT,
+    DEBUG,
+    UUID,
+    BASE64,
+    HEX,
+    JWT,
+    CRYPTO,
+    TIME,
+    SMTP,
+    LLM,
+    EXISTS,
+    EXTRACT,
+    TRANSLATE,
+    TEMPLATE,
+    FROM_FILE,
+    DECIMAL_BUILT_IN,
 ];
 
 pub const OR_BUILT_IN: &str = "Or";
@@ -46,6 +46,15 @@ pub const ERROR_WRONG_ARGUMENT_EXPANDABLE_STRING: &str =
     "wrong argument(s) given to expandable string";
 pub const ERROR_FN_SCOPE: &str =
     "invalid action. Use a valid action for this type of scope [do, if, return, ...]"; //\ndoc: https://docs.csml.dev/language/native-csml-functions
+pub const ERROR_FORM_FIELD: &str =
+    "form field expects a name and a block of options. Example: email: email { ask \"What is your email?\" }";
+pub const ERROR_FORM_TYPE: &str = "form field type must be one of [text, int, float, email, date]";
+pub const ERROR_FORM_OPTION: &str =
+    "form field option must be one of [ask, validate, retries, error, skip if]";
+pub const ERROR_FORM_ASK: &str =
+    "form field expects a component to ask. Example: ask \"What is your name?\"";
+pub const ERROR_FORM_RETRIES: &str =
+    "form field retries must be a positive Int. Example: retries 3";
 
 // Linter Errors
 pub const ERROR_NO_FLOW: &str = "bot must have at least one flow";
@@ -155,8 +164,35 @@ pub const ERROR_JWT: &str = "JWT builtin expects payload as argument. Example: J
       })";
 pub const ERROR_SMTP: &str =
     "SMTP builtin expects SMTP Server Address. Example: SMTP(\"smtp.gmail.com\")";
+pub const ERROR_LLM: &str =
+    "LLM builtin expects a prompt of type String or an array of messages. Example: LLM(\"Hello\", system = \"You are a helpful assistant\")";
+pub const ERROR_LLM_MESSAGES: &str =
+    "LLM messages must be objects with a role and a content of type String. Example: [{\"role\": \"user\", \"content\": \"Hello\"}]";
+pub const ERROR_LLM_RESPONSE: &str = "LLM provider response does not contain any completion";
+pub const ERROR_LLM_JSON: &str = "LLM completion is not a valid JSON in json mode";
+pub const ERROR_DECIMAL: &str =
+    "Decimal builtin expects a number or a numeric String. Example: Decimal(\"19.99\", scale = 2, rounding = \"half_up\")";
 pub const ERROR_CRYPTO: &str =
     "CRYPTO builtin expects one argument of type string. Example: CRYPTO(\"text\")";
+pub const ERROR_EXTRACT: &str =
+    "Extract builtin expects a text of type String and an optional timezone. Example: Extract(event, \"Europe/Paris\")";
+pub const ERROR_KNOWLEDGE_MISSING: &str = "no knowledge base attached to the bot";
+pub const ERROR_FROM_FILE: &str =
+    "FromFile builtin expects a file name of type String. Example: FromFile(\"products.json\")";
+pub const ERROR_FROM_FILE_MISSING: &str = "file not found in the bot files";
+pub const ERROR_TRANSLATE: &str =
+    "T builtin expects a translation key of type String. Example: T(\"greetings.hello\", name = \"Jane\")";
+pub const ERROR_TRANSLATE_COUNT: &str = "T count argument must be of type Int or Float";
+pub const ERROR_TRANSLATE_VALUE: &str =
+    "translation must be a String or an Object of plural forms (zero, one, two, few, many, other)";
+pub const ERROR_TEMPLATE: &str =
+    "Template builtin expects a template of type String and an optional data object. Example: Template(\"Hello {{name}}\", {\"name\": \"Jane\"})";
+pub const ERROR_TEMPLATE_FILE: &str =
+    "Template file must be a template of the bot files (.mustache, .hbs, .html or .txt)";
+pub const ERROR_TEMPLATE_PARTIALS: &str =
+    "Template partials must be an object of templates of type String. Example: partials = {\"footer\": \"Thanks!\"}";
+pub const ERROR_TEMPLATE_ESCAPE: &str = "Template escape must be \"html\" or \"none\"";
+pub const ERROR_TEMPLATE_SYNTAX: &str = "invalid template";
 pub const ERROR_BUILTIN_UNKNOWN: &str = "Unknown builtin";
 
 // ### native Components
@@ -183,10 +219,21 @@ pub const ERROR_BOOLEAN_UNKNOWN_METHOD: &str = "is not a method of Boolean";
 // #### NUMBER
 pub const ERROR_NUMBER_POW: &str =
     "[pow] takes one parameter of type int or float usage: number.pow(42)";
+pub const ERROR_FORMAT_NUMBER: &str = "[format_number] takes an optional locale of type String and an optional number of decimals of type Int, up to 20 for a Float and 100 otherwise. Usage: number.format_number(\"fr\", 2)";
+pub const ERROR_FORMAT_CURRENCY: &str = "[format_currency] takes a currency code of type String and an optional locale of type String. Usage: number.format_currency(\"EUR\", \"fr\")";
+pub const ERROR_LOCALE: &str =
+    "unknown locale, supported locales are en, fr, de, es, it, pt and nl";
 
 // #### Float
 pub const ERROR_FLOAT_UNKNOWN_METHOD: &str = "is not a method of Float";
 
+// #### Decimal
+pub const ERROR_DECIMAL_UNKNOWN_METHOD: &str = "is not a method of Decimal";
+pub const ERROR_DECIMAL_ROUNDING: &str =
+    "rounding must be one of up, down, ceiling, floor, half_up, half_down or half_even";
+pub const ERROR_DECIMAL_EXPONENT: &str = "decimal exponent must be between -1000 and 1000";
+pub const ERROR_DECIMAL_SCALE: &str = "decimal scale must be an int between 0 and 100";
+
 // #### Int
 pub const ERROR_INT_UNKNOWN_METHOD: &str = "is not a method of Int";
 
@@ -205,7 +252,9 @@ pub const ERROR_STRING_REPLACE: &str =
 pub const ERROR_STRING_REPLACE_ALL: &str =
     "[replace_all] takes tow parameter of type String. Usage: \"old old old old\".replace_all(\"old\", \"new\")";
 pub const ERROR_STRING_REPLACE_REGEX: &str =
-    "[replace_regex] takes tow parameter of type String. Usage: \"hello world\".replace_regex(\"world\", \"Clevy\")";
+    "[replace_regex] takes a regex of type String and a replacement of type String or Closure. Usage: \"hello world\".replace_regex(\"(?P<word>world)\", \"Clevy $word\")";
+pub const ERROR_STRING_CAPTURES: &str =
+    "[captures] takes one parameter of type String. Usage: \"2024-03-15\".captures(\"(?P<year>\\\\d{4})\")";
 pub const ERROR_STRING_CONTAINS_REGEX: &str =
     "[contains_regex] takes one parameter of type String. Usage: string.contains_regex(\"regex\")";
 pub const ERROR_STRING_VALID_REGEX: &str = "parameter must be a valid regex expression"; // link to docs
@@ -217,6 +266,10 @@ pub const ERROR_STRING_END_WITH: &str =
 pub const ERROR_STRING_END_WITH_REGEX: &str =
     "[ends_with_regex] takes one parameter of type String. Usage: string.ends_with_regex(\"regex\")";
 pub const ERROR_STRING_FROM_JSON: &str = "[from_json] [!] string to object failed]";
+pub const ERROR_STRING_FROM_XML: &str = "[from_xml] string is not a valid XML document";
+pub const ERROR_STRING_FROM_YAML: &str = "[from_yaml] string is not a valid YAML document";
+pub const ERROR_CSV_ROW_LENGTH: &str = "a row has more fields than the header";
+pub const ERROR_STRING_FROM_CSV: &str = "[from_csv] takes an optional parameter of type Object with a one character delimiter. Usage: string.from_csv({\"delimiter\": \";\", \"header\": true})";
 pub const ERROR_STRING_SPLIT: &str =
     "[split] takes one parameter of type String. Usage: string.split(\"separator\")";
 pub const ERROR_STRING_MATCH_REGEX: &str =
@@ -255,8 +308,36 @@ pub const ERROR_ARRAY_JOIN: &str =
 pub const ERROR_ARRAY_INDEX_OF: &str =
     "[index_of] takes one parameter. Usage: array.index_of(elem)";
 pub const ERROR_ARRAY_FIND: &str = "[find] takes one parameter. Usage: array.find(elem)";
+pub const ERROR_ARRAY_ZIP: &str =
+    "[zip] takes one parameter of type Array. Usage: array.zip([1, 2])";
+pub const ERROR_ARRAY_CHUNK: &str =
+    "[chunk] takes one parameter of type Int greater than 0. Usage: array.chunk(2)";
+pub const ERROR_ARRAY_SUM: &str = "[sum] array must only contain numbers. Usage: [1, 2.5].sum()";
+pub const ERROR_ARRAY_INTERSECTION: &str =
+    "[intersection] takes one parameter of type Array. Usage: array.intersection([1, 2])";
+pub const ERROR_ARRAY_DIFFERENCE: &str =
+    "[difference] takes one parameter of type Array. Usage: array.difference([1, 2])";
+pub const ERROR_ARRAY_FROM_ENTRIES: &str =
+    "[from_entries] array must only contain [key, value] pairs with a key of type String. Usage: [[\"key\", 42]].from_entries()";
+pub const ERROR_ARRAY_TO_CSV: &str = "[to_csv] array must only contain objects and takes an optional parameter of type Object. Usage: array.to_csv({\"delimiter\": \";\", \"columns\": [\"name\"]})";
 pub const ERROR_ARRAY_UNKNOWN_METHOD: &str = "is not a method of Array";
 
+// #### TIME OBJECT
+pub const ERROR_TIME_DIFF: &str = "[diff] takes a Time and an optional unit (milliseconds, seconds, minutes, hours, days, weeks, months, years). Usage: Time().diff(other, \"days\")";
+pub const ERROR_TIME_START_OF: &str =
+    "[start_of] takes one unit of type String (day, week, month, year). Usage: Time().start_of(\"day\")";
+pub const ERROR_TIME_END_OF: &str =
+    "[end_of] takes one unit of type String (day, week, month, year). Usage: Time().end_of(\"month\")";
+pub const ERROR_TIME_COMPARE: &str =
+    "[is_before/is_after] take one argument of type Time. Usage: Time().is_before(other)";
+pub const ERROR_TIME_DURATION: &str =
+    "duration must be a valid ISO 8601 duration. Usage: Time().add(\"P1DT2H30M\")";
+pub const ERROR_TIME_DURATION_RANGE: &str =
+    "the duration moves the Time object out of the range of supported dates";
+pub const ERROR_TIME_TIMEZONE: &str = "invalid timezone";
+pub const ERROR_TIME_INVALID_DATE: &str = "date does not exist in the timezone of the Time object";
+pub const ERROR_TIME_BUSINESS_HOURS: &str = "[is_business_hours] takes a weekly schedule of type Object and an optional Array of holidays. Usage: Time().is_business_hours({\"monday\": [\"09:00\", \"18:00\"]}, [\"2024-12-25\"])";
+
 // #### CRYPTO OBJECT
 // ## HMAC and HASH OBJECT
 pub const ERROR_HASH: &str = "Crypto(string) command expect argument of type String";
@@ -268,6 +349,20 @@ pub const ERROR_DIGEST: &str = "Invalid argument, '.digest' is use incorrectly";
 pub const ERROR_DIGEST_ALGO: &str =
     "Invalid Digest Algorithm, supported Algorithms are hex, base64";
 
+// ## ENCRYPTION, SIGNATURE and PASSWORD
+pub const ERROR_CRYPTO_KEY: &str = "AES key must be a base64 String of 16, 24 or 32 bytes";
+pub const ERROR_CRYPTO_DECRYPT: &str =
+    "decryption failed, the key is wrong or the encrypted value has been modified";
+pub const ERROR_CRYPTO_PRIVATE_KEY: &str = "private key must be a RSA or EC key in PEM format";
+pub const ERROR_CRYPTO_PUBLIC_KEY: &str =
+    "public key must be a RSA or EC public key or a certificate in PEM format";
+pub const ERROR_CRYPTO_RANDOM: &str =
+    "[random_bytes] takes a size between 1 and 1024 and an optional encoding (hex, base64). Usage: Crypto().random_bytes(32, \"hex\")";
+pub const ERROR_CRYPTO_PASSWORD_ALGO: &str =
+    "Invalid password hashing Algorithm, supported Algorithms are scrypt, pbkdf2";
+pub const ERROR_CRYPTO_PASSWORD_HASH: &str =
+    "password hash must be a scrypt or pbkdf2-sha256 hash in the PHC format, with a supported cost";
+
 // #### JWT OBJECT
 pub const ERROR_JWT_ALGO: &str = "Invalid Algorithm, supported Algorithms are HS256, HS384, HS512";
 pub const ERROR_JWT_SECRET: &str = "secret must be of type String";
@@ -291,6 +386,20 @@ pub const ERROR_JWT_VALIDATION_ALGO: &str =
     "JWT(jwt).verify(claims, algo, secret) expect second argument 'algo' of type String";
 pub const ERROR_JWT_VALIDATION_SECRETE: &str =
     "JWT(jwt).verify(claims, algo, secret) expect third argument 'secrete' of type String";
+pub const ERROR_JWT_VERIFY_ALGO: &str =
+    "Invalid Algorithm, supported Algorithms are HS256, HS384, HS512, RS256, RS384, RS512, PS256, PS384, PS512, ES256, ES384, EdDSA";
+pub const ERROR_JWT_PUBLIC_KEY: &str =
+    "JWT public key must be in PEM format for RS, PS, ES and EdDSA algorithms";
+
+pub const ERROR_JWT_JWKS: &str =
+    "JWT(jwt).verify_jwks(jwks, algos, Optional<claims>) expect first argument 'jwks' of type Object, or a String with the name of a bot file or an URL";
+pub const ERROR_JWT_JWKS_ALGO: &str =
+    "JWT(jwt).verify_jwks(jwks, algos, Optional<claims>) expect second argument 'algos' of type String or Array of the allowed Algorithms RS256, RS384, RS512, PS256, PS384, PS512, ES256, ES384, EdDSA";
+pub const ERROR_JWT_JWKS_TOKEN_ALGO: &str = "the algorithm of the token is not allowed";
+pub const ERROR_JWT_JWKS_FETCH: &str = "failed to fetch JWKS";
+pub const ERROR_JWT_JWKS_HEADER: &str = "Invalid JWT header, the token can not be decoded";
+pub const ERROR_JWT_JWKS_KEY: &str =
+    "no key of the JWKS matches the 'kid' and the algorithm of the token";
 
 // #### HTTP OBJECT
 pub const ERROR_HTTP_SET: &str =
@@ -300,6 +409,31 @@ pub const ERROR_HTTP_QUERY: &str =
 
 pub const ERROR_HTTP_SEND: &str = "[send] HTTP Object is bad formatted read doc for correct usage";
 pub const ERROR_HTTP_UNKNOWN_METHOD: &str = "is not a method of HTTP";
+pub const ERROR_HTTP_OAUTH2: &str = "[oauth2] takes a token url, a client id, a client secret and an optional scope of type String. Usage: HTTP(...).oauth2(\"https://auth.example.com/token\", client_id, client_secret, \"read\")";
+pub const ERROR_HTTP_OAUTH2_CLIENT: &str =
+    "OAuth2 client not found, use HTTP(...).oauth2(...) again";
+pub const ERROR_HTTP_OAUTH2_TOKEN: &str = "failed to get OAuth2 token";
+pub const ERROR_HTTP_FORM_BODY: &str =
+    "the body of a form must be of type Object, read doc for correct usage";
+pub const ERROR_HTTP_MULTIPART_FILE: &str =
+    "file of a multipart form must have a 'filename' and a base64 'content' of type String";
+pub const ERROR_HTTP_MULTIPART_CONTENT_TYPE: &str =
+    "file of a multipart form can not have a line break in its 'content_type'";
+pub const ERROR_HTTP_RESPONSE_SIZE: &str = "HTTP response is larger than the maximum size";
+pub const ERROR_HTTP_RESPONSE_READ: &str = "failed to read HTTP response";
+pub const ERROR_HTTP_MOCK_FILE: &str = "failed to read HTTP mock file";
+pub const ERROR_HTTP_MOCK_UNKNOWN: &str = "no mocked response for request";
+pub const ERROR_HTTP_MOCK_RECORD: &str = "failed to record HTTP call";
+pub const ERROR_PARALLEL_HTTP: &str =
+    "[all] takes an Array of HTTP objects, read doc for correct usage";
+pub const ERROR_PARALLEL_CONCURRENCY: &str = "concurrency must be an Int between 1 and";
+pub const ERROR_PARALLEL_REQUEST: &str = "request failed before receiving a response";
+pub const ERROR_PARALLEL_METHOD: &str = "only has the 'all' method, read doc for correct usage";
+pub const ERROR_NETWORK_POLICY: &str = "request blocked by the network policy";
+pub const ERROR_NETWORK_POLICY_SSL: &str =
+    "the network policy does not allow to disable the SSL verification";
+pub const ERROR_NETWORK_POLICY_SMTP_TLS: &str =
+    "the network policy does not allow SMTP servers without TLS";
 
 // #### OBJECT
 pub const ERROR_OBJECT_TYPE: &str = "value must be of type Object";
@@ -314,15 +448,35 @@ pub const ERROR_OBJECT_ASSIGN: &str =
     "[assign] take one argument. Usage: object.assign({\"key\": \"value\"})";
 pub const ERROR_OBJECT_REMOVE: &str =
     "[remove] takes one argument of type String. Usage: object.remove(\"key\")";
+pub const ERROR_OBJECT_GET_PATH: &str =
+    "[get_path] takes a path of type String and an optional default value. Usage: object.get_path(\"a.b[0].c\", default)";
+pub const ERROR_OBJECT_SET_PATH: &str =
+    "[set_path] takes a path of type String and a value, the path must lead to an Object or an Array. Usage: object.set_path(\"a.b[0].c\", 42)";
+pub const ERROR_OBJECT_PATH_FORMAT: &str =
+    "path is bad formatted, expected keys and indexes like \"a.b[0].c\"";
+pub const ERROR_OBJECT_DEEP_MERGE: &str =
+    "[deep_merge] takes one argument of type Object. Usage: object.deep_merge({\"key\": \"value\"})";
+pub const ERROR_OBJECT_PICK: &str =
+    "[pick] takes one argument of type Array of String. Usage: object.pick([\"key\"])";
+pub const ERROR_OBJECT_OMIT: &str =
+    "[omit] takes one argument of type Array of String. Usage: object.omit([\"key\"])";
 pub const ERROR_OBJECT_GET_KEY: &str = "key must be of type String";
 pub const ERROR_OBJECT_UNKNOWN_METHOD: &str = "is not a method of Object";
 
+// #### QUERY
+pub const ERROR_QUERY: &str =
+    "[query] takes one argument of type String. Usage: object.query(\"users[?age > `18`].name\")";
+pub const ERROR_QUERY_EXPRESSION: &str = "[query] invalid JMESPath expression:";
+
 // #### METHODS
 pub const ERROR_METHOD_NAMED_ARGS: &str = "arguments in method are not named";
+pub const ERROR_METHOD_UNKNOWN_ARG: &str = "unknown argument of the method";
+pub const ERROR_METHOD_DUPLICATE_ARG: &str = "argument of the method given twice";
 
 pub const ERROR_OPS: &str = "[!] Ops: Illegal operation";
 pub const ERROR_OPS_DIV_INT: &str = "[!] Int: Division by zero";
 pub const ERROR_OPS_DIV_FLOAT: &str = "[!] Float: Division by zero";
+pub const ERROR_OPS_DIV_DECIMAL: &str = "[!] Decimal: Division by zero";
 
 pub const ERROR_ILLEGAL_OPERATION: &str = "illegal operation:";
 pub const OVERFLOWING_OPERATION: &str = "overflowing operation:";
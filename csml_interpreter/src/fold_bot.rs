@@ -30,6 +30,7 @@ pub fn fold_bot(
     let mut insert_list = HashSet::new();
     let mut valid_closure_list = vec![];
     let mut functions_call_list = vec![];
+    let mut translation_list = vec![];
 
     let mut linter_info = LinterInfo::new(
         "",
@@ -44,6 +45,7 @@ pub fn fold_bot(
         &mut insert_list,
         &mut valid_closure_list,
         &mut functions_call_list,
+        &mut translation_list,
         errors,
         warnings,
         native_components,
@@ -14,7 +14,7 @@ use crate::data::{
 };
 use crate::error_format::*;
 use crate::interpreter::{
-    ast_interpreter::{for_loop, match_actions, solve_if_statement, while_loop},
+    ast_interpreter::{for_loop, match_actions, solve_form, solve_if_statement, while_loop},
     variable_handler::{expr_to_literal, interval::interval_from_expr},
 };
 use crate::parser::ExitCondition;
@@ -55,8 +55,11 @@ pub fn interpret_scope(
             if hold.index.command_index > instruction_total {
                 continue;
             } else if hold.index.command_index == instruction_info.index {
-                data.context.hold = None;
-                continue; // this command is the hold, we need to skip it in order to continue the conversation
+                // a form holding for an answer is resumed with the event instead of being skipped
+                if !matches!(action, Expr::FormExpr(..)) {
+                    data.context.hold = None;
+                    continue; // this command is the hold, we need to skip it in order to continue the conversation
+                }
             }
         }
 
@@ -159,6 +162,37 @@ pub fn interpret_scope(
             Expr::WhileExpr(expr, block, range) => {
                 message_data = while_loop(expr, block, range, message_data, data, &sender)?
             }
+            Expr::FormExpr(form) => {
+                let index = instruction_info.index;
+                let resume = match &data.context.hold {
+                    Some(hold) => hold.index.command_index == index,
+                    None => false,
+                };
+                data.context.hold = None;
+
+                message_data = solve_form(form, resume, message_data, data, &sender)?;
+
+                if let Some(ExitCondition::Hold) = message_data.exit_condition {
+                    let map = data.step_vars.to_owned();
+
+                    let hold = Hold::new(
+                        IndexInfo {
+                            command_index: index,
+                            loop_index: data.loop_indexes.clone(),
+                        },
+                        step_vars_to_json(map),
+                        data.context.step.get_step(),
+                        data.context.flow.clone(),
+                        data.previous_info.clone(),
+                        false,
+                    );
+
+                    message_data.hold = Some(hold.to_owned());
+
+                    MSG::send(&sender, MSG::Hold(hold));
+                    return Ok(message_data);
+                }
+            }
             e => {
                 return Err(gen_error_info(
                     Position::new(interval_from_expr(e), &data.context.flow),
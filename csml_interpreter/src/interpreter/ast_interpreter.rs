@@ -1,9 +1,11 @@
 mod actions;
 mod for_loop;
+mod form;
 mod if_statement;
 mod while_loop;
 
 pub use actions::match_actions;
 pub use for_loop::for_loop;
+pub use form::solve_form;
 pub use if_statement::{evaluate_condition, solve_if_statement};
 pub use while_loop::while_loop;
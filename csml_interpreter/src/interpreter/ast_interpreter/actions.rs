@@ -192,6 +192,10 @@ pub fn match_actions(
                 data.previous_info.clone(),
                 data.custom_component,
                 data.native_component,
+                data.knowledge,
+                data.localization,
+                data.files,
+                data.network_policies,
             );
             // #####################
 
@@ -0,0 +1,283 @@
+use crate::data::primitive::{
+    string::EMAIL_REGEX, PrimitiveArray, PrimitiveFloat, PrimitiveInt, PrimitiveNull,
+    PrimitiveObject, PrimitiveString,
+};
+use crate::data::{ast::*, tokens::*, ArgsType, Data, Literal, MessageData, MSG};
+use crate::error_format::*;
+use crate::interpreter::{
+    ast_interpreter::{if_statement::valid_condition, match_actions},
+    builtins::extract::extract,
+};
+use crate::parser::ExitCondition;
+use std::collections::HashMap;
+use std::sync::mpsc;
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+const FIELD: &str = "field";
+const RETRIES: &str = "retries";
+
+/**
+ * Progress of a form, kept in the step vars while waiting for an answer:
+ * the index of the field being asked and the number of invalid answers already given.
+ */
+#[derive(Default)]
+struct FormState {
+    field: usize,
+    retries: usize,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+fn get_state_name(form: &Form) -> String {
+    format!("_form_{}", form.name.ident)
+}
+
+fn get_object(name: &str, data: &Data) -> HashMap<String, Literal> {
+    match data.step_vars.get(name) {
+        Some(lit) => match lit.primitive.as_any().downcast_ref::<PrimitiveObject>() {
+            Some(object) => object.value.to_owned(),
+            None => HashMap::new(),
+        },
+        None => HashMap::new(),
+    }
+}
+
+fn get_state(form: &Form, data: &Data) -> FormState {
+    let state = get_object(&get_state_name(form), data);
+    let get_index = |key: &str| {
+        state
+            .get(key)
+            .and_then(|lit| lit.primitive.to_string().parse::<usize>().ok())
+            .unwrap_or(0)
+    };
+
+    FormState {
+        field: get_index(FIELD),
+        retries: get_index(RETRIES),
+    }
+}
+
+/**
+ * The answers are stored in a step var named after the form, partial results included,
+ * so that they are saved with the hold and the form can resume on the next event.
+ */
+fn save_form(
+    form: &Form,
+    values: &HashMap<String, Literal>,
+    state: Option<&FormState>,
+    data: &mut Data,
+) {
+    data.step_vars.insert(
+        form.name.ident.to_owned(),
+        PrimitiveObject::get_literal(values, form.interval),
+    );
+
+    match state {
+        Some(state) => {
+            let mut map = HashMap::new();
+            map.insert(
+                FIELD.to_owned(),
+                PrimitiveInt::get_literal(state.field as i64, form.interval),
+            );
+            map.insert(
+                RETRIES.to_owned(),
+                PrimitiveInt::get_literal(state.retries as i64, form.interval),
+            );
+
+            data.step_vars.insert(
+                get_state_name(form),
+                PrimitiveObject::get_literal(&map, form.interval),
+            );
+        }
+        None => {
+            data.step_vars.remove(&get_state_name(form));
+        }
+    }
+}
+
+fn get_date(text: &str, data: &mut Data, interval: Interval) -> Result<Option<Literal>, ErrorInfo> {
+    let mut args = HashMap::new();
+    args.insert(
+        "arg0".to_owned(),
+        PrimitiveString::get_literal(text, interval),
+    );
+
+    let entities = extract(ArgsType::Normal(args), data, interval)?;
+
+    let date = entities
+        .primitive
+        .as_any()
+        .downcast_ref::<PrimitiveObject>()
+        .and_then(|entities| entities.value.get("dates"))
+        .and_then(|dates| dates.primitive.as_any().downcast_ref::<PrimitiveArray>())
+        .and_then(|dates| dates.value.first().cloned());
+
+    Ok(date)
+}
+
+/**
+ * Convert the text of the event to the type of the field,
+ * None is returned if the answer is not valid for this type.
+ */
+fn get_answer(field: &FormField, data: &mut Data) -> Result<Option<Literal>, ErrorInfo> {
+    let interval = field.name.interval;
+    let text = data.event.content_value.trim().to_owned();
+
+    let answer = match field.field_type.as_str() {
+        INT => text
+            .parse::<i64>()
+            .ok()
+            .map(|int| PrimitiveInt::get_literal(int, interval)),
+        FLOAT => text
+            .parse::<f64>()
+            .ok()
+            .filter(|float| float.is_finite())
+            .map(|float| PrimitiveFloat::get_literal(float, interval)),
+        EMAIL => match EMAIL_REGEX.is_match(&text) {
+            true => Some(PrimitiveString::get_literal(&text, interval)),
+            false => None,
+        },
+        DATE => get_date(&text, data, interval)?,
+        _ if text.is_empty() => None,
+        _ => Some(PrimitiveString::get_literal(
+            &data.event.content_value,
+            interval,
+        )),
+    };
+
+    Ok(answer.map(|mut answer| {
+        answer.secure_variable = data.event.secure;
+        answer
+    }))
+}
+
+/**
+ * Inside the validate expression the answer is available under the name of the field.
+ */
+fn validate_answer(
+    field: &FormField,
+    answer: &Literal,
+    data: &mut Data,
+    msg_data: &mut MessageData,
+    sender: &Option<mpsc::Sender<MSG>>,
+) -> bool {
+    let validate = match &field.validate {
+        Some(validate) => validate,
+        None => return true,
+    };
+
+    let previous = data
+        .step_vars
+        .insert(field.name.ident.to_owned(), answer.to_owned());
+
+    let is_valid = valid_condition(validate, data, msg_data, sender);
+
+    match previous {
+        Some(previous) => data.step_vars.insert(field.name.ident.to_owned(), previous),
+        None => data.step_vars.remove(&field.name.ident),
+    };
+
+    is_valid
+}
+
+fn say(
+    expr: &Expr,
+    msg_data: MessageData,
+    data: &mut Data,
+    sender: &Option<mpsc::Sender<MSG>>,
+) -> Result<MessageData, ErrorInfo> {
+    match_actions(
+        &ObjectType::Say(Box::new(expr.to_owned())),
+        msg_data,
+        data,
+        sender,
+    )
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PUBLIC FUNCTION
+////////////////////////////////////////////////////////////////////////////////
+
+/**
+ * Ask each field of the form until a valid answer is given.
+ * When an answer is expected the exit condition is set to Hold and the form
+ * is resumed with the next event, a field is left to null when skipped
+ * or when all its retries are used.
+ */
+pub fn solve_form(
+    form: &Form,
+    resume: bool,
+    mut msg_data: MessageData,
+    data: &mut Data,
+    sender: &Option<mpsc::Sender<MSG>>,
+) -> Result<MessageData, ErrorInfo> {
+    let (mut values, mut state) = match resume {
+        true => (get_object(&form.name.ident, data), get_state(form, data)),
+        false => (HashMap::new(), FormState::default()),
+    };
+    let mut waiting_answer = resume;
+
+    while let Some(field) = form.fields.get(state.field) {
+        let name = field.name.ident.to_owned();
+        // the previous answers can be used in the expressions of the next fields
+        save_form(form, &values, Some(&state), data);
+
+        if waiting_answer {
+            waiting_answer = false;
+
+            let answer = match get_answer(field, data)? {
+                Some(answer) if validate_answer(field, &answer, data, &mut msg_data, sender) => {
+                    Some(answer)
+                }
+                _ => None,
+            };
+
+            if let Some(answer) = answer {
+                values.insert(name, answer);
+                state = FormState {
+                    field: state.field + 1,
+                    retries: 0,
+                };
+                continue;
+            }
+
+            state.retries += 1;
+            if let Some(error) = &field.error {
+                msg_data = say(error, msg_data, data, sender)?;
+            }
+
+            if field
+                .retries
+                .map_or(false, |retries| state.retries >= retries)
+            {
+                values.insert(name, PrimitiveNull::get_literal(field.name.interval));
+                state = FormState {
+                    field: state.field + 1,
+                    retries: 0,
+                };
+                continue;
+            }
+        } else if let Some(skip) = &field.skip {
+            if valid_condition(skip, data, &mut msg_data, sender) {
+                values.insert(name, PrimitiveNull::get_literal(field.name.interval));
+                state.field += 1;
+                continue;
+            }
+        }
+
+        msg_data = say(&field.ask, msg_data, data, sender)?;
+        save_form(form, &values, Some(&state), data);
+
+        msg_data.exit_condition = Some(ExitCondition::Hold);
+        return Ok(msg_data);
+    }
+
+    save_form(form, &values, None, data);
+
+    Ok(msg_data)
+}
@@ -1,12 +1,20 @@
 pub mod api;
 pub mod crypto;
 pub mod exists;
+pub mod extract;
 pub mod format;
+pub mod from_file;
 pub mod functions;
 pub mod http_builtin;
+pub mod http_mock;
 pub mod jwt;
+pub mod llm;
+pub mod oauth2;
+pub mod parallel;
 pub mod smtp;
+pub mod template;
 pub mod time;
+pub mod translate;
 
 pub mod tools;
 
@@ -20,12 +28,17 @@ use std::sync::mpsc;
 use api::api;
 use crypto::crypto;
 use exists::exists;
+use extract::extract;
 use format::*;
+use from_file::from_file;
 use functions::*;
 use http_builtin::http;
 use jwt::jwt;
+use llm::llm;
 use smtp::smtp;
+use template::template;
 use time::time;
+use translate::translate;
 // use uri::*;
 
 pub fn match_native_builtin(
@@ -55,6 +68,7 @@ pub fn match_builtin(
     match name {
         HTTP => http(args, &data.context.flow, interval),
         SMTP => smtp(args, &data.context.flow, interval),
+        LLM => llm(args, &data.context.flow, interval),
         BASE64 => base64(args, &data.context.flow, interval),
         HEX => hex(args, &data.context.flow, interval),
         FN | APP => api(args, interval, data, msg_data, sender),
@@ -66,11 +80,16 @@ pub fn match_builtin(
         RANDOM => random(interval),
         DEBUG => debug(args, interval),
         FLOOR => floor(args, &data.context.flow, interval),
+        DECIMAL_BUILT_IN => decimal(args, &data.context.flow, interval),
         UUID => uuid_command(args, &data.context.flow, interval),
         JWT => jwt(args, &data.context.flow, interval),
         CRYPTO => crypto(args, &data.context.flow, interval),
         TIME => time(args, &data.context.flow, interval),
         EXISTS => exists(args, data, interval),
+        EXTRACT => extract(args, data, interval),
+        TRANSLATE => translate(args, data, interval),
+        TEMPLATE => template(args, data, interval),
+        FROM_FILE => from_file(args, data, interval),
 
         //old builtin
         _object => object(args, &data.context.flow, interval),
@@ -70,13 +70,14 @@ fn format_headers(interval: Interval) -> HashMap<String, Literal> {
     header
 }
 
-pub fn api(
-    args: ArgsType,
+/**
+ * HTTP object of the call of an App, sent to the apps endpoint of the bot
+ */
+pub fn get_api_request(
+    args: &ArgsType,
     interval: Interval,
-    data: &mut Data,
-    msg_data: &mut MessageData,
-    sender: &Option<mpsc::Sender<MSG>>,
-) -> Result<Literal, ErrorInfo> {
+    data: &Data,
+) -> Result<HashMap<String, Literal>, ErrorInfo> {
     let (client, url) = match &data.context.api_info {
         Some(ApiInfo {
             client,
@@ -92,7 +93,7 @@ pub fn api(
 
     let mut http: HashMap<String, Literal> = HashMap::new();
     let header = format_headers(interval);
-    let body = format_body(&args, &data.context.flow, interval, client)?;
+    let body = format_body(args, &data.context.flow, interval, client)?;
 
     http.insert(
         "url".to_owned(),
@@ -103,23 +104,55 @@ pub fn api(
     http.insert("header".to_owned(), lit_header);
     http.insert("body".to_owned(), body);
 
-    match http_request(&http, "post", &data.context.flow, interval, true) {
-        Ok((value, response_info)) => match value.get("data") {
-            Some(value) => {
-                let mut literal = interpolate(value, interval, data, msg_data, sender)?;
-                // add additional information about the http request response: status and headers
-                literal.add_info_block(response_info);
-
-                Ok(literal)
-            }
-            None => {
-                let err = gen_error_info(
-                    Position::new(interval, &data.context.flow),
-                    ERROR_HTTP_NOT_DATA.to_owned(),
-                );
-                Ok(MSG::send_error_msg(sender, msg_data, Err(err)))
-            }
-        },
+    Ok(http)
+}
+
+pub fn get_api_response(
+    response: Result<(serde_json::Value, HashMap<String, Literal>), ErrorInfo>,
+    interval: Interval,
+    data: &mut Data,
+    msg_data: &mut MessageData,
+    sender: &Option<mpsc::Sender<MSG>>,
+) -> Result<Literal, ErrorInfo> {
+    let (value, response_info) = response?;
+
+    match value.get("data") {
+        Some(value) => {
+            let mut literal = interpolate(value, interval, data, msg_data, sender)?;
+            // add additional information about the http request response: status and headers
+            literal.add_info_block(response_info);
+
+            Ok(literal)
+        }
+        None => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            ERROR_HTTP_NOT_DATA.to_owned(),
+        )),
+    }
+}
+
+pub fn api(
+    args: ArgsType,
+    interval: Interval,
+    data: &mut Data,
+    msg_data: &mut MessageData,
+    sender: &Option<mpsc::Sender<MSG>>,
+) -> Result<Literal, ErrorInfo> {
+    let http = get_api_request(&args, interval, data)?;
+
+    let response = http_request(
+        &http,
+        "post",
+        &data.context.flow,
+        interval,
+        true,
+        data.event.http_mock.as_ref(),
+        &data.context.oauth2_clients,
+        data.network_policies,
+    );
+
+    match get_api_response(response, interval, data, msg_data, sender) {
+        Ok(literal) => Ok(literal),
         Err(err) => Ok(MSG::send_error_msg(sender, msg_data, Err(err))),
     }
 }
@@ -16,6 +16,13 @@ pub fn crypto(args: ArgsType, flow_name: &str, interval: Interval) -> Result<Lit
             result.set_content_type("crypto");
             Ok(result)
         }
+        // Crypto() without value gives access to the methods that do not need one, like random_bytes
+        None if args.len() == 0 => {
+            let mut result = PrimitiveObject::get_literal(&HashMap::new(), interval);
+
+            result.set_content_type("crypto");
+            Ok(result)
+        }
         _ => Err(gen_error_info(
             Position::new(interval, flow_name),
             ERROR_CRYPTO.to_owned(),
@@ -0,0 +1,395 @@
+use crate::data::error_info::ErrorInfo;
+use crate::data::position::Position;
+use crate::data::primitive::{
+    PrimitiveArray, PrimitiveFloat, PrimitiveInt, PrimitiveObject, PrimitiveString, PrimitiveType,
+};
+use crate::data::{ast::Interval, ArgsType, Data, Literal};
+use crate::error_format::*;
+use chrono::{Datelike, Duration, NaiveDate, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+const DATES: &str = "dates";
+const AMOUNTS: &str = "amounts";
+const EMAILS: &str = "emails";
+const PHONES: &str = "phones";
+const URLS: &str = "urls";
+const NUMBERS: &str = "numbers";
+
+const ENTITIES: &[&str] = &[DATES, AMOUNTS, EMAILS, PHONES, URLS, NUMBERS];
+
+static URL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?i)\b(?:https?://|www\.)[^\s<>"]*[^\s<>".,;:!?)]"#).unwrap());
+static EMAIL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}").unwrap()
+});
+// 2022-07-14
+static ISO_DATE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(\d{4})-(\d{1,2})-(\d{1,2})\b").unwrap());
+// 14/07/2022 (day first)
+static DAY_FIRST_DATE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(\d{1,2})/(\d{1,2})/(\d{4})\b").unwrap());
+static RELATIVE_DAY: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(today|tomorrow|yesterday)\b").unwrap());
+static IN_DAYS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\bin\s+(\d+)\s+(day|week)s?\b").unwrap());
+static DAYS_AGO: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(\d+)\s+(day|week)s?\s+ago\b").unwrap());
+static WEEKDAY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:(next|this|last)\s+)?(monday|tuesday|wednesday|thursday|friday|saturday|sunday)\b")
+        .unwrap()
+});
+static CURRENCY_FIRST_AMOUNT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([$€£¥])\s?(\d+(?:[.,]\d+)?)").unwrap());
+static CURRENCY_LAST_AMOUNT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(\d+(?:[.,]\d+)?)\s?(€|\$|£|¥|\beur\b|\beuros?\b|\busd\b|\bdollars?\b|\bgbp\b|\bpounds?\b|\bjpy\b|\byen\b)")
+        .unwrap()
+});
+static PHONE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\+?\d[\d .()-]{6,}\d").unwrap());
+// 12.345.678 is a number with dots as thousands separators, not a phone
+static DOTTED_THOUSANDS: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\d{1,3}(\.\d{3})+$").unwrap());
+static NUMBER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"-?\d{1,3}(?:,\d{3})+(?:\.\d+)?|-?\d+(?:[.,]\d+)?").unwrap());
+static COMMA_THOUSANDS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^-?\d{1,3}(,\d{3})+(\.\d+)?$").unwrap());
+
+struct Entity {
+    start: usize,
+    end: usize,
+    kind: &'static str,
+    value: Literal,
+}
+
+struct Extractor<'a> {
+    text: &'a str,
+    entities: Vec<Entity>,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+impl<'a> Extractor<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            entities: vec![],
+        }
+    }
+
+    /**
+     * Each part of the text can only belong to one entity: entities are extracted
+     * from the most specific to the most generic one and overlapping matches are dropped.
+     */
+    fn extract<F>(&mut self, kind: &'static str, regex: &Regex, mut gen_value: F)
+    where
+        F: FnMut(&Captures) -> Option<Literal>,
+    {
+        for captures in regex.captures_iter(self.text) {
+            let capture = captures.get(0).unwrap();
+            let (start, end) = (capture.start(), capture.end());
+
+            if self
+                .entities
+                .iter()
+                .any(|entity| start < entity.end && entity.start < end)
+            {
+                continue;
+            }
+
+            if let Some(value) = gen_value(&captures) {
+                self.entities.push(Entity {
+                    start,
+                    end,
+                    kind,
+                    value,
+                });
+            }
+        }
+    }
+
+    fn into_literal(mut self, interval: Interval) -> Literal {
+        let mut map = HashMap::new();
+
+        self.entities.sort_by_key(|entity| entity.start);
+
+        for kind in ENTITIES.iter() {
+            let values: Vec<Literal> = self
+                .entities
+                .iter()
+                .filter(|entity| entity.kind == *kind)
+                .map(|entity| entity.value.to_owned())
+                .collect();
+
+            map.insert(
+                kind.to_string(),
+                PrimitiveArray::get_literal(&values, interval),
+            );
+        }
+
+        PrimitiveObject::get_literal(&map, interval)
+    }
+}
+
+fn get_timezone(args: &ArgsType, data: &Data, interval: Interval) -> Result<Option<Tz>, ErrorInfo> {
+    let timezone = match args.get("timezone", 1) {
+        Some(lit) => Some(lit),
+        None => data.context.metadata.get("timezone"),
+    };
+
+    match timezone {
+        Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveString => {
+            let tz_name = lit.primitive.to_string();
+
+            match tz_name.parse::<Tz>() {
+                Ok(tz) => Ok(Some(tz)),
+                Err(_) => Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    format!("{} {}", ERROR_TIME_TIMEZONE, tz_name),
+                )),
+            }
+        }
+        _ => Ok(None),
+    }
+}
+
+/**
+ * Dates are returned as time literals set at midnight in the given timezone,
+ * so they can be used with all the Time methods.
+ */
+fn gen_date_literal(date: NaiveDate, timezone: &Option<Tz>, interval: Interval) -> Option<Literal> {
+    let midnight = date.and_hms(0, 0, 0);
+    let mut time = HashMap::new();
+
+    let milliseconds = match timezone {
+        Some(tz) => {
+            time.insert(
+                "timezone".to_owned(),
+                PrimitiveString::get_literal(tz.name(), interval),
+            );
+
+            tz.from_local_datetime(&midnight)
+                .earliest()?
+                .timestamp_millis()
+        }
+        None => Utc.from_utc_datetime(&midnight).timestamp_millis(),
+    };
+
+    time.insert(
+        "milliseconds".to_owned(),
+        PrimitiveInt::get_literal(milliseconds, interval),
+    );
+
+    let mut lit = PrimitiveObject::get_literal(&time, interval);
+    lit.set_content_type("time");
+
+    Some(lit)
+}
+
+fn get_weekday(name: &str) -> Option<Weekday> {
+    name.to_lowercase().parse::<Weekday>().ok()
+}
+
+/**
+ * - "friday" or "this friday" is the upcoming friday, today included
+ * - "next friday" is the upcoming friday, today excluded
+ * - "last friday" is the previous friday, today excluded
+ */
+fn resolve_weekday(today: NaiveDate, modifier: Option<&str>, weekday: Weekday) -> NaiveDate {
+    let today_index = today.weekday().num_days_from_monday() as i64;
+    let weekday_index = weekday.num_days_from_monday() as i64;
+
+    let days = match modifier.map(|modifier| modifier.to_lowercase()).as_deref() {
+        Some("last") => -((today_index - weekday_index + 6) % 7 + 1),
+        Some("next") => (weekday_index - today_index + 6) % 7 + 1,
+        _ => (weekday_index - today_index + 7) % 7,
+    };
+
+    today + Duration::days(days)
+}
+
+fn gen_number_literal(number: &str, interval: Interval) -> Option<Literal> {
+    let number = if COMMA_THOUSANDS.is_match(number) {
+        number.replace(',', "")
+    } else {
+        number.replace(',', ".")
+    };
+
+    if number.contains('.') {
+        let float = number.parse::<f64>().ok()?;
+
+        return Some(PrimitiveFloat::get_literal(float, interval));
+    }
+
+    match number.parse::<i64>() {
+        Ok(int) => Some(PrimitiveInt::get_literal(int, interval)),
+        Err(_) => Some(PrimitiveFloat::get_literal(
+            number.parse::<f64>().ok()?,
+            interval,
+        )),
+    }
+}
+
+fn get_currency(currency: &str) -> &'static str {
+    match currency.to_lowercase().as_str() {
+        "$" | "usd" | "dollar" | "dollars" => "USD",
+        "£" | "gbp" | "pound" | "pounds" => "GBP",
+        "¥" | "jpy" | "yen" => "JPY",
+        _ => "EUR",
+    }
+}
+
+fn gen_amount_literal(number: &str, currency: &str, interval: Interval) -> Option<Literal> {
+    let mut amount = HashMap::new();
+
+    amount.insert("value".to_owned(), gen_number_literal(number, interval)?);
+    amount.insert(
+        "currency".to_owned(),
+        PrimitiveString::get_literal(get_currency(currency), interval),
+    );
+
+    Some(PrimitiveObject::get_literal(&amount, interval))
+}
+
+fn extract_dates(extractor: &mut Extractor, timezone: &Option<Tz>, interval: Interval) {
+    let today = match timezone {
+        Some(tz) => Utc::now().with_timezone(tz).naive_local().date(),
+        None => Utc::now().naive_utc().date(),
+    };
+
+    let parse_int = |captures: &Captures, index: usize| -> Option<u32> {
+        captures.get(index)?.as_str().parse::<u32>().ok()
+    };
+
+    extractor.extract(DATES, &ISO_DATE, |captures| {
+        let date = NaiveDate::from_ymd_opt(
+            parse_int(captures, 1)? as i32,
+            parse_int(captures, 2)?,
+            parse_int(captures, 3)?,
+        )?;
+
+        gen_date_literal(date, timezone, interval)
+    });
+
+    extractor.extract(DATES, &DAY_FIRST_DATE, |captures| {
+        let date = NaiveDate::from_ymd_opt(
+            parse_int(captures, 3)? as i32,
+            parse_int(captures, 2)?,
+            parse_int(captures, 1)?,
+        )?;
+
+        gen_date_literal(date, timezone, interval)
+    });
+
+    extractor.extract(DATES, &RELATIVE_DAY, |captures| {
+        let date = match captures[1].to_lowercase().as_str() {
+            "tomorrow" => today + Duration::days(1),
+            "yesterday" => today - Duration::days(1),
+            _ => today,
+        };
+
+        gen_date_literal(date, timezone, interval)
+    });
+
+    extractor.extract(DATES, &IN_DAYS, |captures| {
+        let days = match captures[2].to_lowercase().as_str() {
+            "week" => parse_int(captures, 1)? as i64 * 7,
+            _ => parse_int(captures, 1)? as i64,
+        };
+
+        gen_date_literal(today + Duration::days(days), timezone, interval)
+    });
+
+    extractor.extract(DATES, &DAYS_AGO, |captures| {
+        let days = match captures[2].to_lowercase().as_str() {
+            "week" => parse_int(captures, 1)? as i64 * 7,
+            _ => parse_int(captures, 1)? as i64,
+        };
+
+        gen_date_literal(today - Duration::days(days), timezone, interval)
+    });
+
+    extractor.extract(DATES, &WEEKDAY, |captures| {
+        let weekday = get_weekday(&captures[2])?;
+        let modifier = captures.get(1).map(|modifier| modifier.as_str());
+
+        gen_date_literal(
+            resolve_weekday(today, modifier, weekday),
+            timezone,
+            interval,
+        )
+    });
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PUBLIC FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+pub fn extract(args: ArgsType, data: &mut Data, interval: Interval) -> Result<Literal, ErrorInfo> {
+    let text = match args.get("text", 0) {
+        Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveString => {
+            lit.primitive.to_string()
+        }
+        _ => {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                ERROR_EXTRACT.to_owned(),
+            ))
+        }
+    };
+    let timezone = get_timezone(&args, data, interval)?;
+
+    let mut extractor = Extractor::new(&text);
+
+    extractor.extract(URLS, &URL, |captures| {
+        Some(PrimitiveString::get_literal(&captures[0], interval))
+    });
+
+    extractor.extract(EMAILS, &EMAIL, |captures| {
+        Some(PrimitiveString::get_literal(&captures[0], interval))
+    });
+
+    extract_dates(&mut extractor, &timezone, interval);
+
+    extractor.extract(AMOUNTS, &CURRENCY_FIRST_AMOUNT, |captures| {
+        gen_amount_literal(&captures[2], &captures[1], interval)
+    });
+
+    extractor.extract(AMOUNTS, &CURRENCY_LAST_AMOUNT, |captures| {
+        gen_amount_literal(&captures[1], &captures[2], interval)
+    });
+
+    // phone numbers have an international prefix or groups of digits, so plain
+    // numbers like order ids stay numbers. They are normalized to their digits.
+    extractor.extract(PHONES, &PHONE, |captures| {
+        let text = &captures[0];
+        let is_grouped = text.chars().any(|c| !c.is_ascii_digit() && c != '+');
+
+        if !text.starts_with('+') && (!is_grouped || DOTTED_THOUSANDS.is_match(text)) {
+            return None;
+        }
+
+        let phone: String = text
+            .chars()
+            .filter(|c| c.is_ascii_digit() || *c == '+')
+            .collect();
+        let digits = phone.chars().filter(|c| c.is_ascii_digit()).count();
+
+        match digits {
+            8..=15 => Some(PrimitiveString::get_literal(&phone, interval)),
+            _ => None,
+        }
+    });
+
+    extractor.extract(NUMBERS, &NUMBER, |captures| {
+        gen_number_literal(&captures[0], interval)
+    });
+
+    Ok(extractor.into_literal(interval))
+}
@@ -0,0 +1,41 @@
+use crate::data::error_info::ErrorInfo;
+use crate::data::position::Position;
+use crate::data::primitive::PrimitiveType;
+use crate::data::{ast::Interval, ArgsType, Data, Literal};
+use crate::error_format::*;
+
+////////////////////////////////////////////////////////////////////////////////
+// PUBLIC FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+pub fn from_file(
+    args: ArgsType,
+    data: &mut Data,
+    interval: Interval,
+) -> Result<Literal, ErrorInfo> {
+    let name = match args.get("file", 0) {
+        Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveString => {
+            lit.primitive.to_string()
+        }
+        _ => {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                ERROR_FROM_FILE.to_owned(),
+            ))
+        }
+    };
+
+    // files are parsed once with the bot, each call only gets a copy of the literal
+    match data.files.and_then(|files| files.get(&name)) {
+        Some(lit) => {
+            let mut lit = lit.to_owned();
+            lit.interval = interval;
+
+            Ok(lit)
+        }
+        None => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            format!("{} [{}]", ERROR_FROM_FILE_MISSING, name),
+        )),
+    }
+}
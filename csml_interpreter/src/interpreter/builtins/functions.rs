@@ -1,6 +1,8 @@
 use crate::data::position::Position;
 use crate::data::primitive::{
-    PrimitiveArray, PrimitiveBoolean, PrimitiveFloat, PrimitiveInt, PrimitiveString,
+    decimal::{get_rounding_mode, DEFAULT_ROUNDING, MAX_DECIMAL_SCALE},
+    PrimitiveArray, PrimitiveBoolean, PrimitiveDecimal, PrimitiveFloat, PrimitiveInt,
+    PrimitiveString, PrimitiveType,
 };
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -195,6 +197,76 @@ pub fn floor(args: ArgsType, flow_name: &str, interval: Interval) -> Result<Lite
     }
 }
 
+/**
+ * Decimal(value, scale, rounding = "half_even"), the value can be a number or a numeric String,
+ * it is only rounded when a scale is given
+ */
+pub fn decimal(args: ArgsType, flow_name: &str, interval: Interval) -> Result<Literal, ErrorInfo> {
+    let (value, secure_variable) = match args.get("value", 0) {
+        Some(literal) => match PrimitiveDecimal::from_primitive(&*literal.primitive) {
+            Ok(decimal) => (decimal.value, literal.secure_variable),
+            Err(err) => {
+                return Err(gen_error_info(
+                    Position::new(interval, flow_name),
+                    err.to_owned(),
+                ))
+            }
+        },
+        None => {
+            return Err(gen_error_info(
+                Position::new(interval, flow_name),
+                ERROR_DECIMAL.to_owned(),
+            ))
+        }
+    };
+
+    let rounding = match args.get("rounding", 2) {
+        Some(rounding) => rounding.primitive.to_string(),
+        None => DEFAULT_ROUNDING.to_owned(),
+    };
+    let rounding = match get_rounding_mode(&rounding) {
+        Some(rounding) => rounding,
+        None => {
+            return Err(gen_error_info(
+                Position::new(interval, flow_name),
+                ERROR_DECIMAL_ROUNDING.to_owned(),
+            ))
+        }
+    };
+
+    let value = match args.get("scale", 1) {
+        Some(scale) if scale.primitive.get_type() == PrimitiveType::PrimitiveInt => {
+            let scale = Literal::get_value::<i64>(
+                &scale.primitive,
+                flow_name,
+                interval,
+                ERROR_DECIMAL.to_owned(),
+            )?;
+
+            if !(0..=MAX_DECIMAL_SCALE).contains(scale) {
+                return Err(gen_error_info(
+                    Position::new(interval, flow_name),
+                    ERROR_DECIMAL_SCALE.to_owned(),
+                ));
+            }
+
+            value.with_scale_round(*scale, rounding)
+        }
+        Some(_) => {
+            return Err(gen_error_info(
+                Position::new(interval, flow_name),
+                ERROR_DECIMAL.to_owned(),
+            ))
+        }
+        None => value,
+    };
+
+    let mut literal = PrimitiveDecimal::get_literal(value, interval);
+    literal.secure_variable = secure_variable;
+
+    Ok(literal)
+}
+
 pub fn uuid_command(
     args: ArgsType,
     flow_name: &str,
@@ -1,12 +1,19 @@
 use crate::data::error_info::ErrorInfo;
 use crate::data::position::Position;
 use crate::data::primitive::{PrimitiveInt, PrimitiveObject, PrimitiveString, PrimitiveType};
-use crate::data::{ast::Interval, csml_logs::*, ArgsType, Literal};
+use crate::data::{
+    ast::Interval, csml_logs::*, network_policy, ArgsType, HttpMock, HttpMockMode, Literal,
+    NetworkPolicy,
+};
 use crate::error_format::*;
+use crate::interpreter::builtins::http_mock;
+use crate::interpreter::builtins::oauth2::{self, OAuth2Clients};
 use std::collections::HashMap;
 use std::env;
-
+use std::io::Read;
 use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 use ureq::{Request, Response};
 
 use rustls::{
@@ -18,6 +25,31 @@ use rustls::{
 /// DATA TYPES
 ////////////////////////////////////////////////////////////////////////////////
 
+const DEFAULT_RETRY_BACKOFF: u64 = 500;
+const MAX_RETRY_COUNT: u64 = 10;
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+// a retried call can not block the bot longer than this, whatever the count and backoff
+const MAX_TOTAL_RETRY_DELAY: Duration = Duration::from_secs(60);
+// methods that can be sent again without changing the result of the first call
+const IDEMPOTENT_METHODS: [&str; 5] = ["get", "head", "put", "delete", "options"];
+// same limit as the one of ureq for the responses read as string
+const DEFAULT_MAX_RESPONSE_SIZE: u64 = 10 * 1024 * 1024;
+// status codes of temporary failures
+const RETRY_STATUS: [u16; 7] = [408, 425, 429, 500, 502, 503, 504];
+
+enum Body {
+    Json(serde_json::Value),
+    Form(Vec<(String, String)>),
+    Multipart { boundary: String, data: Vec<u8> },
+}
+
+struct Retry {
+    count: u64,
+    backoff: u64,
+    // POST and PATCH calls are only retried when asked, the first call may have been processed
+    non_idempotent: bool,
+}
+
 pub(crate) struct NoVerifier;
 
 impl ServerCertVerifier for NoVerifier {
@@ -97,6 +129,21 @@ fn get_request_info(response: &Response, interval: Interval) -> HashMap<String,
     response_info
 }
 
+/**
+ * Optional request timeout in milliseconds, set with the "timeout" key of the object
+ */
+pub fn get_timeout(object: &HashMap<String, Literal>) -> Option<Duration> {
+    match object.get("timeout") {
+        Some(val) if val.primitive.get_type() == PrimitiveType::PrimitiveInt => {
+            match val.primitive.to_string().parse::<u64>() {
+                Ok(timeout) if timeout > 0 => Some(Duration::from_millis(timeout)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 pub fn get_ssl_state(object: &HashMap<String, Literal>) -> bool {
     match object.get("disable_ssl_verify") {
         Some(val) if val.primitive.get_type() == PrimitiveType::PrimitiveBoolean => {
@@ -110,6 +157,39 @@ pub fn get_ssl_state(object: &HashMap<String, Literal>) -> bool {
 /// PUBLIC FUNCTIONS
 ////////////////////////////////////////////////////////////////////////////////
 
+pub fn get_method(
+    object: &HashMap<String, Literal>,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<&'static str, ErrorInfo> {
+    let literal = match object.get("method") {
+        Some(literal) => literal,
+        None => {
+            return Err(gen_error_info(
+                Position::new(interval, flow_name),
+                ERROR_HTTP_SEND.to_owned(),
+            ))
+        }
+    };
+
+    match Literal::get_value::<String>(
+        &literal.primitive,
+        flow_name,
+        interval,
+        ERROR_HTTP_UNKNOWN_METHOD.to_string(),
+    ) {
+        Ok(delete) if delete == "delete" => Ok("delete"),
+        Ok(put) if put == "put" => Ok("put"),
+        Ok(patch) if patch == "patch" => Ok("patch"),
+        Ok(post) if post == "post" => Ok("post"),
+        Ok(get) if get == "get" => Ok("get"),
+        _ => Err(gen_error_info(
+            Position::new(interval, flow_name),
+            ERROR_HTTP_UNKNOWN_METHOD.to_string(),
+        )),
+    }
+}
+
 pub fn get_url(
     object: &HashMap<String, Literal>,
     flow_name: &str,
@@ -131,7 +211,11 @@ pub fn get_url(
         if length > 0 {
             url.push_str("?");
 
-            for (index, key) in query.keys().enumerate() {
+            // parameters are sorted so the same query always gives the same url
+            let mut keys: Vec<&String> = query.keys().collect();
+            keys.sort();
+
+            for (index, key) in keys.into_iter().enumerate() {
                 let value = match query.get(key) {
                     Some(val) => val.primitive.to_string(),
                     None => {
@@ -156,7 +240,7 @@ pub fn get_url(
     Ok(url.to_owned())
 }
 
-fn get_no_certificate_verifier_agent() -> ureq::Agent {
+fn get_no_certificate_verifier_config() -> Arc<rustls::ClientConfig> {
     let root_store = rustls::RootCertStore::empty();
 
     let mut tls_config = rustls::ClientConfig::builder()
@@ -168,49 +252,83 @@ fn get_no_certificate_verifier_agent() -> ureq::Agent {
         .dangerous()
         .set_certificate_verifier(Arc::new(NoVerifier));
 
-    ureq::AgentBuilder::new()
-        .tls_config(Arc::new(tls_config))
-        .build()
+    Arc::new(tls_config)
 }
 
-fn get_http_request(
+fn is_ssl_verify_disabled(is_ssl_disable: bool) -> bool {
+    match env::var("DISABLE_SSL_VERIFY") {
+        Ok(disable_ssl_verify) => matches!(
+            disable_ssl_verify.parse::<bool>(),
+            Ok(low_data) if low_data || is_ssl_disable
+        ),
+        Err(_) => false,
+    }
+}
+
+/**
+ * Agent of the calls that can not use the default one of ureq: the calls without
+ * SSL verification and the calls restricted by a network policy.
+ */
+pub(crate) fn get_agent(
+    disable_ssl_verify: bool,
+    policies: &[NetworkPolicy],
+) -> Option<ureq::Agent> {
+    if !disable_ssl_verify && policies.is_empty() {
+        return None;
+    }
+
+    let mut builder = ureq::AgentBuilder::new();
+
+    if disable_ssl_verify {
+        builder = builder.tls_config(get_no_certificate_verifier_config());
+    }
+
+    if !policies.is_empty() {
+        let policies = policies.to_vec();
+
+        builder =
+            builder.resolver(move |netloc: &str| network_policy::resolve_netloc(&policies, netloc));
+    }
+
+    Some(builder.build())
+}
+
+pub(crate) fn get_http_request(
     method: &str,
     url: &str,
     flow_name: &str,
     interval: Interval,
     is_ssl_disable: bool,
+    policies: &[NetworkPolicy],
 ) -> Result<Request, ErrorInfo> {
-    if let Ok(disable_ssl_verify) = env::var("DISABLE_SSL_VERIFY") {
-        match disable_ssl_verify.parse::<bool>() {
-            Ok(low_data) if low_data || is_ssl_disable => {
-                let agent = get_no_certificate_verifier_agent();
-
-                let request = match method {
-                    delete if delete == "delete" => agent.delete(url),
-                    put if put == "put" => agent.put(url),
-                    patch if patch == "patch" => agent.request("PATCH", url),
-                    post if post == "post" => agent.post(url),
-                    get if get == "get" => agent.get(url),
-                    _ => {
-                        return Err(gen_error_info(
-                            Position::new(interval, flow_name),
-                            ERROR_HTTP_UNKNOWN_METHOD.to_string(),
-                        ))
-                    }
-                };
+    if is_ssl_disable && !network_policy::allows_disable_ssl_verify(policies) {
+        return Err(gen_error_info(
+            Position::new(interval, flow_name),
+            ERROR_NETWORK_POLICY_SSL.to_owned(),
+        ));
+    }
 
-                return Ok(request);
-            }
-            _ => {}
-        }
+    if let Err(err) = network_policy::check_url(policies, url) {
+        return Err(gen_error_info(
+            Position::new(interval, flow_name),
+            format!("{} [{}]: {}", ERROR_NETWORK_POLICY, url, err),
+        ));
     }
 
-    let request = match method {
-        delete if delete == "delete" => ureq::delete(url),
-        put if put == "put" => ureq::put(url),
-        patch if patch == "patch" => ureq::request("PATCH", url),
-        post if post == "post" => ureq::post(url),
-        get if get == "get" => ureq::get(url),
+    let request = match (
+        get_agent(is_ssl_verify_disabled(is_ssl_disable), policies),
+        method,
+    ) {
+        (Some(agent), "delete") => agent.delete(url),
+        (Some(agent), "put") => agent.put(url),
+        (Some(agent), "patch") => agent.request("PATCH", url),
+        (Some(agent), "post") => agent.post(url),
+        (Some(agent), "get") => agent.get(url),
+        (None, "delete") => ureq::delete(url),
+        (None, "put") => ureq::put(url),
+        (None, "patch") => ureq::request("PATCH", url),
+        (None, "post") => ureq::post(url),
+        (None, "get") => ureq::get(url),
         _ => {
             return Err(gen_error_info(
                 Position::new(interval, flow_name),
@@ -222,14 +340,14 @@ fn get_http_request(
     Ok(request)
 }
 
-pub fn http_request(
+fn build_request(
     object: &HashMap<String, Literal>,
     method: &str,
+    url: &str,
+    policies: &[NetworkPolicy],
     flow_name: &str,
     interval: Interval,
-    is_app_call: bool,
-) -> Result<(serde_json::Value, HashMap<String, Literal>), ErrorInfo> {
-    let url = get_url(object, flow_name, interval)?;
+) -> Result<Request, ErrorInfo> {
     let is_ssl_disable = get_ssl_state(object);
 
     let header = get_value::<HashMap<String, Literal>>(
@@ -240,7 +358,11 @@ pub fn http_request(
         ERROR_HTTP_GET_VALUE,
     )?;
 
-    let mut request = get_http_request(method, &url, flow_name, interval, is_ssl_disable)?;
+    let mut request = get_http_request(method, url, flow_name, interval, is_ssl_disable, policies)?;
+
+    if let Some(timeout) = get_timeout(object) {
+        request = request.timeout(timeout);
+    }
 
     for key in header.keys() {
         let value = match header.get(key) {
@@ -256,6 +378,397 @@ pub fn http_request(
         request = request.set(key, &value);
     }
 
+    Ok(request)
+}
+
+/**
+ * Values of the fields of a form body, array values give one field per element
+ */
+fn get_field_values(value: &Literal) -> Vec<&Literal> {
+    if value.primitive.get_type() == PrimitiveType::PrimitiveArray {
+        if let Ok(array) =
+            Literal::get_value::<Vec<Literal>>(&value.primitive, "", value.interval, String::new())
+        {
+            return array.iter().collect();
+        }
+    }
+
+    vec![value]
+}
+
+fn get_fields<'a>(
+    body: &'a Literal,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<Vec<(&'a String, &'a Literal)>, ErrorInfo> {
+    if body.primitive.get_type() == PrimitiveType::PrimitiveNull {
+        return Ok(vec![]);
+    }
+
+    let body = Literal::get_value::<HashMap<String, Literal>>(
+        &body.primitive,
+        flow_name,
+        interval,
+        ERROR_HTTP_FORM_BODY.to_owned(),
+    )?;
+
+    // fields are sorted so the same object always gives the same body
+    let mut keys: Vec<&String> = body.keys().collect();
+    keys.sort();
+
+    Ok(keys
+        .into_iter()
+        .flat_map(|key| {
+            get_field_values(&body[key])
+                .into_iter()
+                .map(move |value| (key, value))
+        })
+        .collect())
+}
+
+fn escape_multipart_name(name: &str) -> String {
+    name.replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/**
+ * Multipart form (RFC 7578), a field is a file when its value is an object with a
+ * 'filename' and a base64 'content'
+ */
+fn get_multipart_body(
+    body: &Literal,
+    boundary: &str,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<Vec<u8>, ErrorInfo> {
+    let mut data = vec![];
+
+    for (name, value) in get_fields(body, flow_name, interval)? {
+        data.extend(format!("--{}\r\n", boundary).as_bytes());
+
+        let file = match value.primitive.get_type() {
+            PrimitiveType::PrimitiveObject => {
+                let file = value.primitive.to_json();
+                file["filename"]
+                    .as_str()
+                    .map(|filename| (filename.to_owned(), file.to_owned()))
+            }
+            _ => None,
+        };
+
+        match file {
+            Some((filename, file)) => {
+                let content = match file["content"].as_str().map(base64::decode) {
+                    Some(Ok(content)) => content,
+                    _ => {
+                        return Err(gen_error_info(
+                            Position::new(interval, flow_name),
+                            format!("'{}' {}", name, ERROR_HTTP_MULTIPART_FILE),
+                        ))
+                    }
+                };
+                let content_type = file["content_type"]
+                    .as_str()
+                    .unwrap_or("application/octet-stream");
+                if content_type.contains('\r') || content_type.contains('\n') {
+                    return Err(gen_error_info(
+                        Position::new(interval, flow_name),
+                        format!("'{}' {}", name, ERROR_HTTP_MULTIPART_CONTENT_TYPE),
+                    ));
+                }
+
+                data.extend(
+                    format!(
+                        "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+                        escape_multipart_name(name),
+                        escape_multipart_name(&filename),
+                        content_type
+                    )
+                    .as_bytes(),
+                );
+                data.extend(content);
+            }
+            None => {
+                data.extend(
+                    format!(
+                        "Content-Disposition: form-data; name=\"{}\"\r\n\r\n{}",
+                        escape_multipart_name(name),
+                        value.primitive.to_string()
+                    )
+                    .as_bytes(),
+                );
+            }
+        }
+
+        data.extend(b"\r\n");
+    }
+    data.extend(format!("--{}--\r\n", boundary).as_bytes());
+
+    Ok(data)
+}
+
+/**
+ * Body of the request, encoded as set with the "body_type" key of the object
+ */
+fn get_body(
+    object: &HashMap<String, Literal>,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<Option<Body>, ErrorInfo> {
+    let body = match object.get("body") {
+        Some(body) => body,
+        None => return Ok(None),
+    };
+
+    let body_type = object.get("body_type").map(|lit| lit.primitive.to_string());
+
+    match body_type.as_deref() {
+        Some("form") => {
+            let fields = get_fields(body, flow_name, interval)?
+                .into_iter()
+                .map(|(name, value)| (name.to_owned(), value.primitive.to_string()))
+                .collect();
+
+            Ok(Some(Body::Form(fields)))
+        }
+        Some("multipart") => {
+            let boundary = format!("csml-{}", uuid::Uuid::new_v4().simple());
+            let data = get_multipart_body(body, &boundary, flow_name, interval)?;
+
+            Ok(Some(Body::Multipart { boundary, data }))
+        }
+        _ => Ok(Some(Body::Json(body.primitive.to_json()))),
+    }
+}
+
+fn get_retry(object: &HashMap<String, Literal>) -> Retry {
+    match object.get("retry") {
+        Some(val) if val.primitive.get_type() == PrimitiveType::PrimitiveObject => {
+            let retry = val.primitive.to_json();
+
+            Retry {
+                count: retry["count"]
+                    .as_u64()
+                    .unwrap_or_default()
+                    .min(MAX_RETRY_COUNT),
+                backoff: retry["backoff"].as_u64().unwrap_or(DEFAULT_RETRY_BACKOFF),
+                non_idempotent: retry["non_idempotent"].as_bool().unwrap_or_default(),
+            }
+        }
+        _ => Retry {
+            count: 0,
+            backoff: DEFAULT_RETRY_BACKOFF,
+            non_idempotent: false,
+        },
+    }
+}
+
+fn get_max_size(object: &HashMap<String, Literal>) -> u64 {
+    match object.get("max_size") {
+        Some(val) if val.primitive.get_type() == PrimitiveType::PrimitiveInt => {
+            match val.primitive.to_string().parse::<u64>() {
+                Ok(max_size) if max_size > 0 => max_size,
+                _ => DEFAULT_MAX_RESPONSE_SIZE,
+            }
+        }
+        _ => DEFAULT_MAX_RESPONSE_SIZE,
+    }
+}
+
+fn get_response_type(object: &HashMap<String, Literal>) -> String {
+    match object.get("response_type") {
+        Some(val) if val.primitive.get_type() == PrimitiveType::PrimitiveString => {
+            val.primitive.to_string()
+        }
+        _ => "json".to_owned(),
+    }
+}
+
+/**
+ * Transport errors and the status codes of temporary failures can be retried,
+ * the delay doubles at each attempt unless the server asks for a longer one
+ */
+fn get_retry_delay(err: &ureq::Error, retry: &Retry, attempts: u64) -> Option<Duration> {
+    let retry_after = match err {
+        ureq::Error::Status(code, response) if RETRY_STATUS.contains(code) => response
+            .header("Retry-After")
+            .and_then(|seconds| seconds.parse::<u64>().ok())
+            .map(Duration::from_secs),
+        ureq::Error::Status(..) => return None,
+        ureq::Error::Transport(transport) => match transport.kind() {
+            ureq::ErrorKind::InvalidUrl
+            | ureq::ErrorKind::UnknownScheme
+            | ureq::ErrorKind::InvalidProxyUrl => return None,
+            _ => None,
+        },
+    };
+
+    let backoff = Duration::from_millis(retry.backoff.saturating_mul(1 << (attempts - 1).min(16)));
+    let delay = match retry_after {
+        Some(retry_after) if retry_after > backoff => retry_after,
+        _ => backoff,
+    };
+
+    Some(delay.min(MAX_RETRY_DELAY))
+}
+
+fn send_request(request: Request, body: &Option<Body>) -> Result<Response, ureq::Error> {
+    match body {
+        Some(Body::Json(value)) => request.send_json(value.to_owned()),
+        Some(Body::Form(fields)) => {
+            let form: Vec<(&str, &str)> = fields
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .collect();
+
+            request.send_form(&form)
+        }
+        Some(Body::Multipart { boundary, data }) => request
+            .set(
+                "Content-Type",
+                &format!("multipart/form-data; boundary={}", boundary),
+            )
+            .send_bytes(data),
+        None => request.call(),
+    }
+}
+
+fn call(
+    request: Request,
+    object: &HashMap<String, Literal>,
+    body: &Option<Body>,
+    (method, url): (&str, &str),
+    oauth2_clients: &OAuth2Clients,
+    policies: &[NetworkPolicy],
+    flow_name: &str,
+    interval: Interval,
+) -> Result<Result<Response, ureq::Error>, ErrorInfo> {
+    // the OAuth2 token is added after the request is logged
+    match oauth2::get_authorization(object, false, oauth2_clients, policies, flow_name, interval)? {
+        Some(authorization) => {
+            match send_request(request.set("Authorization", &authorization), body) {
+                // the token may have been revoked before its expiry, it is renewed once
+                Err(ureq::Error::Status(401, _)) => {
+                    let authorization = oauth2::get_authorization(
+                        object,
+                        true,
+                        oauth2_clients,
+                        policies,
+                        flow_name,
+                        interval,
+                    )?
+                    .unwrap_or_default();
+                    let request =
+                        build_request(object, method, url, policies, flow_name, interval)?;
+
+                    Ok(send_request(
+                        request.set("Authorization", &authorization),
+                        body,
+                    ))
+                }
+                response => Ok(response),
+            }
+        }
+        None => Ok(send_request(request, body)),
+    }
+}
+
+/**
+ * Information about the call itself, added next to the status and headers of the response
+ */
+fn get_call_info(
+    object: &HashMap<String, Literal>,
+    attempts: u64,
+    interval: Interval,
+) -> HashMap<String, Literal> {
+    let mut call_info = HashMap::new();
+
+    call_info.insert(
+        "attempts".to_owned(),
+        PrimitiveInt::get_literal(attempts as i64, interval),
+    );
+    if let Some(timeout) = get_timeout(object) {
+        call_info.insert(
+            "timeout".to_owned(),
+            PrimitiveInt::get_literal(timeout.as_millis() as i64, interval),
+        );
+    }
+
+    call_info
+}
+
+fn read_response(
+    response: Response,
+    max_size: u64,
+    response_info: &HashMap<String, Literal>,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<Vec<u8>, ErrorInfo> {
+    let size_error = || {
+        set_http_error_info(
+            response_info,
+            format!("{} ({} bytes)", ERROR_HTTP_RESPONSE_SIZE, max_size),
+            flow_name,
+            interval,
+        )
+    };
+
+    let content_length = response
+        .header("Content-Length")
+        .and_then(|length| length.parse::<u64>().ok());
+    if let Some(length) = content_length {
+        if length > max_size {
+            return Err(size_error());
+        }
+    }
+
+    // one more byte than the limit is read to know if the body is too large
+    let mut bytes = vec![];
+    if let Err(err) = response
+        .into_reader()
+        .take(max_size + 1)
+        .read_to_end(&mut bytes)
+    {
+        return Err(set_http_error_info(
+            response_info,
+            format!("{}: {}", ERROR_HTTP_RESPONSE_READ, err),
+            flow_name,
+            interval,
+        ));
+    }
+
+    if bytes.len() as u64 > max_size {
+        return Err(size_error());
+    }
+
+    Ok(bytes)
+}
+
+fn send_http_request(
+    object: &HashMap<String, Literal>,
+    method: &str,
+    url: &str,
+    flow_name: &str,
+    interval: Interval,
+    is_app_call: bool,
+    oauth2_clients: &OAuth2Clients,
+    policies: &[NetworkPolicy],
+) -> Result<(serde_json::Value, HashMap<String, Literal>), ErrorInfo> {
+    // the apps endpoint is set by the engine, it is not restricted by the policies of the bots
+    let policies: &[NetworkPolicy] = match is_app_call {
+        true => &[],
+        false => policies,
+    };
+
+    let body = get_body(object, flow_name, interval)?;
+    let mut retry = get_retry(object);
+    if !retry.non_idempotent && !IDEMPOTENT_METHODS.contains(&method.to_lowercase().as_str()) {
+        retry.count = 0;
+    }
+
+    let mut request = build_request(object, method, url, policies, flow_name, interval)?;
+
     csml_logger(
         CsmlLog::new(
             None,
@@ -275,44 +788,103 @@ pub fn http_request(
         LogLvl::Debug,
     );
 
-    let response = match object.get("body") {
-        Some(body) => request.send_json(body.primitive.to_json()),
-        None => request.call(),
-    };
+    let mut attempts = 0;
+    let mut total_delay = Duration::from_millis(0);
+    let response = loop {
+        attempts += 1;
 
-    match response {
-        Ok(response) => {
-            let response_info = get_request_info(&response, interval);
+        match call(
+            request,
+            object,
+            &body,
+            (method, url),
+            oauth2_clients,
+            policies,
+            flow_name,
+            interval,
+        )? {
+            Err(err) if attempts <= retry.count => match get_retry_delay(&err, &retry, attempts) {
+                Some(delay) if total_delay + delay <= MAX_TOTAL_RETRY_DELAY => {
+                    total_delay += delay;
 
-            match response.into_string() {
-                Ok(string_value) => {
-                    match serde_json::from_str::<serde_json::Value>(&string_value) {
-                        Ok(json_value) => Ok((json_value, response_info)),
-                        Err(_) => Ok((serde_json::json!(string_value), response_info)),
-                    }
-                }
-                Err(err) => {
                     csml_logger(
                         CsmlLog::new(
                             None,
                             Some(flow_name.to_string()),
                             Some(interval.start_line),
-                            format!("Http response Json parsing failed: {:?}", err),
+                            format!(
+                                "Http call attempt {} failed, retry in {}ms",
+                                attempts,
+                                delay.as_millis()
+                            ),
                         ),
-                        LogLvl::Error,
-                    );
-                    let mut error = set_http_error_info(
-                        &response_info,
-                        ERROR_FAIL_RESPONSE_JSON.to_owned(),
-                        flow_name,
-                        interval,
+                        LogLvl::Warn,
                     );
 
-                    let error_body =
-                        "Invalid Response format, please send a json or a valid UTF-8 sequence";
-                    error.add_info("body", PrimitiveString::get_literal(error_body, interval));
-                    Err(error)
+                    thread::sleep(delay);
+                    request = build_request(object, method, url, policies, flow_name, interval)?;
                 }
+                _ => break Err(err),
+            },
+            response => break response,
+        }
+    };
+
+    let call_info = get_call_info(object, attempts, interval);
+
+    match response {
+        Ok(response) => {
+            let mut response_info = get_request_info(&response, interval);
+            response_info.extend(call_info);
+
+            let bytes = read_response(
+                response,
+                get_max_size(object),
+                &response_info,
+                flow_name,
+                interval,
+            )?;
+            response_info.insert(
+                "size".to_owned(),
+                PrimitiveInt::get_literal(bytes.len() as i64, interval),
+            );
+
+            match get_response_type(object).as_str() {
+                "binary" => Ok((serde_json::json!(base64::encode(&bytes)), response_info)),
+                "text" => Ok((
+                    serde_json::json!(String::from_utf8_lossy(&bytes)),
+                    response_info,
+                )),
+                _ => match String::from_utf8(bytes) {
+                    Ok(string_value) => {
+                        match serde_json::from_str::<serde_json::Value>(&string_value) {
+                            Ok(json_value) => Ok((json_value, response_info)),
+                            Err(_) => Ok((serde_json::json!(string_value), response_info)),
+                        }
+                    }
+                    Err(err) => {
+                        csml_logger(
+                            CsmlLog::new(
+                                None,
+                                Some(flow_name.to_string()),
+                                Some(interval.start_line),
+                                format!("Http response Json parsing failed: {:?}", err),
+                            ),
+                            LogLvl::Error,
+                        );
+                        let mut error = set_http_error_info(
+                            &response_info,
+                            ERROR_FAIL_RESPONSE_JSON.to_owned(),
+                            flow_name,
+                            interval,
+                        );
+
+                        let error_body =
+                            "Invalid Response format, please send a json or a valid UTF-8 sequence";
+                        error.add_info("body", PrimitiveString::get_literal(error_body, interval));
+                        Err(error)
+                    }
+                },
             }
         }
         Err(err) => {
@@ -339,7 +911,8 @@ pub fn http_request(
             );
 
             if let ureq::Error::Status(_, response) = err {
-                let response_info = get_request_info(&response, interval);
+                let mut response_info = get_request_info(&response, interval);
+                response_info.extend(call_info);
 
                 let mut error =
                     set_http_error_info(&response_info, error_message, flow_name, interval);
@@ -356,15 +929,67 @@ pub fn http_request(
 
                 Err(error)
             } else {
-                Err(gen_error_info(
-                    Position::new(interval, flow_name),
+                Err(set_http_error_info(
+                    &call_info,
                     error_message,
+                    flow_name,
+                    interval,
                 ))
             }
         }
     }
 }
 
+pub fn http_request(
+    object: &HashMap<String, Literal>,
+    method: &str,
+    flow_name: &str,
+    interval: Interval,
+    is_app_call: bool,
+    mock: Option<&HttpMock>,
+    oauth2_clients: &OAuth2Clients,
+    policies: &[NetworkPolicy],
+) -> Result<(serde_json::Value, HashMap<String, Literal>), ErrorInfo> {
+    let url = get_url(object, flow_name, interval)?;
+
+    match mock {
+        Some(mock) if mock.mode == HttpMockMode::Record => {
+            let result = send_http_request(
+                object,
+                method,
+                &url,
+                flow_name,
+                interval,
+                is_app_call,
+                oauth2_clients,
+                policies,
+            );
+            http_mock::record(mock, method, &url, object, &result, flow_name, interval);
+
+            result
+        }
+        Some(mock) => http_mock::get_mock_response(
+            mock,
+            method,
+            &url,
+            object,
+            is_app_call,
+            flow_name,
+            interval,
+        ),
+        None => send_http_request(
+            object,
+            method,
+            &url,
+            flow_name,
+            interval,
+            is_app_call,
+            oauth2_clients,
+            policies,
+        ),
+    }
+}
+
 pub fn http(args: ArgsType, flow_name: &str, interval: Interval) -> Result<Literal, ErrorInfo> {
     let mut http: HashMap<String, Literal> = HashMap::new();
     let mut header = HashMap::new();
@@ -0,0 +1,448 @@
+use crate::data::error_info::ErrorInfo;
+use crate::data::position::Position;
+use crate::data::primitive::{PrimitiveInt, PrimitiveObject, PrimitiveString};
+use crate::data::{ast::Interval, csml_logs::*, HttpMock, HttpMockMode, Literal};
+use crate::error_format::*;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+type HttpResult = Result<(serde_json::Value, HashMap<String, Literal>), ErrorInfo>;
+
+// files loaded once by path, with their modification time to see the edited files
+type MockCache<T> = Lazy<Mutex<HashMap<String, (Option<SystemTime>, Arc<T>)>>>;
+
+// requests running at the same time (HTTP.all) must not overwrite each other's records
+static FIXTURE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+static FIXTURES: MockCache<Fixture> = Lazy::new(|| Mutex::new(HashMap::new()));
+static STUBS: MockCache<Vec<CompiledStub>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/**
+ * Requests are matched on their method, url and body. Headers are never saved
+ * so the authorization of the calls does not end up in the fixtures.
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct MockRequest {
+    method: String,
+    url: String,
+    #[serde(default)]
+    body: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MockResponse {
+    #[serde(default = "default_status")]
+    status: u16,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    #[serde(default)]
+    body: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: MockRequest,
+    response: MockResponse,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Fixture {
+    #[serde(default)]
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Stub {
+    method: Option<String>,
+    url: String,
+    response: MockResponse,
+}
+
+#[derive(Debug, Deserialize)]
+struct StubFile {
+    #[serde(default)]
+    stubs: Vec<Stub>,
+}
+
+#[derive(Debug)]
+struct CompiledStub {
+    method: Option<String>,
+    url: Regex,
+    response: MockResponse,
+}
+
+fn default_status() -> u16 {
+    200
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+fn get_mock_request(method: &str, url: &str, object: &HashMap<String, Literal>) -> MockRequest {
+    MockRequest {
+        method: method.to_owned(),
+        url: url.to_owned(),
+        body: match object.get("body") {
+            Some(body) => body.primitive.to_json(),
+            None => serde_json::Value::Null,
+        },
+    }
+}
+
+fn read_file<T: DeserializeOwned>(
+    file: &str,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<T, ErrorInfo> {
+    let content = fs::read_to_string(file).map_err(|err| {
+        gen_error_info(
+            Position::new(interval, flow_name),
+            format!("{} [{}]: {}", ERROR_HTTP_MOCK_FILE, file, err),
+        )
+    })?;
+
+    serde_json::from_str(&content).map_err(|err| {
+        gen_error_info(
+            Position::new(interval, flow_name),
+            format!("{} [{}]: {}", ERROR_HTTP_MOCK_FILE, file, err),
+        )
+    })
+}
+
+fn get_cached<T>(
+    cache: &MockCache<T>,
+    file: &str,
+    load: impl Fn() -> Result<T, ErrorInfo>,
+) -> Result<Arc<T>, ErrorInfo> {
+    let modified = fs::metadata(file)
+        .and_then(|metadata| metadata.modified())
+        .ok();
+
+    if let Ok(cache) = cache.lock() {
+        match cache.get(file) {
+            Some((time, value)) if modified.is_some() && *time == modified => {
+                return Ok(value.clone())
+            }
+            _ => {}
+        }
+    }
+
+    let value = Arc::new(load()?);
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(file.to_owned(), (modified, value.clone()));
+    }
+
+    Ok(value)
+}
+
+/**
+ * The url of a stub is a regex of the whole url
+ */
+fn load_stubs(
+    file: &str,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<Vec<CompiledStub>, ErrorInfo> {
+    let stub_file: StubFile = read_file(file, flow_name, interval)?;
+
+    stub_file
+        .stubs
+        .into_iter()
+        .map(|stub| match Regex::new(&format!("^(?:{})$", stub.url)) {
+            Ok(url) => Ok(CompiledStub {
+                method: stub.method,
+                url,
+                response: stub.response,
+            }),
+            Err(err) => Err(gen_error_info(
+                Position::new(interval, flow_name),
+                format!("{} [{}]: {}", ERROR_HTTP_MOCK_FILE, file, err),
+            )),
+        })
+        .collect()
+}
+
+fn get_response_info(response: &MockResponse, interval: Interval) -> HashMap<String, Literal> {
+    let mut response_info = HashMap::new();
+
+    response_info.insert(
+        "status".to_owned(),
+        PrimitiveInt::get_literal(response.status as i64, interval),
+    );
+
+    let headers: HashMap<String, Literal> = response
+        .headers
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.to_owned(),
+                PrimitiveString::get_literal(value, interval),
+            )
+        })
+        .collect();
+    response_info.insert(
+        "headers".to_owned(),
+        PrimitiveObject::get_literal(&headers, interval),
+    );
+
+    response_info
+}
+
+/**
+ * Mocked responses give the same values and errors as the real calls
+ */
+fn to_http_result(
+    response: &MockResponse,
+    url: &str,
+    is_app_call: bool,
+    flow_name: &str,
+    interval: Interval,
+) -> HttpResult {
+    let response_info = get_response_info(response, interval);
+
+    if response.status < 400 {
+        return Ok((response.body.to_owned(), response_info));
+    }
+
+    let error_message = match is_app_call {
+        true => format!("Apps service: status code {}", response.status),
+        false => format!("{}: status code {}", url, response.status),
+    };
+
+    let mut error = gen_error_info(Position::new(interval, flow_name), error_message);
+    error.add_info_block(response_info);
+
+    let body = match &response.body {
+        serde_json::Value::String(body) => body.to_owned(),
+        body => body.to_string(),
+    };
+    error.add_info("body", PrimitiveString::get_literal(&body, interval));
+
+    Err(error)
+}
+
+fn unknown_request(
+    request: &MockRequest,
+    mock: &HttpMock,
+    flow_name: &str,
+    interval: Interval,
+) -> ErrorInfo {
+    gen_error_info(
+        Position::new(interval, flow_name),
+        format!(
+            "{} [{} {}] in {}",
+            ERROR_HTTP_MOCK_UNKNOWN,
+            request.method.to_uppercase(),
+            request.url,
+            mock.file
+        ),
+    )
+}
+
+fn replay(
+    mock: &HttpMock,
+    request: &MockRequest,
+    is_app_call: bool,
+    flow_name: &str,
+    interval: Interval,
+) -> HttpResult {
+    let fixture = get_cached(&FIXTURES, &mock.file, || {
+        read_file(&mock.file, flow_name, interval)
+    })?;
+
+    match fixture
+        .interactions
+        .iter()
+        .find(|interaction| interaction.request == *request)
+    {
+        Some(interaction) => to_http_result(
+            &interaction.response,
+            &request.url,
+            is_app_call,
+            flow_name,
+            interval,
+        ),
+        None => Err(unknown_request(request, mock, flow_name, interval)),
+    }
+}
+
+/**
+ * Stubs are tried in the order of the file, their method is optional
+ */
+fn stub(
+    mock: &HttpMock,
+    request: &MockRequest,
+    is_app_call: bool,
+    flow_name: &str,
+    interval: Interval,
+) -> HttpResult {
+    let stubs = get_cached(&STUBS, &mock.file, || {
+        load_stubs(&mock.file, flow_name, interval)
+    })?;
+
+    for stub in stubs.iter() {
+        if let Some(method) = &stub.method {
+            if !method.eq_ignore_ascii_case(&request.method) {
+                continue;
+            }
+        }
+
+        if stub.url.is_match(&request.url) {
+            return to_http_result(
+                &stub.response,
+                &request.url,
+                is_app_call,
+                flow_name,
+                interval,
+            );
+        }
+    }
+
+    Err(unknown_request(request, mock, flow_name, interval))
+}
+
+/**
+ * Only the calls that got a response are recorded, transport errors are not
+ */
+fn get_recorded_response(result: &HttpResult) -> Option<MockResponse> {
+    let (response_info, body) = match result {
+        Ok((body, response_info)) => (response_info, body.to_owned()),
+        Err(err) => {
+            let response_info = err.additional_info.as_ref()?;
+            let body = match response_info.get("body") {
+                Some(body) => {
+                    let body = body.primitive.to_string();
+                    serde_json::from_str(&body).unwrap_or(serde_json::Value::String(body))
+                }
+                None => serde_json::Value::Null,
+            };
+
+            (response_info, body)
+        }
+    };
+
+    let status = response_info.get("status")?.primitive.to_json().as_u64()? as u16;
+    let headers = match response_info.get("headers") {
+        Some(headers) => match headers.primitive.to_json() {
+            serde_json::Value::Object(headers) => headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    value
+                        .as_str()
+                        .map(|value| (name.to_owned(), value.to_owned()))
+                })
+                .collect(),
+            _ => HashMap::new(),
+        },
+        None => HashMap::new(),
+    };
+
+    Some(MockResponse {
+        status,
+        headers,
+        body,
+    })
+}
+
+fn write_fixture(mock: &HttpMock, interaction: Interaction) -> Result<(), String> {
+    let _lock = FIXTURE_LOCK.lock().map_err(|err| err.to_string())?;
+
+    let mut fixture = match fs::read_to_string(&mock.file) {
+        Ok(content) => serde_json::from_str::<Fixture>(&content).map_err(|err| err.to_string())?,
+        Err(_) => Fixture::default(),
+    };
+
+    // a request recorded again replaces its previous response
+    match fixture
+        .interactions
+        .iter_mut()
+        .find(|recorded| recorded.request == interaction.request)
+    {
+        Some(recorded) => recorded.response = interaction.response,
+        None => fixture.interactions.push(interaction),
+    }
+
+    let content = serde_json::to_string_pretty(&fixture).map_err(|err| err.to_string())?;
+    fs::write(&mock.file, content).map_err(|err| err.to_string())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PUBLIC FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+/**
+ * Response of a call in replay or stub mode, no request is sent
+ */
+pub fn get_mock_response(
+    mock: &HttpMock,
+    method: &str,
+    url: &str,
+    object: &HashMap<String, Literal>,
+    is_app_call: bool,
+    flow_name: &str,
+    interval: Interval,
+) -> HttpResult {
+    let request = get_mock_request(method, url, object);
+
+    csml_logger(
+        CsmlLog::new(
+            None,
+            Some(flow_name.to_string()),
+            Some(interval.start_line),
+            format!("Mock Http call {} {}", method.to_uppercase(), url),
+        ),
+        LogLvl::Info,
+    );
+
+    match mock.mode {
+        HttpMockMode::Stub => stub(mock, &request, is_app_call, flow_name, interval),
+        _ => replay(mock, &request, is_app_call, flow_name, interval),
+    }
+}
+
+/**
+ * Saves the response of a call in record mode, a fixture that can not be written
+ * is logged but does not change the result of the call
+ */
+pub fn record(
+    mock: &HttpMock,
+    method: &str,
+    url: &str,
+    object: &HashMap<String, Literal>,
+    result: &HttpResult,
+    flow_name: &str,
+    interval: Interval,
+) {
+    let response = match get_recorded_response(result) {
+        Some(response) => response,
+        None => return,
+    };
+
+    let interaction = Interaction {
+        request: get_mock_request(method, url, object),
+        response,
+    };
+
+    if let Err(err) = write_fixture(mock, interaction) {
+        csml_logger(
+            CsmlLog::new(
+                None,
+                Some(flow_name.to_string()),
+                Some(interval.start_line),
+                format!("{} [{}]: {}", ERROR_HTTP_MOCK_RECORD, mock.file, err),
+            ),
+            LogLvl::Error,
+        );
+    }
+}
@@ -0,0 +1,374 @@
+use crate::data::error_info::ErrorInfo;
+use crate::data::position::Position;
+use crate::data::primitive::{
+    PrimitiveArray, PrimitiveBoolean, PrimitiveInt, PrimitiveObject, PrimitiveString, PrimitiveType,
+};
+use crate::data::{ast::Interval, ArgsType, Data, Literal};
+use crate::error_format::*;
+use crate::interpreter::{builtins::http_builtin::http_request, json_to_literal};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+////////////////////////////////////////////////////////////////////////////////
+/// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+// keys of the LLM configuration in the bot env
+const LLM_URL: &str = "LLM_URL";
+const LLM_API_KEY: &str = "LLM_API_KEY";
+const LLM_MODEL: &str = "LLM_MODEL";
+const LLM_PROVIDER: &str = "LLM_PROVIDER";
+const LLM_MOCK_RESPONSE: &str = "LLM_MOCK_RESPONSE";
+
+const DEFAULT_URL: &str = "https://api.openai.com/v1";
+const DEFAULT_MODEL: &str = "gpt-4o-mini";
+const CHAT_COMPLETIONS: &str = "/chat/completions";
+
+// options that can be given as named arguments: LLM(prompt, system = "...", temperature = 0.2)
+const LLM_OPTIONS: &[&str] = &[
+    "system",
+    "temperature",
+    "model",
+    "json",
+    "max_tokens",
+    "timeout",
+    "disable_ssl_verify",
+];
+
+////////////////////////////////////////////////////////////////////////////////
+/// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+fn get_env(data: &Data, key: &str) -> Option<String> {
+    data.env
+        .primitive
+        .as_any()
+        .downcast_ref::<PrimitiveObject>()
+        .and_then(|env| env.value.get(key))
+        .map(|value| value.primitive.to_string())
+}
+
+fn gen_message(role: &str, content: &str, interval: Interval) -> Literal {
+    let mut message = HashMap::new();
+    message.insert(
+        "role".to_owned(),
+        PrimitiveString::get_literal(role, interval),
+    );
+    message.insert(
+        "content".to_owned(),
+        PrimitiveString::get_literal(content, interval),
+    );
+
+    PrimitiveObject::get_literal(&message, interval)
+}
+
+/**
+ * A prompt is sent as a single user message, messages must be objects
+ * with a role and a content: [{"role": "user", "content": "Hello"}]
+ */
+fn get_messages(
+    prompt: &Literal,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<Literal, ErrorInfo> {
+    match prompt.primitive.get_type() {
+        PrimitiveType::PrimitiveString => Ok(PrimitiveArray::get_literal(
+            &[gen_message("user", &prompt.primitive.to_string(), interval)],
+            interval,
+        )),
+        PrimitiveType::PrimitiveArray => {
+            let is_valid = prompt
+                .primitive
+                .to_json()
+                .as_array()
+                .map_or(false, |messages| {
+                    messages.iter().all(|message| {
+                        message["role"].is_string() && message["content"].is_string()
+                    })
+                });
+
+            match is_valid {
+                true => Ok(prompt.to_owned()),
+                false => Err(gen_error_info(
+                    Position::new(interval, flow_name),
+                    ERROR_LLM_MESSAGES.to_owned(),
+                )),
+            }
+        }
+        _ => Err(gen_error_info(
+            Position::new(interval, flow_name),
+            ERROR_LLM.to_owned(),
+        )),
+    }
+}
+
+fn get_endpoint(data: &Data) -> String {
+    let url = get_env(data, LLM_URL).unwrap_or_else(|| DEFAULT_URL.to_owned());
+    let url = url.trim_end_matches('/');
+
+    match url.ends_with(CHAT_COMPLETIONS) {
+        true => url.to_owned(),
+        false => format!("{}{}", url, CHAT_COMPLETIONS),
+    }
+}
+
+fn gen_body(object: &HashMap<String, Literal>, data: &Data) -> Value {
+    let mut messages = vec![];
+
+    if let Some(system) = object.get("system") {
+        messages.push(json!({"role": "system", "content": system.primitive.to_string()}));
+    }
+    if let Some(Value::Array(prompt)) = object.get("messages").map(|lit| lit.primitive.to_json()) {
+        messages.extend(prompt);
+    }
+
+    let model = match object.get("model") {
+        Some(model) => model.primitive.to_string(),
+        None => get_env(data, LLM_MODEL).unwrap_or_else(|| DEFAULT_MODEL.to_owned()),
+    };
+
+    let mut body = Map::new();
+    body.insert("model".to_owned(), json!(model));
+    body.insert("messages".to_owned(), Value::Array(messages));
+
+    for key in ["temperature", "max_tokens"].iter() {
+        if let Some(value) = object.get(*key) {
+            body.insert(key.to_string(), value.primitive.to_json());
+        }
+    }
+    if is_json_mode(object) {
+        body.insert("response_format".to_owned(), json!({"type": "json_object"}));
+    }
+
+    Value::Object(body)
+}
+
+fn is_json_mode(object: &HashMap<String, Literal>) -> bool {
+    match object.get("json") {
+        Some(json) if json.primitive.get_type() == PrimitiveType::PrimitiveBoolean => {
+            json.primitive.as_bool()
+        }
+        _ => false,
+    }
+}
+
+fn count_tokens(text: &str) -> usize {
+    text.split_whitespace().count()
+}
+
+/**
+ * The mock provider answers without any network call: the response is LLM_MOCK_RESPONSE
+ * if set in the bot env, else the content of the last message is echoed.
+ */
+fn mock_completion(body: &Value, data: &Data) -> Value {
+    let messages = body["messages"].as_array().cloned().unwrap_or_default();
+
+    let content = match get_env(data, LLM_MOCK_RESPONSE) {
+        Some(response) => response,
+        None => messages
+            .last()
+            .and_then(|message| message["content"].as_str())
+            .unwrap_or_default()
+            .to_owned(),
+    };
+
+    let prompt_tokens: usize = messages
+        .iter()
+        .filter_map(|message| message["content"].as_str())
+        .map(count_tokens)
+        .sum();
+    let completion_tokens = count_tokens(&content);
+
+    json!({
+        "model": body["model"],
+        "choices": [{
+            "message": {"role": "assistant", "content": content},
+            "finish_reason": "stop"
+        }],
+        "usage": {
+            "prompt_tokens": prompt_tokens,
+            "completion_tokens": completion_tokens,
+            "total_tokens": prompt_tokens + completion_tokens
+        }
+    })
+}
+
+/**
+ * The completion is sent with the HTTP builtin so that it follows the same SSL and timeout options.
+ */
+fn http_completion(
+    object: &HashMap<String, Literal>,
+    body: &Value,
+    data: &Data,
+    interval: Interval,
+) -> Result<(Value, HashMap<String, Literal>), ErrorInfo> {
+    let mut header = HashMap::new();
+    header.insert(
+        "Content-Type".to_owned(),
+        PrimitiveString::get_literal("application/json", interval),
+    );
+    header.insert(
+        "Accept".to_owned(),
+        PrimitiveString::get_literal("application/json", interval),
+    );
+    header.insert(
+        "User-Agent".to_owned(),
+        PrimitiveString::get_literal("csml/v1", interval),
+    );
+    if let Some(api_key) = get_env(data, LLM_API_KEY) {
+        header.insert(
+            "Authorization".to_owned(),
+            PrimitiveString::get_literal(&format!("Bearer {}", api_key), interval),
+        );
+    }
+
+    let mut http = HashMap::new();
+    http.insert(
+        "url".to_owned(),
+        PrimitiveString::get_literal(&get_endpoint(data), interval),
+    );
+    http.insert(
+        "header".to_owned(),
+        PrimitiveObject::get_literal(&header, interval),
+    );
+    http.insert(
+        "body".to_owned(),
+        json_to_literal(body, interval, &data.context.flow)?,
+    );
+    for key in ["timeout", "disable_ssl_verify"].iter() {
+        if let Some(value) = object.get(*key) {
+            http.insert(key.to_string(), value.to_owned());
+        }
+    }
+
+    http_request(
+        &http,
+        "post",
+        &data.context.flow,
+        interval,
+        false,
+        data.event.http_mock.as_ref(),
+        &data.context.oauth2_clients,
+        data.network_policies,
+    )
+}
+
+fn get_usage(response: &Value, interval: Interval) -> Literal {
+    let mut usage = HashMap::new();
+
+    for key in ["prompt_tokens", "completion_tokens", "total_tokens"].iter() {
+        let value = response["usage"][*key].as_i64().unwrap_or(0);
+        usage.insert(key.to_string(), PrimitiveInt::get_literal(value, interval));
+    }
+
+    PrimitiveObject::get_literal(&usage, interval)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+/// PUBLIC FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+pub fn llm(args: ArgsType, flow_name: &str, interval: Interval) -> Result<Literal, ErrorInfo> {
+    let prompt = match args.get("messages", 0) {
+        Some(prompt) => prompt,
+        None => {
+            return Err(gen_error_info(
+                Position::new(interval, flow_name),
+                ERROR_LLM.to_owned(),
+            ))
+        }
+    };
+
+    let mut llm: HashMap<String, Literal> = HashMap::new();
+    llm.insert(
+        "messages".to_owned(),
+        get_messages(prompt, flow_name, interval)?,
+    );
+
+    if let ArgsType::Named(named) = &args {
+        for (key, value) in named.iter() {
+            if LLM_OPTIONS.contains(&key.as_str()) {
+                llm.insert(key.to_owned(), value.to_owned());
+            }
+        }
+    }
+    if let Some(json) = llm.get("json") {
+        if json.primitive.get_type() != PrimitiveType::PrimitiveBoolean {
+            llm.insert(
+                "json".to_owned(),
+                PrimitiveBoolean::get_literal(json.primitive.as_bool(), interval),
+            );
+        }
+    }
+
+    let mut result = PrimitiveObject::get_literal(&llm, interval);
+    result.set_content_type("llm");
+
+    Ok(result)
+}
+
+/**
+ * Send the completion request and return the content of the first choice,
+ * parsed as a literal in JSON mode. The token usage, model and finish reason are
+ * added to the additional information along with the HTTP status and headers.
+ */
+pub fn llm_request(
+    object: &HashMap<String, Literal>,
+    data: &Data,
+    interval: Interval,
+) -> Result<Literal, ErrorInfo> {
+    let flow_name = &data.context.flow;
+    let body = gen_body(object, data);
+
+    let (response, mut response_info) = match get_env(data, LLM_PROVIDER) {
+        Some(provider) if provider.to_lowercase() == "mock" => {
+            (mock_completion(&body, data), HashMap::new())
+        }
+        _ => http_completion(object, &body, data, interval)?,
+    };
+
+    let choice = &response["choices"][0];
+    let content = match choice["message"]["content"].as_str() {
+        Some(content) => content,
+        None => {
+            let mut error = gen_error_info(
+                Position::new(interval, flow_name),
+                ERROR_LLM_RESPONSE.to_owned(),
+            );
+            error.add_info_block(response_info);
+            return Err(error);
+        }
+    };
+
+    let mut literal = match is_json_mode(object) {
+        true => match serde_json::from_str::<Value>(content) {
+            Ok(value) => json_to_literal(&value, interval, flow_name)?,
+            Err(_) => {
+                let mut error = gen_error_info(
+                    Position::new(interval, flow_name),
+                    ERROR_LLM_JSON.to_owned(),
+                );
+                error.add_info("content", PrimitiveString::get_literal(content, interval));
+                return Err(error);
+            }
+        },
+        false => PrimitiveString::get_literal(content, interval),
+    };
+
+    response_info.insert("usage".to_owned(), get_usage(&response, interval));
+    if let Some(model) = response["model"].as_str() {
+        response_info.insert(
+            "model".to_owned(),
+            PrimitiveString::get_literal(model, interval),
+        );
+    }
+    if let Some(finish_reason) = choice["finish_reason"].as_str() {
+        response_info.insert(
+            "finish_reason".to_owned(),
+            PrimitiveString::get_literal(finish_reason, interval),
+        );
+    }
+    literal.add_info_block(response_info);
+
+    Ok(literal)
+}
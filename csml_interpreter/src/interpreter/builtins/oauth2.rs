@@ -0,0 +1,377 @@
+use crate::data::error_info::ErrorInfo;
+use crate::data::position::Position;
+use crate::data::primitive::PrimitiveType;
+use crate::data::{ast::Interval, csml_logs::*, Literal, NetworkPolicy};
+use crate::error_format::*;
+use crate::interpreter::builtins::http_builtin::{get_http_request, get_ssl_state, get_timeout};
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+// tokens are renewed a bit before their expiry so they do not expire during the request
+const TOKEN_EXPIRY_MARGIN: i64 = 30;
+// lifetime of the tokens when the authorization server does not give one
+const DEFAULT_TOKEN_LIFETIME: i64 = 3600;
+// clients kept for a conversation, the ones without a valid token are evicted first
+const MAX_OAUTH2_CLIENTS: usize = 32;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OAuth2Token {
+    pub access_token: String,
+    // unix timestamp in seconds
+    pub expires_at: i64,
+}
+
+/**
+ * OAuth2 client credentials are kept out of the HTTP object, so the client secret
+ * can not be displayed or saved by the flow. The HTTP object only has the id of the client.
+ */
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OAuth2Client {
+    pub token_url: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub scope: Option<String>,
+    pub token: Option<OAuth2Token>,
+}
+
+#[derive(Default)]
+struct OAuth2Cache {
+    clients: HashMap<String, OAuth2Client>,
+    // clients registered or with a new token, the engine saves them in the bot state
+    updated: HashSet<String>,
+}
+
+// finds a client saved by a previous request, the engine reads it from the state of the bot
+pub type OAuth2Loader = Arc<dyn Fn(&str) -> Option<OAuth2Client> + Send + Sync>;
+
+/**
+ * OAuth2 clients of the conversation. The clients that are not in the conversation yet
+ * are looked up with the loader, and the engine saves the updated ones after each step,
+ * so the tokens are shared by the requests of a bot until they expire.
+ */
+#[derive(Clone, Default)]
+pub struct OAuth2Clients {
+    cache: Arc<Mutex<OAuth2Cache>>,
+    loader: Option<OAuth2Loader>,
+}
+
+// the credentials are never displayed
+impl fmt::Debug for OAuth2Clients {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ids: Vec<String> = match self.cache.lock() {
+            Ok(cache) => cache.clients.keys().cloned().collect(),
+            Err(_) => vec![],
+        };
+
+        f.debug_tuple("OAuth2Clients").field(&ids).finish()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+fn now() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+fn get_client_id(object: &HashMap<String, Literal>) -> Option<String> {
+    match object.get("oauth2") {
+        Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveObject => {
+            lit.primitive.to_json()["client"]
+                .as_str()
+                .map(|id| id.to_owned())
+        }
+        _ => None,
+    }
+}
+
+impl OAuth2Client {
+    fn get_valid_token(&self) -> Option<String> {
+        match &self.token {
+            Some(token) if now() + TOKEN_EXPIRY_MARGIN < token.expires_at => {
+                Some(token.access_token.to_owned())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl OAuth2Cache {
+    fn evict(&mut self) {
+        while self.clients.len() >= MAX_OAUTH2_CLIENTS {
+            let id = self
+                .clients
+                .iter()
+                .min_by_key(|(_, client)| match &client.token {
+                    Some(token) => token.expires_at,
+                    None => i64::MIN,
+                })
+                .map(|(id, _)| id.to_owned());
+
+            match id {
+                Some(id) => {
+                    self.clients.remove(&id);
+                    self.updated.remove(&id);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// METHOD FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+impl OAuth2Clients {
+    pub fn with_loader(
+        loader: impl Fn(&str) -> Option<OAuth2Client> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            cache: Arc::default(),
+            loader: Some(Arc::new(loader)),
+        }
+    }
+
+    /**
+     * Clients registered or with a new token since the last call
+     */
+    pub fn take_updated(&self) -> Vec<(String, OAuth2Client)> {
+        match self.cache.lock() {
+            Ok(mut cache) => {
+                let updated = std::mem::take(&mut cache.updated);
+
+                updated
+                    .into_iter()
+                    .filter_map(|id| {
+                        let client = cache.clients.get(&id)?.to_owned();
+
+                        Some((id, client))
+                    })
+                    .collect()
+            }
+            Err(_) => vec![],
+        }
+    }
+
+    /**
+     * The loader is called without the lock, so a slow lookup does not block the
+     * requests of the other clients.
+     */
+    fn load(&self, id: &str) -> Option<OAuth2Client> {
+        let client = (self.loader.as_ref()?)(id)?;
+
+        let mut cache = self.cache.lock().ok()?;
+        if !cache.clients.contains_key(id) {
+            cache.evict();
+            cache.clients.insert(id.to_owned(), client);
+        }
+
+        cache.clients.get(id).cloned()
+    }
+
+    fn register(&self, id: &str, client: OAuth2Client) {
+        if self.get(id).is_some() {
+            return;
+        }
+
+        if let Ok(mut cache) = self.cache.lock() {
+            if !cache.clients.contains_key(id) {
+                cache.evict();
+                cache.clients.insert(id.to_owned(), client);
+                cache.updated.insert(id.to_owned());
+            }
+        }
+    }
+
+    fn get(&self, id: &str) -> Option<OAuth2Client> {
+        let client = self.cache.lock().ok()?.clients.get(id).cloned();
+
+        client.or_else(|| self.load(id))
+    }
+
+    fn set_token(&self, id: &str, token: OAuth2Token) {
+        if let Ok(mut cache) = self.cache.lock() {
+            if let Some(client) = cache.clients.get_mut(id) {
+                client.token = Some(token);
+                cache.updated.insert(id.to_owned());
+            }
+        }
+    }
+}
+
+/**
+ * Client credentials grant (RFC 6749 section 4.4), the client authenticates with HTTP Basic.
+ */
+fn fetch_token(
+    id: &str,
+    object: &HashMap<String, Literal>,
+    clients: &OAuth2Clients,
+    policies: &[NetworkPolicy],
+    flow_name: &str,
+    interval: Interval,
+) -> Result<String, ErrorInfo> {
+    let client = match clients.get(id) {
+        Some(client) => client,
+        None => {
+            return Err(gen_error_info(
+                Position::new(interval, flow_name),
+                ERROR_HTTP_OAUTH2_CLIENT.to_owned(),
+            ))
+        }
+    };
+
+    let mut request = get_http_request(
+        "post",
+        &client.token_url,
+        flow_name,
+        interval,
+        get_ssl_state(object),
+        policies,
+    )?;
+    if let Some(timeout) = get_timeout(object) {
+        request = request.timeout(timeout);
+    }
+
+    let credentials = format!("{}:{}", client.client_id, client.client_secret);
+    let request = request
+        .set(
+            "Authorization",
+            &format!("Basic {}", base64::encode(credentials.as_bytes())),
+        )
+        .set("Accept", "application/json");
+
+    let mut form = vec![("grant_type", "client_credentials")];
+    if let Some(scope) = &client.scope {
+        form.push(("scope", scope.as_str()));
+    }
+
+    csml_logger(
+        CsmlLog::new(
+            None,
+            Some(flow_name.to_string()),
+            Some(interval.start_line),
+            format!("Request OAuth2 token from {}", client.token_url),
+        ),
+        LogLvl::Info,
+    );
+
+    let response: serde_json::Value = match request.send_form(&form) {
+        Ok(response) => response.into_json().unwrap_or_default(),
+        Err(err) => {
+            // the error of the authorization server is logged without the request
+            let error = match err {
+                ureq::Error::Status(code, _) => format!("status code {}", code),
+                ureq::Error::Transport(transport) => transport.kind().to_string(),
+            };
+
+            return Err(gen_error_info(
+                Position::new(interval, flow_name),
+                format!(
+                    "{} [{}]: {}",
+                    ERROR_HTTP_OAUTH2_TOKEN, client.token_url, error
+                ),
+            ));
+        }
+    };
+
+    let token = match response["access_token"].as_str() {
+        Some(token) => token.to_owned(),
+        None => {
+            return Err(gen_error_info(
+                Position::new(interval, flow_name),
+                format!(
+                    "{} [{}]: missing access_token",
+                    ERROR_HTTP_OAUTH2_TOKEN, client.token_url
+                ),
+            ))
+        }
+    };
+    let lifetime = response["expires_in"]
+        .as_i64()
+        .unwrap_or(DEFAULT_TOKEN_LIFETIME);
+
+    clients.set_token(
+        id,
+        OAuth2Token {
+            access_token: token.to_owned(),
+            expires_at: now().saturating_add(lifetime),
+        },
+    );
+
+    Ok(token)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PUBLIC FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+/**
+ * Clients are identified by a hash of the bot and of the credentials, so each bot
+ * has its own token cache and changing a credential gives a new client.
+ */
+pub fn register_client(
+    clients: &OAuth2Clients,
+    bot_id: Option<&str>,
+    token_url: &str,
+    client_id: &str,
+    client_secret: &str,
+    scope: Option<&str>,
+) -> String {
+    let key = [
+        bot_id.unwrap_or_default(),
+        token_url,
+        client_id,
+        client_secret,
+        scope.unwrap_or_default(),
+    ]
+    .join("\0");
+
+    let id = hex::encode(openssl::sha::sha256(key.as_bytes()));
+
+    clients.register(
+        &id,
+        OAuth2Client {
+            token_url: token_url.to_owned(),
+            client_id: client_id.to_owned(),
+            client_secret: client_secret.to_owned(),
+            scope: scope.map(|scope| scope.to_owned()),
+            token: None,
+        },
+    );
+
+    id
+}
+
+/**
+ * Bearer authorization of the HTTP object if it has an OAuth2 client, the cached token
+ * is used until it expires unless `refresh` is set.
+ */
+pub fn get_authorization(
+    object: &HashMap<String, Literal>,
+    refresh: bool,
+    clients: &OAuth2Clients,
+    policies: &[NetworkPolicy],
+    flow_name: &str,
+    interval: Interval,
+) -> Result<Option<String>, ErrorInfo> {
+    let id = match get_client_id(object) {
+        Some(id) => id,
+        None => return Ok(None),
+    };
+
+    let token = match clients.get(&id).and_then(|client| client.get_valid_token()) {
+        Some(token) if !refresh => token,
+        _ => fetch_token(&id, object, clients, policies, flow_name, interval)?,
+    };
+
+    Ok(Some(format!("Bearer {}", token)))
+}
@@ -0,0 +1,303 @@
+use crate::data::error_info::ErrorInfo;
+use crate::data::position::Position;
+use crate::data::primitive::{PrimitiveArray, PrimitiveNull, PrimitiveString, PrimitiveType};
+use crate::data::{ast::Interval, ArgsType, Data, Literal, MessageData, MSG};
+use crate::error_format::*;
+use crate::interpreter::{
+    builtins::{
+        api::{get_api_request, get_api_response},
+        http_builtin::{get_method, http_request},
+    },
+    json_to_rust::json_to_literal,
+};
+
+use std::collections::HashMap;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+const DEFAULT_CONCURRENCY: usize = 4;
+const MAX_CONCURRENCY: usize = 16;
+
+type HttpResult = Result<(serde_json::Value, HashMap<String, Literal>), ErrorInfo>;
+
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+fn get_requests<'a>(
+    args: &'a ArgsType,
+    usage: &str,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<&'a Vec<Literal>, ErrorInfo> {
+    match args.get("requests", 0) {
+        Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveArray => {
+            Literal::get_value::<Vec<Literal>>(
+                &lit.primitive,
+                flow_name,
+                interval,
+                format!("usage: {}", usage),
+            )
+        }
+        _ => Err(gen_error_info(
+            Position::new(interval, flow_name),
+            format!("usage: {}", usage),
+        )),
+    }
+}
+
+fn get_concurrency(
+    args: &ArgsType,
+    usage: &str,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<usize, ErrorInfo> {
+    let concurrency = match args {
+        ArgsType::Named(map) => map.get("concurrency").or_else(|| map.get("arg1")),
+        ArgsType::Normal(map) => map.get("arg1"),
+    };
+
+    match concurrency {
+        Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveInt => {
+            match lit.primitive.to_string().parse::<usize>() {
+                Ok(concurrency) if (1..=MAX_CONCURRENCY).contains(&concurrency) => Ok(concurrency),
+                _ => Err(gen_error_info(
+                    Position::new(interval, flow_name),
+                    format!("{} [{}]", ERROR_PARALLEL_CONCURRENCY, MAX_CONCURRENCY),
+                )),
+            }
+        }
+        Some(_) => Err(gen_error_info(
+            Position::new(interval, flow_name),
+            format!("usage: {}", usage),
+        )),
+        None => Ok(DEFAULT_CONCURRENCY),
+    }
+}
+
+/**
+ * Runs the jobs on at most `concurrency` threads, the results are in the order of the jobs.
+ * The result of a job is None only if its thread panicked.
+ */
+fn run_all<T, R, F>(jobs: Vec<T>, concurrency: usize, run: F) -> Vec<Option<R>>
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> R + Send + Sync + 'static,
+{
+    let size = jobs.len();
+    let jobs = Arc::new(Mutex::new(jobs.into_iter().enumerate()));
+    let run = Arc::new(run);
+    let (sender, receiver) = mpsc::channel();
+
+    let workers: Vec<_> = (0..concurrency.min(size))
+        .map(|_| {
+            let jobs = Arc::clone(&jobs);
+            let run = Arc::clone(&run);
+            let sender = sender.clone();
+
+            thread::spawn(move || loop {
+                let job = match jobs.lock() {
+                    Ok(mut jobs) => jobs.next(),
+                    Err(_) => None,
+                };
+
+                match job {
+                    Some((index, job)) => {
+                        if sender.send((index, (*run)(job))).is_err() {
+                            break;
+                        }
+                    }
+                    None => break,
+                }
+            })
+        })
+        .collect();
+    drop(sender);
+
+    let mut results: Vec<Option<R>> = (0..size).map(|_| None).collect();
+    for (index, result) in receiver {
+        results[index] = Some(result);
+    }
+
+    for worker in workers {
+        worker.join().ok();
+    }
+
+    results
+}
+
+/**
+ * A failed request does not stop the others, it gives a null value with the error
+ * and the status of the response in its additional info
+ */
+fn error_to_literal(err: ErrorInfo, interval: Interval) -> Literal {
+    let mut literal = PrimitiveNull::get_literal(interval);
+
+    literal.additional_info = err.additional_info;
+    literal.add_info(
+        "error",
+        PrimitiveString::get_literal(&err.message, interval),
+    );
+
+    literal
+}
+
+fn exec_http_requests(
+    requests: Vec<HashMap<String, Literal>>,
+    methods: Vec<&'static str>,
+    concurrency: usize,
+    is_app_call: bool,
+    data: &Data,
+    interval: Interval,
+) -> Vec<HttpResult> {
+    let jobs = requests.into_iter().zip(methods).collect();
+    let flow = data.context.flow.to_owned();
+    let mock = data.event.http_mock.to_owned();
+    let oauth2_clients = data.context.oauth2_clients.clone();
+    let policies = data.network_policies.to_vec();
+
+    run_all(jobs, concurrency, move |(request, method)| {
+        http_request(
+            &request,
+            method,
+            &flow,
+            interval,
+            is_app_call,
+            mock.as_ref(),
+            &oauth2_clients,
+            &policies,
+        )
+    })
+    .into_iter()
+    .map(|result| match result {
+        Some(result) => result,
+        None => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            ERROR_PARALLEL_REQUEST.to_owned(),
+        )),
+    })
+    .collect()
+}
+
+/**
+ * The requests of App.all are objects with the name of the App in 'fn_id' and its
+ * arguments in the other keys, or just the name of the App
+ */
+fn get_app_args(
+    request: &Literal,
+    usage: &str,
+    flow_name: &str,
+    interval: Interval,
+) -> Result<ArgsType, ErrorInfo> {
+    match request.primitive.get_type() {
+        PrimitiveType::PrimitiveString => {
+            let mut args = HashMap::new();
+            args.insert("fn_id".to_owned(), request.to_owned());
+
+            Ok(ArgsType::Named(args))
+        }
+        PrimitiveType::PrimitiveObject => {
+            let args = Literal::get_value::<HashMap<String, Literal>>(
+                &request.primitive,
+                flow_name,
+                interval,
+                format!("usage: {}", usage),
+            )?;
+
+            Ok(ArgsType::Named(args.to_owned()))
+        }
+        _ => Err(gen_error_info(
+            Position::new(interval, flow_name),
+            format!("usage: {}", usage),
+        )),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PUBLIC FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+pub fn http_all(args: &ArgsType, data: &Data, interval: Interval) -> Result<Literal, ErrorInfo> {
+    let usage = "HTTP.all(requests: Array<HTTP>, concurrency: Int = 4) => Array";
+    let flow_name = &data.context.flow;
+
+    let concurrency = get_concurrency(args, usage, flow_name, interval)?;
+
+    let mut requests = vec![];
+    let mut methods = vec![];
+    for request in get_requests(args, usage, flow_name, interval)? {
+        if request.content_type != "http" {
+            return Err(gen_error_info(
+                Position::new(interval, flow_name),
+                ERROR_PARALLEL_HTTP.to_owned(),
+            ));
+        }
+
+        let request = Literal::get_value::<HashMap<String, Literal>>(
+            &request.primitive,
+            flow_name,
+            interval,
+            ERROR_PARALLEL_HTTP.to_owned(),
+        )?;
+
+        methods.push(get_method(request, flow_name, interval)?);
+        requests.push(request.to_owned());
+    }
+
+    let mut results = vec![];
+    for result in exec_http_requests(requests, methods, concurrency, false, data, interval) {
+        let literal = match result {
+            Ok((value, response_info)) => {
+                let mut literal = json_to_literal(&value, interval, flow_name)?;
+                // add additional information about the http request response: status and headers
+                literal.add_info_block(response_info);
+
+                literal
+            }
+            Err(err) => error_to_literal(err, interval),
+        };
+
+        results.push(literal);
+    }
+
+    Ok(PrimitiveArray::get_literal(&results, interval))
+}
+
+pub fn app_all(
+    args: &ArgsType,
+    data: &mut Data,
+    interval: Interval,
+    msg_data: &mut MessageData,
+    sender: &Option<mpsc::Sender<MSG>>,
+) -> Result<Literal, ErrorInfo> {
+    let usage = "App.all(requests: Array<String | Object>, concurrency: Int = 4) => Array";
+    let flow_name = data.context.flow.to_owned();
+
+    let concurrency = get_concurrency(args, usage, &flow_name, interval)?;
+
+    let mut requests = vec![];
+    for request in get_requests(args, usage, &flow_name, interval)? {
+        let app_args = get_app_args(request, usage, &flow_name, interval)?;
+
+        requests.push(get_api_request(&app_args, interval, data)?);
+    }
+    let methods = vec!["post"; requests.len()];
+
+    let mut results = vec![];
+    for result in exec_http_requests(requests, methods, concurrency, true, data, interval) {
+        // the responses of the Apps are interpolated one by one in the interpreter thread
+        let literal = match get_api_response(result, interval, data, msg_data, sender) {
+            Ok(literal) => literal,
+            Err(err) => error_to_literal(err, interval),
+        };
+
+        results.push(literal);
+    }
+
+    Ok(PrimitiveArray::get_literal(&results, interval))
+}
@@ -0,0 +1,670 @@
+use crate::data::error_info::ErrorInfo;
+use crate::data::position::Position;
+use crate::data::primitive::{PrimitiveString, PrimitiveType};
+use crate::data::{ast::Interval, ArgsType, BotFiles, Data, Literal};
+use crate::error_format::*;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+// partials can include other partials, this limit stops recursive partials
+const MAX_PARTIAL_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Escape {
+    Html,
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SectionKind {
+    Section,
+    Inverted,
+    If,
+    Unless,
+    Each,
+    With,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TagKind {
+    Variable,
+    Raw,
+    Open(SectionKind),
+    Close,
+    Else,
+    Partial,
+    Comment,
+}
+
+enum Token {
+    Text(String),
+    Tag(TagKind, String),
+}
+
+enum Node {
+    Text(String),
+    Variable {
+        name: String,
+        raw: bool,
+    },
+    Section {
+        kind: SectionKind,
+        name: String,
+        children: Vec<Node>,
+        inverse: Vec<Node>,
+    },
+    Partial(String),
+}
+
+struct OpenSection {
+    kind: SectionKind,
+    name: String,
+    children: Vec<Node>,
+    inverse: Option<Vec<Node>>,
+}
+
+/**
+ * A context of the template: the current value and the @index, @first, @last and @key
+ * variables of the loop that pushed it.
+ */
+struct Frame {
+    value: Value,
+    locals: Map<String, Value>,
+}
+
+struct Renderer<'a> {
+    partials: &'a HashMap<String, String>,
+    files: Option<&'a BotFiles>,
+    escape: Escape,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+fn parse_tag(content: &str) -> (TagKind, &str) {
+    match content.chars().next() {
+        Some('!') => (TagKind::Comment, ""),
+        Some('&') => (TagKind::Raw, content[1..].trim()),
+        Some('>') => (TagKind::Partial, content[1..].trim()),
+        Some('/') => (TagKind::Close, content[1..].trim()),
+        Some('^') => (TagKind::Open(SectionKind::Inverted), content[1..].trim()),
+        Some('#') => {
+            let content = content[1..].trim();
+
+            match content.split_once(char::is_whitespace) {
+                Some(("if", name)) => (TagKind::Open(SectionKind::If), name.trim()),
+                Some(("unless", name)) => (TagKind::Open(SectionKind::Unless), name.trim()),
+                Some(("each", name)) => (TagKind::Open(SectionKind::Each), name.trim()),
+                Some(("with", name)) => (TagKind::Open(SectionKind::With), name.trim()),
+                _ => (TagKind::Open(SectionKind::Section), content),
+            }
+        }
+        _ if content == "else" => (TagKind::Else, ""),
+        _ => (TagKind::Variable, content),
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut rest = source;
+
+    while let Some(start) = rest.find("{{") {
+        if start > 0 {
+            tokens.push(Token::Text(rest[..start].to_owned()));
+        }
+
+        let tag = &rest[start + 2..];
+
+        let (kind, content, len) = if let Some(inner) = tag.strip_prefix('{') {
+            let end = inner
+                .find("}}}")
+                .ok_or_else(|| "unclosed tag '{{{'".to_owned())?;
+
+            (TagKind::Raw, inner[..end].trim(), end + 4)
+        } else if let Some(inner) = tag.strip_prefix("!--") {
+            let end = inner
+                .find("--}}")
+                .ok_or_else(|| "unclosed comment '{{!--'".to_owned())?;
+
+            (TagKind::Comment, "", end + 7)
+        } else {
+            let end = tag
+                .find("}}")
+                .ok_or_else(|| "unclosed tag '{{'".to_owned())?;
+            let (kind, content) = parse_tag(tag[..end].trim());
+
+            (kind, content, end + 2)
+        };
+
+        tokens.push(Token::Tag(kind, content.to_owned()));
+        rest = &tag[len..];
+    }
+
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest.to_owned()));
+    }
+
+    Ok(tokens)
+}
+
+fn is_blank(text: &str) -> bool {
+    text.chars().all(|c| c == ' ' || c == '\t' || c == '\r')
+}
+
+/**
+ * Section, else, partial and comment tags alone on their line are removed with the line,
+ * so that they do not leave empty lines in multi-line templates.
+ */
+fn trim_standalone_tags(tokens: &mut Vec<Token>) {
+    let standalone: Vec<bool> = (0..tokens.len())
+        .map(|i| match &tokens[i] {
+            Token::Tag(TagKind::Variable, _) | Token::Tag(TagKind::Raw, _) => false,
+            Token::Tag(..) => {
+                let before = match i.checked_sub(1).map(|j| &tokens[j]) {
+                    None => true,
+                    Some(Token::Text(text)) => match text.rfind('\n') {
+                        Some(idx) => is_blank(&text[idx + 1..]),
+                        None => i == 1 && is_blank(text),
+                    },
+                    Some(_) => false,
+                };
+                let after = match tokens.get(i + 1) {
+                    None => true,
+                    Some(Token::Text(text)) => match text.find('\n') {
+                        Some(idx) => is_blank(&text[..idx]),
+                        None => i + 2 == tokens.len() && is_blank(text),
+                    },
+                    Some(_) => false,
+                };
+
+                before && after
+            }
+            Token::Text(_) => false,
+        })
+        .collect();
+
+    for (i, _) in standalone.iter().enumerate().filter(|(_, s)| **s) {
+        if i > 0 {
+            if let Token::Text(text) = &mut tokens[i - 1] {
+                match text.rfind('\n') {
+                    Some(idx) => text.truncate(idx + 1),
+                    None => text.clear(),
+                }
+            }
+        }
+
+        if let Some(Token::Text(text)) = tokens.get_mut(i + 1) {
+            match text.find('\n') {
+                Some(idx) => {
+                    text.drain(..=idx);
+                }
+                None => text.clear(),
+            }
+        }
+    }
+}
+
+fn closing_name(kind: SectionKind, name: &str) -> &str {
+    match kind {
+        SectionKind::If => "if",
+        SectionKind::Unless => "unless",
+        SectionKind::Each => "each",
+        SectionKind::With => "with",
+        SectionKind::Section | SectionKind::Inverted => name,
+    }
+}
+
+fn parse_template(source: &str) -> Result<Vec<Node>, String> {
+    let mut tokens = tokenize(source)?;
+    trim_standalone_tags(&mut tokens);
+
+    let mut root = vec![];
+    let mut stack: Vec<OpenSection> = vec![];
+
+    for token in tokens {
+        let node = match token {
+            Token::Text(text) if text.is_empty() => continue,
+            Token::Text(text) => Node::Text(text),
+            Token::Tag(TagKind::Comment, _) => continue,
+            Token::Tag(TagKind::Variable, name) => Node::Variable { name, raw: false },
+            Token::Tag(TagKind::Raw, name) => Node::Variable { name, raw: true },
+            Token::Tag(TagKind::Partial, name) => Node::Partial(name),
+            Token::Tag(TagKind::Open(kind), name) => {
+                stack.push(OpenSection {
+                    kind,
+                    name,
+                    children: vec![],
+                    inverse: None,
+                });
+                continue;
+            }
+            Token::Tag(TagKind::Else, _) => {
+                match stack.last_mut() {
+                    Some(section) if section.inverse.is_none() => section.inverse = Some(vec![]),
+                    _ => return Err("unexpected '{{else}}'".to_owned()),
+                }
+                continue;
+            }
+            Token::Tag(TagKind::Close, name) => {
+                let section = stack
+                    .pop()
+                    .ok_or_else(|| format!("unexpected closing tag '{{{{/{}}}}}'", name))?;
+
+                if closing_name(section.kind, &section.name) != name {
+                    return Err(format!(
+                        "section '{}' closed by '{{{{/{}}}}}'",
+                        section.name, name
+                    ));
+                }
+
+                Node::Section {
+                    kind: section.kind,
+                    name: section.name,
+                    children: section.children,
+                    inverse: section.inverse.unwrap_or_default(),
+                }
+            }
+        };
+
+        match stack.last_mut() {
+            Some(OpenSection {
+                inverse: Some(inverse),
+                ..
+            }) => inverse.push(node),
+            Some(section) => section.children.push(node),
+            None => root.push(node),
+        }
+    }
+
+    match stack.pop() {
+        Some(section) => Err(format!("unclosed section '{}'", section.name)),
+        None => Ok(root),
+    }
+}
+
+fn get_key<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    match value {
+        Value::Object(map) => map.get(key),
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    }
+}
+
+/**
+ * Names are looked up from the innermost context to the outermost one, like in Mustache.
+ * '.' and 'this' are the current context and '@index', '@first', '@last' and '@key'
+ * are the variables of the innermost loop.
+ */
+fn lookup(name: &str, stack: &[Frame]) -> Value {
+    let current = match stack.last() {
+        Some(frame) => frame,
+        None => return Value::Null,
+    };
+
+    if name == "." || name == "this" {
+        return current.value.clone();
+    }
+
+    if let Some(local) = name.strip_prefix('@') {
+        return stack
+            .iter()
+            .rev()
+            .find_map(|frame| frame.locals.get(local))
+            .cloned()
+            .unwrap_or(Value::Null);
+    }
+
+    let (frames, path) = match name.strip_prefix("this.") {
+        Some(path) => (&stack[stack.len() - 1..], path),
+        None => (stack, name),
+    };
+
+    let mut keys = path.split('.');
+    let first = keys.next().unwrap_or_default();
+
+    for frame in frames.iter().rev() {
+        if let Some(value) = get_key(&frame.value, first) {
+            return keys
+                .try_fold(value, |value, key| get_key(value, key))
+                .cloned()
+                .unwrap_or(Value::Null);
+        }
+    }
+
+    Value::Null
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64() != Some(0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(_) => true,
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.to_owned(),
+        value => value.to_string(),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn loop_frame(value: Value, index: usize, len: usize, key: Option<&str>) -> Frame {
+    let mut locals = Map::new();
+    locals.insert("index".to_owned(), Value::from(index));
+    locals.insert("first".to_owned(), Value::Bool(index == 0));
+    locals.insert("last".to_owned(), Value::Bool(index + 1 == len));
+
+    if let Some(key) = key {
+        locals.insert("key".to_owned(), Value::from(key));
+    }
+
+    Frame { value, locals }
+}
+
+fn context_frame(value: Value) -> Frame {
+    Frame {
+        value,
+        locals: Map::new(),
+    }
+}
+
+fn get_named_arg<'a>(args: &'a ArgsType, name: &str) -> Option<&'a Literal> {
+    match args {
+        ArgsType::Named(map) => map.get(name),
+        ArgsType::Normal(_) => None,
+    }
+}
+
+/**
+ * Templates can be given inline or by the name of a template file of the bot
+ * (.mustache, .hbs, .html or .txt).
+ */
+fn get_source(args: &ArgsType, data: &Data, interval: Interval) -> Result<String, ErrorInfo> {
+    if let Some(lit) = get_named_arg(args, "file") {
+        let name = match lit.primitive.get_type() {
+            PrimitiveType::PrimitiveString => lit.primitive.to_string(),
+            _ => {
+                return Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_TEMPLATE.to_owned(),
+                ))
+            }
+        };
+
+        return match data.files.and_then(|files| files.get(&name)) {
+            Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveString => {
+                Ok(lit.primitive.to_string())
+            }
+            Some(_) => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("{} [{}]", ERROR_TEMPLATE_FILE, name),
+            )),
+            None => Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("{} [{}]", ERROR_FROM_FILE_MISSING, name),
+            )),
+        };
+    }
+
+    match args.get("source", 0) {
+        Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveString => {
+            Ok(lit.primitive.to_string())
+        }
+        _ => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            ERROR_TEMPLATE.to_owned(),
+        )),
+    }
+}
+
+fn get_partials(
+    args: &ArgsType,
+    data: &Data,
+    interval: Interval,
+) -> Result<HashMap<String, String>, ErrorInfo> {
+    let partials = match get_named_arg(args, "partials") {
+        Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveObject => {
+            lit.primitive.to_json()
+        }
+        Some(_) => {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                ERROR_TEMPLATE_PARTIALS.to_owned(),
+            ))
+        }
+        None => return Ok(HashMap::new()),
+    };
+
+    let mut map = HashMap::new();
+
+    if let Value::Object(partials) = partials {
+        for (name, partial) in partials {
+            match partial {
+                Value::String(partial) => {
+                    map.insert(name, partial);
+                }
+                _ => {
+                    return Err(gen_error_info(
+                        Position::new(interval, &data.context.flow),
+                        ERROR_TEMPLATE_PARTIALS.to_owned(),
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(map)
+}
+
+fn get_escape(args: &ArgsType, data: &Data, interval: Interval) -> Result<Escape, ErrorInfo> {
+    match get_named_arg(args, "escape") {
+        None => Ok(Escape::Html),
+        Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveString => {
+            match lit.primitive.to_string().as_str() {
+                "html" => Ok(Escape::Html),
+                "none" => Ok(Escape::None),
+                _ => Err(gen_error_info(
+                    Position::new(interval, &data.context.flow),
+                    ERROR_TEMPLATE_ESCAPE.to_owned(),
+                )),
+            }
+        }
+        Some(_) => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            ERROR_TEMPLATE_ESCAPE.to_owned(),
+        )),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// METHOD FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+impl<'a> Renderer<'a> {
+    /**
+     * Partials given to the builtin take precedence over the template files of the bot.
+     */
+    fn get_partial(&self, name: &str) -> Result<String, String> {
+        if let Some(partial) = self.partials.get(name) {
+            return Ok(partial.to_owned());
+        }
+
+        match self.files.and_then(|files| files.get(name)) {
+            Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveString => {
+                Ok(lit.primitive.to_string())
+            }
+            _ => Err(format!("partial '{}' not found", name)),
+        }
+    }
+
+    fn render_variable(&self, value: &Value, raw: bool, output: &mut String) {
+        let text = value_to_string(value);
+
+        match (raw, self.escape) {
+            (false, Escape::Html) => output.push_str(&escape_html(&text)),
+            _ => output.push_str(&text),
+        }
+    }
+
+    fn render_section(
+        &self,
+        kind: SectionKind,
+        value: Value,
+        (children, inverse): (&[Node], &[Node]),
+        stack: &mut Vec<Frame>,
+        depth: usize,
+        output: &mut String,
+    ) -> Result<(), String> {
+        match kind {
+            SectionKind::If | SectionKind::Unless | SectionKind::Inverted => {
+                let show = is_truthy(&value) == (kind == SectionKind::If);
+
+                self.render(if show { children } else { inverse }, stack, depth, output)
+            }
+            _ if !is_truthy(&value) => self.render(inverse, stack, depth, output),
+            SectionKind::Each | SectionKind::Section if value.is_array() => {
+                let items = match value {
+                    Value::Array(items) => items,
+                    _ => vec![],
+                };
+                let len = items.len();
+
+                for (index, item) in items.into_iter().enumerate() {
+                    stack.push(loop_frame(item, index, len, None));
+                    let result = self.render(children, stack, depth, output);
+                    stack.pop();
+                    result?;
+                }
+
+                Ok(())
+            }
+            SectionKind::Each if value.is_object() => {
+                let entries = match value {
+                    Value::Object(map) => map,
+                    _ => Map::new(),
+                };
+                let len = entries.len();
+
+                for (index, (key, item)) in entries.into_iter().enumerate() {
+                    stack.push(loop_frame(item, index, len, Some(&key)));
+                    let result = self.render(children, stack, depth, output);
+                    stack.pop();
+                    result?;
+                }
+
+                Ok(())
+            }
+            _ => {
+                stack.push(context_frame(value));
+                let result = self.render(children, stack, depth, output);
+                stack.pop();
+                result
+            }
+        }
+    }
+
+    fn render(
+        &self,
+        nodes: &[Node],
+        stack: &mut Vec<Frame>,
+        depth: usize,
+        output: &mut String,
+    ) -> Result<(), String> {
+        for node in nodes.iter() {
+            match node {
+                Node::Text(text) => output.push_str(text),
+                Node::Variable { name, raw } => {
+                    self.render_variable(&lookup(name, stack), *raw, output)
+                }
+                Node::Section {
+                    kind,
+                    name,
+                    children,
+                    inverse,
+                } => {
+                    let value = lookup(name, stack);
+
+                    self.render_section(*kind, value, (children, inverse), stack, depth, output)?
+                }
+                Node::Partial(name) => {
+                    if depth >= MAX_PARTIAL_DEPTH {
+                        return Err(format!(
+                            "partial '{}' exceeds the maximum depth of {}",
+                            name, MAX_PARTIAL_DEPTH
+                        ));
+                    }
+
+                    let partial = parse_template(&self.get_partial(name)?)?;
+
+                    self.render(&partial, stack, depth + 1, output)?
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PUBLIC FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+/**
+ * Renders a Mustache/Handlebars template: {{name}}, {{{raw}}}, {{#section}}, {{^inverted}},
+ * {{#if}}, {{#unless}}, {{#each}}, {{#with}}, {{else}}, {{> partial}} and {{! comments }}.
+ */
+pub fn template(args: ArgsType, data: &mut Data, interval: Interval) -> Result<Literal, ErrorInfo> {
+    let source = get_source(&args, data, interval)?;
+    let partials = get_partials(&args, data, interval)?;
+    let escape = get_escape(&args, data, interval)?;
+
+    let value = match args.get("data", 1).or_else(|| args.get("arg1", 1)) {
+        Some(lit) => lit.primitive.to_json(),
+        None => Value::Object(Map::new()),
+    };
+
+    let renderer = Renderer {
+        partials: &partials,
+        files: data.files,
+        escape,
+    };
+    let mut output = String::new();
+
+    parse_template(&source)
+        .and_then(|nodes| renderer.render(&nodes, &mut vec![context_frame(value)], 0, &mut output))
+        .map_err(|err| {
+            gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("{}: {}", ERROR_TEMPLATE_SYNTAX, err),
+            )
+        })?;
+
+    Ok(PrimitiveString::get_literal(&output, interval))
+}
@@ -0,0 +1,145 @@
+use crate::data::error_info::ErrorInfo;
+use crate::data::localization::get_plural_form;
+use crate::data::position::Position;
+use crate::data::primitive::{PrimitiveString, PrimitiveType};
+use crate::data::{ast::Interval, ArgsType, Data, Literal};
+use crate::error_format::*;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde_json::Value;
+use std::collections::HashMap;
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+// key of the user locale in _metadata and in the memory
+const LOCALE: &str = "locale";
+const COUNT: &str = "count";
+
+// {{name}} placeholder of a translation
+static PLACEHOLDER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\{\s*(\w+)\s*\}\}").unwrap());
+
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+/**
+ * The locale of the user is given by _metadata.locale, then by the 'locale' memory.
+ * If none is set, the default locale of the bot is used.
+ */
+fn get_locale(data: &Data) -> Option<String> {
+    let locale = data
+        .context
+        .metadata
+        .get(LOCALE)
+        .or_else(|| data.context.current.get(LOCALE));
+
+    match locale {
+        Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveString => {
+            Some(lit.primitive.to_string())
+        }
+        _ => None,
+    }
+}
+
+fn get_count(args: &ArgsType, data: &Data, interval: Interval) -> Result<Option<f64>, ErrorInfo> {
+    match args.get(COUNT, 1) {
+        Some(lit)
+            if lit.primitive.get_type() == PrimitiveType::PrimitiveInt
+                || lit.primitive.get_type() == PrimitiveType::PrimitiveFloat =>
+        {
+            Ok(lit.primitive.to_string().parse::<f64>().ok())
+        }
+        Some(_) => Err(gen_error_info(
+            Position::new(interval, &data.context.flow),
+            ERROR_TRANSLATE_COUNT.to_owned(),
+        )),
+        None => Ok(None),
+    }
+}
+
+/**
+ * Named arguments are used as values for the {{name}} placeholders of the translation.
+ */
+fn get_values(args: &ArgsType) -> HashMap<String, String> {
+    let mut values: HashMap<String, String> = match args {
+        ArgsType::Named(map) => map
+            .iter()
+            .filter(|(name, _)| *name != "arg0")
+            .map(|(name, lit)| (name.to_owned(), lit.primitive.to_string()))
+            .collect(),
+        ArgsType::Normal(_) => HashMap::new(),
+    };
+
+    if let Some(count) = args.get(COUNT, 1) {
+        values.insert(COUNT.to_owned(), count.primitive.to_string());
+    }
+
+    values
+}
+
+fn interpolate(text: &str, values: &HashMap<String, String>) -> String {
+    PLACEHOLDER
+        .replace_all(text, |captures: &Captures| match values.get(&captures[1]) {
+            Some(value) => value.to_owned(),
+            None => captures[0].to_owned(),
+        })
+        .to_string()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PUBLIC FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+pub fn translate(
+    args: ArgsType,
+    data: &mut Data,
+    interval: Interval,
+) -> Result<Literal, ErrorInfo> {
+    let key = match args.get("key", 0) {
+        Some(lit) if lit.primitive.get_type() == PrimitiveType::PrimitiveString => {
+            lit.primitive.to_string()
+        }
+        _ => {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                ERROR_TRANSLATE.to_owned(),
+            ))
+        }
+    };
+    let count = get_count(&args, data, interval)?;
+    let locale = get_locale(data);
+
+    // a missing translation must not break the conversation, the key is displayed instead
+    let translation = data
+        .localization
+        .and_then(|localization| localization.get_translation(locale.as_deref(), &key));
+
+    let (locale, value) = match translation {
+        Some(translation) => translation,
+        None => return Ok(PrimitiveString::get_literal(&key, interval)),
+    };
+
+    let value = match (value, count) {
+        (Value::Object(_), Some(count)) => get_plural_form(value, locale, count),
+        (Value::Object(forms), None) => forms.get("other"),
+        (value, _) => Some(value),
+    };
+
+    let text = match value {
+        Some(Value::String(text)) => text.to_owned(),
+        Some(Value::Number(number)) => number.to_string(),
+        _ => {
+            return Err(gen_error_info(
+                Position::new(interval, &data.context.flow),
+                format!("{} [{}]", ERROR_TRANSLATE_VALUE, key),
+            ))
+        }
+    };
+
+    Ok(PrimitiveString::get_literal(
+        &interpolate(&text, &get_values(&args)),
+        interval,
+    ))
+}
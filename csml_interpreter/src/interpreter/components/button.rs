@@ -27,9 +27,22 @@ pub fn add_button(map: &mut serde_json::Map<String, serde_json::Value>) {
                             ],
                             "add_value": [
                                 {"$_get": "title" },
-                                {"$_get": "payload" }
+                                {"$_get": "payload" },
+                                {"$_get": "synonyms" }
                             ]
                         }
+                    },
+                    {
+                        "synonyms": {
+                            "required": false,
+                            "type": "Array"
+                        }
+                    },
+                    {
+                        "match_options": {
+                            "required": false,
+                            "type": "Object"
+                        }
                     }
                 ]
             }
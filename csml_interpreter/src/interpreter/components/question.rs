@@ -19,6 +19,18 @@ pub fn add_question(map: &mut serde_json::Map<String, serde_json::Value>) {
                             "required": true,
                             "type": "Array"
                         }
+                    },
+                    {
+                        "synonyms": {
+                            "required": false,
+                            "type": "Object"
+                        }
+                    },
+                    {
+                        "match_options": {
+                            "required": false,
+                            "type": "Object"
+                        }
                     }
                 ]
             }
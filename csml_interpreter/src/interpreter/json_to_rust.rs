@@ -1,7 +1,7 @@
 use crate::data::position::Position;
 use crate::data::primitive::{
-    PrimitiveArray, PrimitiveBoolean, PrimitiveClosure, PrimitiveFloat, PrimitiveInt,
-    PrimitiveNull, PrimitiveObject, PrimitiveString,
+    PrimitiveArray, PrimitiveBoolean, PrimitiveClosure, PrimitiveDecimal, PrimitiveFloat,
+    PrimitiveInt, PrimitiveNull, PrimitiveObject, PrimitiveString,
 };
 use crate::data::{ast::Interval, Data, Literal, MessageData, MSG};
 use crate::error_format::*;
@@ -128,6 +128,18 @@ pub fn memory_to_literal(
             }
         }
 
+        serde_json::Value::Object(map) if map.contains_key("_decimal") => {
+            let decimal = map
+                .get("_decimal")
+                .and_then(|decimal| decimal.as_str())
+                .and_then(|decimal| PrimitiveDecimal::parse(decimal).ok());
+
+            match decimal {
+                Some(decimal) => Ok(PrimitiveDecimal::get_literal(decimal.value, interval)),
+                None => Ok(PrimitiveNull::get_literal(interval)),
+            }
+        }
+
         serde_json::Value::Object(map) if map.contains_key("_closure") => {
             if let Some(closure_json) = map.get("_closure") {
                 let closure: PrimitiveClosure = serde_json::from_value(closure_json.to_owned())?;
@@ -19,11 +19,12 @@ use crate::data::primitive::{
 use crate::data::{
     ast::{Expr, Function, GotoValueType, Identifier, Interval, PathLiteral, PathState},
     data::Data,
-    tokens::{COMPONENT, EVENT, _ENV, _MEMORY, _METADATA},
+    tokens::{_ENV, _MEMORY, _METADATA, APP, COMPONENT, EVENT, FN, HTTP, KNOWLEDGE},
     warnings::DisplayWarnings,
-    ArgsType, Literal, MemoryType, MessageData, MSG,
+    Literal, MemoryType, MessageData, MSG,
 };
 use crate::error_format::*;
+use crate::interpreter::builtins::parallel;
 use crate::interpreter::variable_handler::{
     gen_literal::gen_literal_from_component,
     gen_literal::gen_literal_from_event,
@@ -77,6 +78,8 @@ fn loop_path(
     // this is temporary until we find a better way, it helps restore the string in the
     // string index otherwise the string will be replaced by the char at the index
     let mut old_string = None;
+    // literals reached through the path, like the Time objects of an array, keep their own methods
+    let mut nested_content_type = None;
 
     while let Some((interval, action)) = path.next() {
         match action {
@@ -106,7 +109,10 @@ fn loop_path(
                 }
             }
             PathLiteral::VecIndex(index) => match get_at_index(lit, &data.context.flow, *index) {
-                Some(new_lit) => lit = new_lit,
+                Some(new_lit) => {
+                    nested_content_type = Some(ContentType::get(new_lit));
+                    lit = new_lit
+                }
                 None => {
                     let err = gen_error_info(
                         Position::new(*interval, &data.context.flow),
@@ -144,7 +150,10 @@ fn loop_path(
                     return Ok((lit.to_owned(), true));
                 } else {
                     match get_value_from_key(lit, &data.context.flow, key) {
-                        Some(new_lit) => lit = new_lit,
+                        Some(new_lit) => {
+                            nested_content_type = Some(ContentType::get(new_lit));
+                            lit = new_lit
+                        }
                         None => {
                             let err = gen_error_info(
                                 Position::new(*interval, &data.context.flow),
@@ -177,13 +186,18 @@ fn loop_path(
                 interval,
                 args,
             } => {
-                let args = match args {
-                    ArgsType::Normal(args) => args,
-                    ArgsType::Named(_) => {
-                        let err = gen_error_info(
-                            Position::new(*interval, &data.context.flow),
-                            format!("{}", ERROR_METHOD_NAMED_ARGS),
-                        );
+                let content_type = match &nested_content_type {
+                    Some(ContentType::Primitive) | None => content_type,
+                    Some(nested_content_type) => nested_content_type,
+                };
+
+                let args = match args.to_positional(
+                    content_type.get_named_params(name),
+                    &data.context.flow,
+                    *interval,
+                ) {
+                    Ok(args) => args,
+                    Err(err) => {
                         return Ok((
                             MSG::send_error_msg(&sender, msg_data, Err(err)),
                             tmp_update_var,
@@ -197,7 +211,7 @@ fn loop_path(
 
                 let mut return_lit = match lit.primitive.exec(
                     name,
-                    args,
+                    &args,
                     mem_type,
                     &lit.additional_info,
                     *interval,
@@ -592,6 +606,52 @@ pub fn get_literal_from_metadata(
     Ok(lit)
 }
 
+pub fn get_literal_from_parallel(
+    name: &str,
+    path: &[(Interval, PathLiteral)],
+    dis_warnings: &DisplayWarnings,
+    data: &mut Data,
+    msg_data: &mut MessageData,
+    sender: &Option<mpsc::Sender<MSG>>,
+) -> Result<Literal, ErrorInfo> {
+    let mut lit = match path.get(0) {
+        Some((
+            interval,
+            PathLiteral::Func {
+                name: method, args, ..
+            },
+        )) if method == "all" => match name {
+            HTTP => parallel::http_all(args, data, *interval)?,
+            _ => parallel::app_all(args, data, *interval, msg_data, sender)?,
+        },
+        Some((interval, _)) => {
+            return Err(gen_error_info(
+                Position::new(*interval, &data.context.flow),
+                format!("[{}] {}", name, ERROR_PARALLEL_METHOD),
+            ));
+        }
+        None => unreachable!(),
+    };
+
+    if path.len() == 1 {
+        return Ok(lit);
+    }
+
+    let content_type = ContentType::get(&lit);
+    let (lit, _tmp_mem_update) = exec_path_actions(
+        &mut lit,
+        dis_warnings,
+        &MemoryType::Constant,
+        None,
+        &Some(path[1..].to_owned()),
+        &content_type,
+        data,
+        msg_data,
+        sender,
+    )?;
+    Ok(lit)
+}
+
 pub fn get_var(
     var: Identifier,
     dis_warnings: &DisplayWarnings,
@@ -672,6 +732,41 @@ pub fn get_var(
                 None => Ok(lit),
             }
         }
+        // HTTP.all([...]) and App.all([...]) send several requests at the same time
+        name if [HTTP, APP, FN].contains(&name.as_str()) && path.is_some() => {
+            let path = resolve_path(
+                path.unwrap_or_default(),
+                dis_warnings,
+                data,
+                msg_data,
+                sender,
+            )?;
+            get_literal_from_parallel(&name, &path, dis_warnings, data, msg_data, sender)
+        }
+        name if name == KNOWLEDGE => {
+            let mut lit = PrimitiveObject::get_literal(&HashMap::new(), var.interval);
+            lit.set_content_type("knowledge");
+
+            match path {
+                Some(path) => {
+                    let path = resolve_path(path, dis_warnings, data, msg_data, sender)?;
+                    let (lit, _tmp_mem_update) = exec_path_actions(
+                        &mut lit,
+                        dis_warnings,
+                        &MemoryType::Constant,
+                        None,
+                        &Some(path),
+                        &ContentType::Knowledge,
+                        data,
+                        msg_data,
+                        sender,
+                    )?;
+
+                    Ok(lit)
+                }
+                None => Ok(lit),
+            }
+        }
         _ => {
             // ######################
             // create a temporary scope
@@ -703,6 +798,10 @@ pub fn get_var(
                 data.previous_info.clone(),
                 data.custom_component,
                 data.native_component,
+                data.knowledge,
+                data.localization,
+                data.files,
+                data.network_policies,
             );
             // #####################
 
@@ -15,8 +15,31 @@ use crate::interpreter::{
     json_to_rust::json_to_literal,
     variable_handler::{exec_path_actions, resolve_path},
 };
+use std::collections::HashMap;
 use std::sync::mpsc;
 
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+/**
+ * The keys of the event content are never replaced
+ */
+fn insert_event_key(
+    map: &mut HashMap<String, Literal>,
+    key: &str,
+    value: &serde_json::Value,
+    interval: Interval,
+    data: &Data,
+) -> Result<(), ErrorInfo> {
+    if !map.contains_key(key) {
+        let value = json_to_literal(value, interval, &data.context.flow)?;
+        map.insert(key.to_owned(), value);
+    }
+
+    Ok(())
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // PUBLIC FUNCTIONS
 ////////////////////////////////////////////////////////////////////////////////
@@ -32,9 +55,30 @@ pub fn gen_literal_from_event(
     match path {
         Some(path) => {
             let path = resolve_path(path, dis_warnings, data, msg_data, sender)?;
+
             let mut lit =
                 json_to_literal(&data.event.content, interval.to_owned(), &data.context.flow)?;
 
+            // the trigger that selected the current flow is exposed as event.trigger
+            // and the NLU annotations as event.nlu, with event.intent and event.entities
+            // as shortcuts, unless the event content has these keys
+            if let Some(map) = lit
+                .primitive
+                .get_mut_value()
+                .downcast_mut::<HashMap<String, Literal>>()
+            {
+                if let Some(trigger) = &data.event.trigger {
+                    insert_event_key(map, "trigger", &trigger.to_json(), interval, data)?;
+                }
+                if let Some(nlu) = &data.event.nlu {
+                    let nlu = nlu.to_json();
+
+                    insert_event_key(map, "intent", &nlu["intent"], interval, data)?;
+                    insert_event_key(map, "entities", &nlu["entities"], interval, data)?;
+                    insert_event_key(map, "nlu", &nlu, interval, data)?;
+                }
+            }
+
             lit.set_content_type("event");
 
             let content_type = match ContentType::get(&lit) {
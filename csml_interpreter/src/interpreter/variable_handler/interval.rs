@@ -18,6 +18,7 @@ pub fn interval_from_expr(expr: &Expr) -> Interval {
         Expr::PathExpr { literal, .. } => interval_from_expr(literal),
         Expr::ForEachExpr(_, _, _, _, range_interval) => *range_interval,
         Expr::WhileExpr(_, _, range_interval) => *range_interval,
+        Expr::FormExpr(form) => form.interval,
         Expr::IdentExpr(ident) => ident.interval.to_owned(),
         Expr::LitExpr { literal, .. } => literal.interval.to_owned(),
         Expr::IfExpr(ifstmt) => interval_from_if_stmt(ifstmt),
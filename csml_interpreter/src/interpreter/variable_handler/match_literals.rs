@@ -1,6 +1,176 @@
 // use crate::error_format::ErrorInfo;
-use crate::data::primitive::{PrimitiveArray, PrimitiveObject, PrimitiveString};
+use crate::data::primitive::{
+    tools::{get_levenshtein_distance, get_similarity, get_tokens, normalize_text},
+    PrimitiveArray, PrimitiveBoolean, PrimitiveFloat, PrimitiveInt, PrimitiveObject,
+    PrimitiveString,
+};
 use crate::data::Literal;
+use std::collections::HashMap;
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+/**
+ * Matching rules set on a Button or a Question with the `match_options` parameter.
+ * Without options, strings are compared the legacy way (case insensitive equality).
+ */
+#[derive(Debug, Clone, Default)]
+pub struct MatchOptions {
+    pub fold_accents: bool,
+    pub max_distance: Option<usize>,
+    pub threshold: Option<f64>,
+    pub contains: bool,
+    pub synonyms: HashMap<String, Vec<String>>,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// METHOD FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+impl MatchOptions {
+    fn get_option<'a>(options: Option<&'a PrimitiveObject>, key: &str) -> Option<&'a Literal> {
+        options?.value.get(key)
+    }
+
+    fn get_synonyms(lit: &Literal, fold_accents: bool) -> HashMap<String, Vec<String>> {
+        let mut synonyms = HashMap::new();
+
+        let object = match lit.primitive.as_any().downcast_ref::<PrimitiveObject>() {
+            Some(object) => object,
+            None => return synonyms,
+        };
+
+        for (key, value) in object.value.iter() {
+            if let Some(array) = value.primitive.as_any().downcast_ref::<PrimitiveArray>() {
+                let values = array
+                    .value
+                    .iter()
+                    .map(|value| normalize_text(&value.primitive.to_string(), fold_accents))
+                    .collect();
+
+                synonyms.insert(normalize_text(key, fold_accents), values);
+            }
+        }
+
+        synonyms
+    }
+
+    pub fn from_literal(lit: &Literal) -> Option<Self> {
+        let object = lit.primitive.as_any().downcast_ref::<PrimitiveObject>()?;
+
+        let (options, synonyms) = match (
+            object.value.get("match_options"),
+            object.value.get("synonyms"),
+        ) {
+            (None, None) => return None,
+            (options, synonyms) => (
+                options.and_then(|lit| lit.primitive.as_any().downcast_ref::<PrimitiveObject>()),
+                synonyms,
+            ),
+        };
+
+        let fold_accents = match MatchOptions::get_option(options, "fold_accents") {
+            Some(lit) => match lit.primitive.as_any().downcast_ref::<PrimitiveBoolean>() {
+                Some(boolean) => boolean.value,
+                None => false,
+            },
+            None => false,
+        };
+
+        let max_distance = MatchOptions::get_option(options, "max_distance").and_then(|lit| {
+            lit.primitive
+                .as_any()
+                .downcast_ref::<PrimitiveInt>()
+                .filter(|int| int.value >= 0)
+                .map(|int| int.value as usize)
+        });
+
+        let threshold = MatchOptions::get_option(options, "threshold").and_then(|lit| {
+            match lit.primitive.as_any().downcast_ref::<PrimitiveFloat>() {
+                Some(float) => Some(float.value),
+                None => lit
+                    .primitive
+                    .as_any()
+                    .downcast_ref::<PrimitiveInt>()
+                    .map(|int| int.value as f64),
+            }
+        });
+
+        let contains = match MatchOptions::get_option(options, "contains") {
+            Some(lit) => match lit.primitive.as_any().downcast_ref::<PrimitiveBoolean>() {
+                Some(boolean) => boolean.value,
+                None => false,
+            },
+            None => false,
+        };
+
+        let synonyms = match synonyms {
+            Some(synonyms) => MatchOptions::get_synonyms(synonyms, fold_accents),
+            None => HashMap::new(),
+        };
+
+        Some(Self {
+            fold_accents,
+            max_distance,
+            threshold,
+            contains,
+            synonyms,
+        })
+    }
+
+    fn is_close(&self, input: &str, expected: &str) -> bool {
+        if input == expected {
+            return true;
+        }
+
+        if let Some(max_distance) = self.max_distance {
+            if get_levenshtein_distance(input, expected) <= max_distance {
+                return true;
+            }
+        }
+
+        if let Some(threshold) = self.threshold {
+            if get_similarity(input, expected) >= threshold {
+                return true;
+            }
+        }
+
+        if self.contains && !expected.is_empty() {
+            let input_tokens = get_tokens(input);
+
+            return get_tokens(expected)
+                .iter()
+                .all(|token| input_tokens.contains(token));
+        }
+
+        false
+    }
+
+    /**
+     * Normalize both strings, then compare the user's input to the expected value
+     * and to all of its synonyms.
+     */
+    pub fn is_match(&self, input: &str, expected: &str) -> bool {
+        let input = normalize_text(input, self.fold_accents);
+        let expected = normalize_text(expected, self.fold_accents);
+
+        if self.is_close(&input, &expected) {
+            return true;
+        }
+
+        match self.synonyms.get(&expected) {
+            Some(synonyms) => synonyms
+                .iter()
+                .any(|synonym| self.is_close(&input, synonym)),
+            None => false,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
 
 fn get_accept(lit: &Literal) -> Option<&Literal> {
     let val = lit
@@ -12,7 +182,46 @@ fn get_accept(lit: &Literal) -> Option<&Literal> {
     Some(val)
 }
 
-fn contains(array_lit: &Literal, key: &Literal) -> bool {
+fn get_buttons(lit: &Literal) -> Option<&Vec<Literal>> {
+    let val = lit
+        .primitive
+        .as_any()
+        .downcast_ref::<PrimitiveObject>()?
+        .value
+        .get("buttons")?;
+
+    Some(
+        &val.primitive
+            .as_any()
+            .downcast_ref::<PrimitiveArray>()?
+            .value,
+    )
+}
+
+// buttons stored in a question are serialized as {"content_type": "button", "content": {..}}
+fn get_button_content(lit: &Literal) -> &Literal {
+    let object = match lit.primitive.as_any().downcast_ref::<PrimitiveObject>() {
+        Some(object) => object,
+        None => return lit,
+    };
+
+    match (
+        object.value.get("content_type"),
+        object.value.get("content"),
+    ) {
+        (Some(_), Some(content)) => content,
+        _ => lit,
+    }
+}
+
+fn is_string_match(lit1: &Literal, lit2: &Literal, options: &MatchOptions) -> Option<bool> {
+    let lhs = lit1.primitive.as_any().downcast_ref::<PrimitiveString>()?;
+    let rhs = lit2.primitive.as_any().downcast_ref::<PrimitiveString>()?;
+
+    Some(options.is_match(&lhs.value, &rhs.value) || options.is_match(&rhs.value, &lhs.value))
+}
+
+fn contains(array_lit: &Literal, key: &Literal, options: Option<&MatchOptions>) -> bool {
     let key_string = key.primitive.as_any().downcast_ref::<PrimitiveString>();
     match (
         array_lit
@@ -20,9 +229,16 @@ fn contains(array_lit: &Literal, key: &Literal) -> bool {
             .as_any()
             .downcast_ref::<PrimitiveArray>(),
         key_string,
+        options,
     ) {
-        (Some(array), None) => array.value.contains(key),
-        (Some(array), Some(string)) => {
+        (Some(array), Some(string), Some(options)) => array.value.iter().any(|elem| {
+            match elem.primitive.as_any().downcast_ref::<PrimitiveString>() {
+                Some(val) => options.is_match(&string.value, &val.value),
+                None => false,
+            }
+        }),
+        (Some(array), None, _) => array.value.contains(key),
+        (Some(array), Some(string), None) => {
             for elem in array.value.iter() {
                 match elem.primitive.as_any().downcast_ref::<PrimitiveString>() {
                     Some(val)
@@ -39,28 +255,80 @@ fn contains(array_lit: &Literal, key: &Literal) -> bool {
     }
 }
 
-pub fn match_obj(lit1: &Literal, lit2: &Literal) -> bool {
+/**
+ * A question matches if one of its buttons matches.
+ * The question's match_options apply to all of its buttons, unless a button has its own.
+ */
+fn find_question_match<'a>(
+    question: &'a Literal,
+    lit: &Literal,
+    options: Option<&MatchOptions>,
+) -> Option<&'a Literal> {
+    let question_options = MatchOptions::from_literal(question);
+    let options = question_options.as_ref().or(options);
+
+    get_buttons(question)?
+        .iter()
+        .map(|button| get_button_content(button))
+        .find(|button| match_obj_with_options(button, lit, options))
+}
+
+fn match_obj_with_options(lit1: &Literal, lit2: &Literal, options: Option<&MatchOptions>) -> bool {
     match (&lit1.content_type, &lit2.content_type) {
+        (q1, ..) if q1 == "question" => find_question_match(lit1, lit2, options).is_some(),
+        (.., q2) if q2 == "question" => find_question_match(lit2, lit1, options).is_some(),
+
         (b1, b2) if (b1 == "button" || b1 == "object") && (b2 == "button" || b2 == "object") => {
             match (get_accept(lit1), get_accept(lit2)) {
-                (Some(l1), Some(l2)) => match_obj(l1, l2),
+                (Some(l1), Some(l2)) => match_obj_with_options(l1, l2, options),
                 (_, _) => false,
             }
         }
 
         (.., button) if (button == "button" || button == "object") => match get_accept(lit2) {
-            Some(l2) => match_obj(lit1, l2),
+            Some(l2) => {
+                let button_options = MatchOptions::from_literal(lit2);
+
+                match_obj_with_options(lit1, l2, button_options.as_ref().or(options))
+            }
             None => false,
         },
         (button, ..) if (button == "button" || button == "object") => match get_accept(lit1) {
-            Some(l1) => match_obj(l1, lit2),
+            Some(l1) => {
+                let button_options = MatchOptions::from_literal(lit1);
+
+                match_obj_with_options(l1, lit2, button_options.as_ref().or(options))
+            }
             None => false,
         },
 
         (array1, array2) if array1 == "array" && array2 == "array" => lit1 == lit2,
-        (.., array) if array == "array" => contains(lit2, lit1),
-        (array, ..) if array == "array" => contains(lit1, lit2),
-        (..) => &lit1.primitive == &lit2.primitive,
+        (.., array) if array == "array" => contains(lit2, lit1, options),
+        (array, ..) if array == "array" => contains(lit1, lit2, options),
+        (..) => match options.and_then(|options| is_string_match(lit1, lit2, options)) {
+            Some(is_match) => is_match,
+            None => &lit1.primitive == &lit2.primitive,
+        },
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PUBLIC FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+pub fn match_obj(lit1: &Literal, lit2: &Literal) -> bool {
+    match_obj_with_options(lit1, lit2, None)
+}
+
+/**
+ * Return the literal matching the user's input:
+ * the matching button if lit is a question, lit itself otherwise.
+ */
+pub fn find_match<'a>(input: &Literal, lit: &'a Literal) -> Option<&'a Literal> {
+    match &lit.content_type {
+        question if question == "question" => find_question_match(lit, input, None),
+        _ if match_obj(input, lit) => Some(lit),
+        _ => None,
     }
 }
 
@@ -152,6 +420,43 @@ mod tests {
         }
     }
 
+    fn gen_component(name: &str, map: HashMap<String, Literal>) -> Literal {
+        let native_component = load_components().unwrap();
+
+        match native_component.get(name) {
+            Some(component) => {
+                match gen_generic_component(
+                    name,
+                    false,
+                    "Test",
+                    &gen_inter(),
+                    &ArgsType::Named(map),
+                    component,
+                ) {
+                    Ok(lit) => lit,
+                    Err(..) => panic!("gen {} error", name),
+                }
+            }
+            None => panic!("error in native_component"),
+        }
+    }
+
+    fn gen_match_options(options: serde_json::Value) -> Literal {
+        crate::interpreter::json_to_literal(&options, gen_inter(), "Test").unwrap()
+    }
+
+    fn gen_button_options(name: &str, options: serde_json::Value) -> Literal {
+        let mut map = HashMap::new();
+
+        map.insert(
+            "title".to_owned(),
+            PrimitiveString::get_literal(name, gen_inter()),
+        );
+        map.insert("match_options".to_owned(), gen_match_options(options));
+
+        gen_component("Button", map)
+    }
+
     fn match_lit_true(lit1: &Literal, lit2: &Literal) {
         match match_obj(&lit1, &lit2) {
             boolean if boolean => {}
@@ -236,4 +541,87 @@ mod tests {
 
         match_lit_err(&bt1, &bt2);
     }
+
+    #[test]
+    fn ok_match_button_fold_accents() {
+        let bt1 = gen_button_options("Oui", serde_json::json!({"fold_accents": true}));
+        let bt2 = PrimitiveString::get_literal("OUÍ !", gen_inter());
+
+        match_lit_true(&bt1, &bt2);
+        match_lit_false(&gen_button("Oui"), &bt2);
+    }
+
+    #[test]
+    fn ok_match_button_distance() {
+        let bt1 = gen_button_options("yes", serde_json::json!({"max_distance": 1}));
+
+        match_lit_true(&bt1, &PrimitiveString::get_literal("yess", gen_inter()));
+        match_lit_false(&bt1, &PrimitiveString::get_literal("yesss", gen_inter()));
+    }
+
+    #[test]
+    fn ok_match_button_contains() {
+        let bt1 = gen_button_options("yes", serde_json::json!({"contains": true}));
+
+        match_lit_true(
+            &bt1,
+            &PrimitiveString::get_literal("Yes please", gen_inter()),
+        );
+        match_lit_false(
+            &bt1,
+            &PrimitiveString::get_literal("yesterday", gen_inter()),
+        );
+    }
+
+    #[test]
+    fn ok_match_button_synonyms() {
+        let mut map = HashMap::new();
+
+        map.insert(
+            "title".to_owned(),
+            PrimitiveString::get_literal("yes", gen_inter()),
+        );
+        map.insert(
+            "synonyms".to_owned(),
+            PrimitiveArray::get_literal(
+                &vec![PrimitiveString::get_literal("yep", gen_inter())],
+                gen_inter(),
+            ),
+        );
+        let bt1 = gen_component("Button", map);
+
+        match_lit_true(&bt1, &PrimitiveString::get_literal("Yep", gen_inter()));
+    }
+
+    #[test]
+    fn ok_match_question() {
+        let mut map = HashMap::new();
+
+        map.insert(
+            "buttons".to_owned(),
+            PrimitiveArray::get_literal(&vec![gen_button("yes"), gen_button("no")], gen_inter()),
+        );
+        map.insert(
+            "synonyms".to_owned(),
+            gen_match_options(serde_json::json!({"yes": ["sure", "of course"]})),
+        );
+        map.insert(
+            "match_options".to_owned(),
+            gen_match_options(serde_json::json!({"contains": true})),
+        );
+        let question = gen_component("Question", map);
+
+        let input = PrimitiveString::get_literal("of course !", gen_inter());
+        match_lit_true(&question, &input);
+
+        match find_match(&input, &question) {
+            Some(button) => match_lit_true(button, &gen_button("yes")),
+            None => panic!("no button found in question"),
+        }
+
+        match_lit_false(
+            &question,
+            &PrimitiveString::get_literal("maybe", gen_inter()),
+        );
+    }
 }
@@ -21,7 +21,10 @@ use data::message_data::MessageData;
 use data::msg::MSG;
 use data::CsmlResult;
 use data::{csml_bot::CsmlBot, CsmlFlow};
-use data::{Context, Data, Position, STEP_LIMIT};
+use data::{
+    BotFiles, Context, Data, HttpMock, KnowledgeIndex, Localization, NetworkPolicy, Position,
+    STEP_LIMIT,
+};
 use error_format::*;
 use fold_bot::fold_bot as fold;
 use linter::{linter::lint_bot, FlowToValidate};
@@ -104,6 +107,23 @@ fn get_step_limit(event: &Event) -> usize {
     }
 }
 
+fn get_http_mock(event: &Event) -> Option<HttpMock> {
+    match &event.http_mock {
+        Some(http_mock) => Some(http_mock.to_owned()),
+        None => HttpMock::from_env(),
+    }
+}
+
+/**
+ * The policy of the engine and the one of the bot both apply
+ */
+fn get_network_policies(bot: &CsmlBot) -> Vec<NetworkPolicy> {
+    NetworkPolicy::from_env()
+        .into_iter()
+        .chain(bot.network_policy.to_owned())
+        .collect()
+}
+
 fn get_flow_ast<'a, 'b>(
     flows: &'a HashMap<String, Flow>,
     flow: &'b str,
@@ -275,6 +295,7 @@ pub fn validate_bot(bot: &CsmlBot) -> CsmlResult {
             &mut warnings,
             &bot.native_components,
             &bot.default_flow,
+            &bot.locales,
         );
     }
 
@@ -372,6 +393,28 @@ fn get_flows(bot: &CsmlBot) -> (HashMap<String, Flow>, HashMap<String, Flow>) {
     }
 }
 
+/**
+ * Use the knowledge index built with the bot if any, otherwise build it from the bot knowledge.
+ */
+fn get_knowledge(bot: &CsmlBot) -> Option<KnowledgeIndex> {
+    match (&bot.knowledge_index, &bot.knowledge) {
+        (Some(index), _) => KnowledgeIndex::decode(index),
+        (None, Some(knowledge)) => KnowledgeIndex::from_knowledge(knowledge).ok(),
+        (None, None) => None,
+    }
+}
+
+/**
+ * Use the parsed files stored with the bot if any, otherwise parse the bot files.
+ */
+fn get_files(bot: &CsmlBot) -> Option<BotFiles> {
+    match (&bot.files_cache, &bot.files) {
+        (Some(files), _) => BotFiles::decode(files),
+        (None, Some(files)) => BotFiles::from_files(files).ok(),
+        (None, None) => None,
+    }
+}
+
 pub fn search_for_modules(bot: &mut CsmlBot) -> Result<(), String> {
     let default_auth = env::var("MODULES_AUTH").ok();
     let default_url = env::var("MODULES_URL").ok();
@@ -429,6 +472,7 @@ pub fn search_for_modules(bot: &mut CsmlBot) -> Result<(), String> {
                         name: module.name.clone(),
                         content: flow_content,
                         commands: vec![],
+                        triggers: vec![],
                     });
                 }
                 Err(error) => return Err(error.to_string()),
@@ -442,11 +486,13 @@ pub fn search_for_modules(bot: &mut CsmlBot) -> Result<(), String> {
 pub fn interpret(
     bot: CsmlBot,
     mut context: Context,
-    event: Event,
+    mut event: Event,
     sender: Option<mpsc::Sender<MSG>>,
 ) -> MessageData {
     csml_logs::init_logger();
 
+    event.http_mock = get_http_mock(&event);
+
     let mut msg_data = MessageData::default();
 
     let mut flow = context.flow.to_owned();
@@ -471,6 +517,10 @@ pub fn interpret(
     };
 
     let (flows, extern_flows) = get_flows(&bot);
+    let knowledge = get_knowledge(&bot);
+    let localization = Localization::new(&bot.locales, &bot.default_locale);
+    let files = get_files(&bot);
+    let network_policies = get_network_policies(&bot);
 
     let env = match bot.env {
         Some(env) => json_to_literal(&env, Interval::default(), &flow).unwrap(),
@@ -515,6 +565,10 @@ pub fn interpret(
             previous_info.clone(),
             &custom,
             &native,
+            knowledge.as_ref(),
+            localization,
+            files.as_ref(),
+            &network_policies,
         );
 
         msg_data = match inserted_ast {
@@ -4,7 +4,7 @@ pub mod linter;
 use crate::data::ast::Flow;
 pub use data::{
     ConstantInfo, FlowConstantUse, FunctionCallInfo, FunctionInfo, ImportInfo, InsertInfo,
-    LinterInfo, ScopeType, State, StepBreakers, StepInfo,
+    LinterInfo, ScopeType, State, StepBreakers, StepInfo, TranslationInfo,
 };
 use std::collections::HashMap;
 
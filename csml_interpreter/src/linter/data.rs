@@ -77,6 +77,13 @@ pub struct InsertInfo<'a> {
     pub interval: Interval,
 }
 
+#[derive(Debug, Clone)]
+pub struct TranslationInfo<'a> {
+    pub key: String,
+    pub in_flow: &'a str,
+    pub interval: Interval,
+}
+
 #[derive(Debug)]
 pub struct State {
     pub in_function: i16,
@@ -103,6 +110,7 @@ pub struct LinterInfo<'a> {
     pub insert_list: &'a mut HashSet<InsertInfo<'a>>,
     pub valid_closure_list: &'a mut Vec<FunctionCallInfo<'a>>,
     pub functions_call_list: &'a mut Vec<FunctionCallInfo<'a>>,
+    pub translation_list: &'a mut Vec<TranslationInfo<'a>>,
     pub errors: &'a mut Vec<ErrorInfo>,
     pub warnings: &'a mut Vec<Warnings>,
     pub native_components: &'a Option<serde_json::Map<String, serde_json::Value>>,
@@ -237,6 +245,7 @@ impl<'a> LinterInfo<'a> {
         insert_list: &'a mut HashSet<InsertInfo<'a>>,
         valid_closure_list: &'a mut Vec<FunctionCallInfo<'a>>,
         functions_call_list: &'a mut Vec<FunctionCallInfo<'a>>,
+        translation_list: &'a mut Vec<TranslationInfo<'a>>,
         errors: &'a mut Vec<ErrorInfo>,
         warnings: &'a mut Vec<Warnings>,
         native_components: &'a Option<serde_json::Map<String, serde_json::Value>>,
@@ -254,6 +263,7 @@ impl<'a> LinterInfo<'a> {
             insert_list,
             valid_closure_list,
             functions_call_list,
+            translation_list,
             errors,
             warnings,
             native_components,
@@ -299,6 +309,16 @@ impl<'a> FunctionCallInfo<'a> {
     }
 }
 
+impl<'a> TranslationInfo<'a> {
+    pub fn new(key: String, in_flow: &'a str, interval: Interval) -> Self {
+        Self {
+            key,
+            in_flow,
+            interval,
+        }
+    }
+}
+
 impl<'a> ImportInfo<'a> {
     pub fn new(
         as_name: String,
@@ -1,8 +1,9 @@
 use crate::data::{
     ast::*,
+    localization::get_catalog_keys,
     position::Position,
     primitive::{PrimitiveClosure, PrimitiveType},
-    tokens::{Span, BUILT_IN, BUILT_IN_WITHOUT_WARNINGS, COMPONENT},
+    tokens::{Span, BUILT_IN, BUILT_IN_WITHOUT_WARNINGS, COMPONENT, TRANSLATE},
     warnings::*,
     Literal,
 };
@@ -13,10 +14,10 @@ use crate::error_format::{
 use crate::interpreter::variable_handler::interval::interval_from_expr;
 use crate::linter::{
     ConstantInfo, FlowConstantUse, FlowToValidate, FunctionCallInfo, FunctionInfo, ImportInfo,
-    InsertInfo, LinterInfo, ScopeType, State, StepBreakers, StepInfo,
+    InsertInfo, LinterInfo, ScopeType, State, StepBreakers, StepInfo, TranslationInfo,
 };
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 pub const ERROR_GOTO_IN_FN: &str = "'goto' action is not allowed in function scope";
 pub const ERROR_REMEMBER_IN_FN: &str = "'remember' action is not allowed in function scope";
@@ -25,6 +26,7 @@ pub const ERROR_RETURN_IN_FN: &str = "'return' action is not allowed outside fun
 pub const ERROR_BREAK_IN_LOOP: &str = "'break' action is not allowed outside loop";
 pub const ERROR_CONTINUE_IN_LOOP: &str = "'continue' action is not allowed outside loop";
 pub const ERROR_HOLD_IN_LOOP: &str = "'hold' action is not allowed in function scope";
+pub const ERROR_FORM_IN_FN: &str = "'form' action is not allowed in function scope";
 
 ////////////////////////////////////////////////////////////////////////////////
 // PUBLIC FUNCTIONS
@@ -37,6 +39,7 @@ pub fn lint_bot(
     warnings: &mut Vec<Warnings>,
     native_components: &Option<serde_json::Map<String, serde_json::Value>>,
     default_flow: &str,
+    locales: &Option<HashMap<String, serde_json::Value>>,
 ) {
     let scope_type = ScopeType::Step("start".to_owned());
     let mut bot_constants = HashMap::new();
@@ -47,6 +50,7 @@ pub fn lint_bot(
     let mut insert_list = HashSet::new();
     let mut valid_closure_list = vec![];
     let mut functions_call_list = vec![];
+    let mut translation_list = vec![];
 
     let mut linter_info = LinterInfo::new(
         "",
@@ -61,6 +65,7 @@ pub fn lint_bot(
         &mut insert_list,
         &mut valid_closure_list,
         &mut functions_call_list,
+        &mut translation_list,
         errors,
         warnings,
         native_components,
@@ -94,6 +99,7 @@ pub fn lint_bot(
     validate_functions(&mut linter_info);
     validate_constants(&mut linter_info);
     validate_inserts(&mut linter_info);
+    validate_translations(&mut linter_info, locales);
 
     match infinite_loop_check(
         &linter_info,
@@ -356,6 +362,61 @@ pub fn validate_constants(linter_info: &mut LinterInfo) {
     }
 }
 
+/**
+ * Warn about translation keys missing in some locales: the keys used with T("key") in the flows
+ * and the keys defined in a catalog but not in the others.
+ */
+pub fn validate_translations(
+    linter_info: &mut LinterInfo,
+    locales: &Option<HashMap<String, serde_json::Value>>,
+) {
+    let locales = match locales {
+        Some(locales) if !locales.is_empty() => locales,
+        _ => return,
+    };
+
+    let catalogs: BTreeMap<&String, BTreeSet<String>> = locales
+        .iter()
+        .map(|(locale, catalog)| (locale, get_catalog_keys(catalog)))
+        .collect();
+    let mut reported_keys = HashSet::new();
+
+    for info in linter_info.translation_list.iter() {
+        for (locale, keys) in catalogs.iter() {
+            if !keys.contains(&info.key) {
+                linter_info.warnings.push(gen_warning_info(
+                    Position::new(info.interval.to_owned(), info.in_flow),
+                    format!(
+                        "translation key '{}' is missing in locale '{}'",
+                        info.key, locale
+                    ),
+                ));
+                reported_keys.insert(info.key.as_str());
+            }
+        }
+    }
+
+    let all_keys: BTreeSet<&String> = catalogs.values().flatten().collect();
+
+    for key in all_keys {
+        if reported_keys.contains(key.as_str()) {
+            continue;
+        }
+
+        for (locale, keys) in catalogs.iter() {
+            if !keys.contains(key) {
+                linter_info.warnings.push(gen_warning_info(
+                    Position::new(Interval::default(), &format!("locales/{}", locale)),
+                    format!(
+                        "translation key '{}' is missing in locale '{}'",
+                        key, locale
+                    ),
+                ));
+            }
+        }
+    }
+}
+
 pub fn validate_flow_ast(flow: &FlowToValidate, linter_info: &mut LinterInfo, extern_module: bool) {
     let mut is_step_start_present = false;
     let mut steps_nbr = 0;
@@ -488,6 +549,33 @@ fn register_closure(
     }
 }
 
+fn get_string_literal(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::LitExpr { literal, .. }
+            if literal.primitive.get_type() == PrimitiveType::PrimitiveString =>
+        {
+            Some(literal.primitive.to_string())
+        }
+        Expr::ComplexLiteral(vec, ..) if vec.len() == 1 => get_string_literal(&vec[0]),
+        _ => None,
+    }
+}
+
+/**
+ * Only translation keys given as plain strings can be checked by the linter.
+ */
+fn register_translation(args: &Expr, interval: &Interval, linter_info: &mut LinterInfo) {
+    if let Expr::VecExpr(args, ..) = args {
+        if let Some(key) = args.first().and_then(get_string_literal) {
+            linter_info.translation_list.push(TranslationInfo::new(
+                key,
+                linter_info.flow_name,
+                interval.to_owned(),
+            ));
+        }
+    }
+}
+
 fn register_flow_breaker(
     step_breakers: &mut Option<&mut Vec<StepBreakers>>,
     breaker: StepBreakers,
@@ -538,6 +626,8 @@ fn validate_expr_literals(to_be_literal: &Expr, state: &mut State, linter_info:
                     interval.to_owned(),
                     WARNING_FN,
                 ));
+            } else if name == TRANSLATE {
+                register_translation(args, interval, linter_info);
             }
 
             linter_info.functions_call_list.push(FunctionCallInfo::new(
@@ -873,6 +963,29 @@ fn validate_scope(
                 validate_scope(block, state, linter_info, step_breakers);
                 state.exit_loop();
             }
+            Expr::FormExpr(form) => {
+                register_flow_breaker(step_breakers, StepBreakers::HOLD(form.interval));
+
+                if state.in_function > 0 {
+                    linter_info.errors.push(gen_error_info(
+                        Position::new(form.interval.to_owned(), linter_info.flow_name),
+                        convert_error_from_interval(
+                            Span::new(linter_info.raw_flow),
+                            ERROR_FORM_IN_FN.to_owned(),
+                            form.interval.to_owned(),
+                        ),
+                    ));
+                }
+
+                for field in form.fields.iter() {
+                    let options = [&field.validate, &field.error, &field.skip];
+
+                    validate_expr_literals(&field.ask, state, linter_info);
+                    for expr in options.iter().copied().flatten() {
+                        validate_expr_literals(expr, state, linter_info);
+                    }
+                }
+            }
             _ => {}
         }
     }
@@ -6,6 +6,7 @@ pub mod parse_closure;
 pub mod parse_comments;
 pub mod parse_constant;
 pub mod parse_foreach;
+pub mod parse_form;
 pub mod parse_functions;
 pub mod parse_goto;
 pub mod parse_idents;
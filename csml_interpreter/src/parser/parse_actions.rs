@@ -6,6 +6,7 @@ use crate::parser::{
     operator::parse_operator,
     parse_comments::comment,
     parse_foreach::parse_foreach,
+    parse_form::parse_form,
     parse_goto::parse_goto,
     parse_idents::{parse_idents_assignation, parse_idents_usage},
     parse_if::parse_if,
@@ -429,6 +430,7 @@ where
         parse_if,
         parse_foreach,
         parse_while,
+        parse_form,
         // only accessible inside foreach or if scopes
         parse_break,
         parse_continue,
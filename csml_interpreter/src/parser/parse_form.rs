@@ -0,0 +1,178 @@
+use crate::data::{
+    ast::{Expr, Form, FormField},
+    tokens::*,
+};
+use crate::error_format::{
+    gen_nom_failure, ERROR_FORM_ASK, ERROR_FORM_FIELD, ERROR_FORM_OPTION, ERROR_FORM_RETRIES,
+    ERROR_FORM_TYPE,
+};
+use crate::parser::{
+    operator::parse_operator,
+    parse_braces::{parse_l_brace, parse_r_brace},
+    parse_comments::comment,
+    parse_idents::parse_idents_assignation,
+    tools::{get_interval, get_string, get_tag},
+};
+use nom::{
+    bytes::complete::tag,
+    character::complete::digit1,
+    combinator::{cut, opt},
+    error::{ContextError, ParseError},
+    multi::many0,
+    sequence::preceded,
+    *,
+};
+
+////////////////////////////////////////////////////////////////////////////////
+// DATA STRUCTURE
+////////////////////////////////////////////////////////////////////////////////
+
+enum FieldOption {
+    Ask(Expr),
+    Validate(Expr),
+    Retries(usize),
+    Error(Expr),
+    Skip(Expr),
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PRIVATE FUNCTIONS
+////////////////////////////////////////////////////////////////////////////////
+
+fn parse_retries<'a, E>(s: Span<'a>) -> IResult<Span<'a>, FieldOption, E>
+where
+    E: ParseError<Span<'a>> + ContextError<Span<'a>>,
+{
+    let (rest, retries) = match preceded(comment, digit1::<Span<'a>, E>)(s) {
+        Ok(value) => value,
+        Err(_) => return Err(gen_nom_failure(s, ERROR_FORM_RETRIES)),
+    };
+
+    match retries.fragment().parse::<usize>() {
+        Ok(retries) if retries > 0 => Ok((rest, FieldOption::Retries(retries))),
+        _ => Err(gen_nom_failure(s, ERROR_FORM_RETRIES)),
+    }
+}
+
+fn parse_field_option<'a, E>(s: Span<'a>) -> IResult<Span<'a>, FieldOption, E>
+where
+    E: ParseError<Span<'a>> + ContextError<Span<'a>>,
+{
+    let (rest, name) = preceded(comment, get_string)(s)?;
+
+    match name.as_str() {
+        FORM_ASK => {
+            let (rest, expr) = cut(parse_operator)(rest)?;
+            Ok((rest, FieldOption::Ask(expr)))
+        }
+        FORM_VALIDATE => {
+            let (rest, expr) = cut(parse_operator)(rest)?;
+            Ok((rest, FieldOption::Validate(expr)))
+        }
+        FORM_ERROR => {
+            let (rest, expr) = cut(parse_operator)(rest)?;
+            Ok((rest, FieldOption::Error(expr)))
+        }
+        FORM_RETRIES => parse_retries(rest),
+        FORM_SKIP => {
+            let (rest, value) = cut(preceded(comment, get_string))(rest)?;
+            let (rest, ..) = cut(get_tag(value, IF))(rest)?;
+            let (rest, expr) = cut(parse_operator)(rest)?;
+
+            Ok((rest, FieldOption::Skip(expr)))
+        }
+        _ => Err(gen_nom_failure(s, ERROR_FORM_OPTION)),
+    }
+}
+
+fn parse_field_type<'a, E>(s: Span<'a>) -> IResult<Span<'a>, String, E>
+where
+    E: ParseError<Span<'a>> + ContextError<Span<'a>>,
+{
+    let (s, _) = preceded(comment, tag(COLON))(s)?;
+    let (rest, field_type) = cut(preceded(comment, get_string))(s)?;
+
+    if !FORM_TYPES.contains(&field_type.as_str()) {
+        return Err(gen_nom_failure(s, ERROR_FORM_TYPE));
+    }
+
+    Ok((rest, field_type))
+}
+
+fn parse_form_field<'a, E>(s: Span<'a>) -> IResult<Span<'a>, FormField, E>
+where
+    E: ParseError<Span<'a>> + ContextError<Span<'a>>,
+{
+    let (s, name) = parse_idents_assignation(s)?;
+    let (s, field_type) = opt(parse_field_type)(s)?;
+
+    let (s, _) = match preceded(comment, parse_l_brace::<E>)(s) {
+        Ok(value) => value,
+        Err(_) => return Err(gen_nom_failure(s, ERROR_FORM_FIELD)),
+    };
+    let (s, options) = many0(parse_field_option)(s)?;
+    let (end, _) = preceded(comment, parse_r_brace)(s)?;
+
+    let mut ask = None;
+    let mut validate = None;
+    let mut retries = None;
+    let mut error = None;
+    let mut skip = None;
+
+    for option in options {
+        match option {
+            FieldOption::Ask(expr) => ask = Some(Box::new(expr)),
+            FieldOption::Validate(expr) => validate = Some(Box::new(expr)),
+            FieldOption::Retries(value) => retries = Some(value),
+            FieldOption::Error(expr) => error = Some(Box::new(expr)),
+            FieldOption::Skip(expr) => skip = Some(Box::new(expr)),
+        }
+    }
+
+    let ask = match ask {
+        Some(ask) => ask,
+        None => return Err(gen_nom_failure(s, ERROR_FORM_ASK)),
+    };
+
+    let field = FormField {
+        name,
+        field_type: field_type.unwrap_or_else(|| TEXT_TYPE.to_owned()),
+        ask,
+        validate,
+        retries,
+        error,
+        skip,
+    };
+
+    Ok((end, field))
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// PUBLIC FUNCTION
+////////////////////////////////////////////////////////////////////////////////
+
+pub fn parse_form<'a, E>(s: Span<'a>) -> IResult<Span<'a>, Expr, E>
+where
+    E: ParseError<Span<'a>> + ContextError<Span<'a>>,
+{
+    let (s, name) = preceded(comment, get_string)(s)?;
+    let (s, mut interval) = get_interval(s)?;
+    let (s, ..) = get_tag(name, FORM)(s)?;
+
+    let (s, name) = cut(parse_idents_assignation)(s)?;
+    let (s, _) = preceded(comment, parse_l_brace)(s)?;
+    let (s, fields) = many0(parse_form_field)(s)?;
+    let (s, _) = preceded(comment, parse_r_brace)(s)?;
+
+    let (s, end) = get_interval(s)?;
+    interval.add_end(end);
+
+    Ok((
+        s,
+        Expr::FormExpr(Form {
+            name,
+            fields,
+            interval,
+        }),
+    ))
+}
@@ -54,6 +54,12 @@ pub fn count_commands(command: &mut Expr, index: &mut usize, info: &mut Instruct
             info.index = *index;
             *index = *index + 1
         }
+        // the index before a form is left unused, so a form starting a block does not
+        // share the index of the block and the block is not skipped when the form resumes
+        Expr::FormExpr(..) => {
+            info.index = *index + 1;
+            *index = *index + 2
+        }
 
         Expr::IfExpr(if_statement) => {
             info.index = *index;
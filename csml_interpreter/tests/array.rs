@@ -560,3 +560,209 @@ fn array_reduce_index() {
 
     assert_eq!(v1, v2)
 }
+
+#[test]
+fn array_sort() {
+    let data = r#"{"memories":[], "messages":[{"content":[1, 2, 3], "content_type":"array"}, {"content":["a", "b", "c"], "content_type":"array"}, {"content":["a", "c", "b"], "content_type":"array"}]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "array_sort",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/array.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn array_unique() {
+    let data = r#"{"memories":[], "messages":[{"content":[1, 2, 3], "content_type":"array"}, {"content":[2, 4], "content_type":"array"}, {"content":[1, 3], "content_type":"array"}]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "array_unique",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/array.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn array_group_by() {
+    let data = r#"{"memories":[], "messages":[{"content":[1, 3, 5], "content_type":"array"}, {"content":[2, 4], "content_type":"array"}]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "array_group_by",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/array.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn array_zip_chunk() {
+    let data = r#"{"memories":[], "messages":[{"content":[[1, "a"], [2, "b"]], "content_type":"array"}, {"content":[[1, 2], [3, 4], [5]], "content_type":"array"}]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "array_zip_chunk",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/array.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn array_predicates() {
+    let data = r#"{"memories":[], "messages":[{"content":{"text":"1"}, "content_type":"text"}, {"content":{"text":"-1"}, "content_type":"text"}, {"content":{"text":"true"}, "content_type":"text"}, {"content":{"text":"false"}, "content_type":"text"}]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "array_predicates",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/array.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn array_predicates_short_circuit() {
+    let data = r#"{"memories":[], "messages":[{"content":{"text":"5"}, "content_type":"text"}, {"content":{"text":"8"}, "content_type":"text"}, {"content":{"text":"true"}, "content_type":"text"}, {"content":{"text":"5"}, "content_type":"text"}, {"content":{"text":"8"}, "content_type":"text"}, {"content":{"text":"false"}, "content_type":"text"}]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "array_predicates_short_circuit",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/array.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn array_sort_mixed() {
+    let data = r#"{"memories":[], "messages":[{"content":[[2], true, null, 1.5, 3, {"k": 1}, "a"], "content_type":"array"}]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "array_sort_mixed",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/array.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn array_min_max_sum() {
+    let data = r#"{"memories":[], "messages":[{"content":{"text":"1"}, "content_type":"text"}, {"content":{"text":"7"}, "content_type":"text"}, {"content":{"text":"6"}, "content_type":"text"}, {"content":{"text":"3.5"}, "content_type":"text"}]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "array_min_max_sum",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/array.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn array_sum_invalid() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "array_sum_invalid",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/array.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("[sum]"));
+}
@@ -0,0 +1,85 @@
+mod support;
+
+use csml_interpreter::data::context::Context;
+use csml_interpreter::data::event::Event;
+use std::collections::HashMap;
+
+use crate::support::tools::format_message;
+use crate::support::tools::message_to_json_value;
+
+use serde_json::Value;
+
+fn run_step(step: &str) -> Value {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            step,
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/built-in/crypto.csml",
+    );
+
+    message_to_json_value(msg)
+}
+
+#[test]
+fn ok_crypto_encrypt() {
+    let data =
+        r#"{"memories":[], "messages":[{"content":{"text":"hello"}, "content_type":"text"}]}"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(run_step("encrypt"), v2)
+}
+
+#[test]
+fn ko_crypto_decrypt() {
+    let msg = run_step("decrypt_invalid");
+
+    let message = msg["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("decryption failed"));
+}
+
+#[test]
+fn ok_crypto_sign() {
+    let data = r#"{"memories":[], "messages":[
+        {"content":{"text":"true"}, "content_type":"text"},
+        {"content":{"text":"false"}, "content_type":"text"}
+    ]}"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(run_step("sign"), v2)
+}
+
+#[test]
+fn ok_crypto_password() {
+    let data = r#"{"memories":[], "messages":[
+        {"content":{"text":"true"}, "content_type":"text"},
+        {"content":{"text":"false"}, "content_type":"text"},
+        {"content":{"text":"true"}, "content_type":"text"},
+        {"content":{"text":"true"}, "content_type":"text"},
+        {"content":{"text":"true"}, "content_type":"text"},
+        {"content":{"text":"true"}, "content_type":"text"},
+        {"content":{"text":"32"}, "content_type":"text"},
+        {"content":{"text":"24"}, "content_type":"text"}
+    ]}"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(run_step("password"), v2)
+}
+
+#[test]
+fn ko_crypto_password_cost() {
+    let msg = run_step("password_cost");
+    let message = msg["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("with a supported cost"));
+}
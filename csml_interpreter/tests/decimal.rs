@@ -0,0 +1,115 @@
+mod support;
+
+use csml_interpreter::data::context::Context;
+use csml_interpreter::data::event::Event;
+use std::collections::HashMap;
+
+use crate::support::tools::format_message;
+use crate::support::tools::message_to_json_value;
+
+use serde_json::Value;
+
+fn decimal_step(step: &str) -> Value {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            step,
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/decimal.csml",
+    );
+
+    message_to_json_value(msg)
+}
+
+#[test]
+fn decimal_operations() {
+    let data = r#"{
+        "memories":[
+        ],
+        "messages":[
+            {"content":{"text": "0.3"}, "content_type":"text"},
+            {"content":{"text": "59.97"}, "content_type":"text"},
+            {"content":{"text": "2.5"}, "content_type":"text"},
+            {"content":{"text": "1.5"}, "content_type":"text"},
+            {"content":{"text": "1.00"}, "content_type":"text"},
+            {"content":{"text": "true"}, "content_type":"text"},
+            {"content":{"text": "true"}, "content_type":"text"},
+            {"content":{"text": "decimal"}, "content_type":"text"}
+        ]}"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(decimal_step("start"), v2)
+}
+
+#[test]
+fn decimal_rounding() {
+    let data = r#"{
+        "memories":[
+        ],
+        "messages":[
+            {"content":{"text": "2.34"}, "content_type":"text"},
+            {"content":{"text": "2.35"}, "content_type":"text"},
+            {"content":{"text": "3"}, "content_type":"text"},
+            {"content":{"text": "19.99"}, "content_type":"text"},
+            {"content":{"text": "-1"}, "content_type":"text"}
+        ]}"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(decimal_step("rounding"), v2)
+}
+
+#[test]
+fn decimal_memory() {
+    let data = r#"{
+        "memories":[
+            {"key": "total", "value": {"_decimal": "0.30"}}
+        ],
+        "messages":[
+            {"content":{"text": "1.30"}, "content_type":"text"},
+            {"content":{"text": "{\"price\":\"0.30\"}"}, "content_type":"text"}
+        ]}"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(decimal_step("memory"), v2)
+}
+
+#[test]
+fn decimal_invalid() {
+    let msg = decimal_step("invalid");
+
+    let message = msg["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("Decimal builtin"));
+}
+
+#[test]
+fn decimal_exponent_out_of_range() {
+    let msg = decimal_step("exponent");
+    let message = msg["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("decimal exponent must be between -1000 and 1000"));
+
+    let msg = decimal_step("exponent_operation");
+    let message = msg["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("illegal operation"));
+}
+
+#[test]
+fn decimal_scale_out_of_range() {
+    for step in ["scale", "builtin_scale"].iter() {
+        let msg = decimal_step(step);
+        let message = msg["messages"][0]["content"]["error"].as_str().unwrap();
+
+        assert!(message.contains("decimal scale must be an int between 0 and 100"));
+    }
+}
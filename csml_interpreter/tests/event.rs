@@ -2,6 +2,7 @@ mod support;
 
 use csml_interpreter::data::context::Context;
 use csml_interpreter::data::event::Event;
+use csml_interpreter::data::{Intent, NluEntity, NluResult, TriggerKind, TriggerMatch};
 
 use crate::support::tools::format_message;
 use crate::support::tools::message_to_json_value;
@@ -402,3 +403,182 @@ fn event_types() {
         }
     }
 }
+
+#[test]
+fn event_step_trigger() {
+    let data = r#"{
+        "memories":[
+        ],
+        "messages":[
+            {"content":{"text": "helo"}, "content_type":"text"},
+            {"content":{"text": "fuzzy"}, "content_type":"text"}
+        ]}"#;
+
+    let mut map = serde_json::Map::new();
+    map.insert(
+        "text".to_owned(),
+        serde_json::Value::String("hello".to_owned()),
+    );
+
+    let mut event = Event::new("text", "hello", serde_json::Value::Object(map));
+    event.trigger = Some(TriggerMatch {
+        flow: "flow".to_owned(),
+        value: "helo".to_owned(),
+        kind: TriggerKind::Fuzzy,
+        score: 0.8,
+        priority: 0,
+    });
+
+    let msg = format_message(
+        event,
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "step_trigger",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/event.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn event_step_trigger_content() {
+    let data = r#"{
+        "memories":[
+        ],
+        "messages":[
+            {"content":{"text": "from the channel"}, "content_type":"text"}
+        ]}"#;
+
+    // the trigger key of the event content is not replaced by the flow trigger
+    let mut event = Event::new(
+        "text",
+        "hello",
+        serde_json::json!({"text": "hello", "trigger": "from the channel"}),
+    );
+    event.trigger = Some(TriggerMatch {
+        flow: "flow".to_owned(),
+        value: "hello".to_owned(),
+        kind: TriggerKind::Exact,
+        score: 1.0,
+        priority: 0,
+    });
+
+    let msg = format_message(
+        event,
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "step_trigger_content",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/event.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn event_step_intent() {
+    let data = r#"{
+        "memories":[
+        ],
+        "messages":[
+            {"content":{"text": "order_pizza"}, "content_type":"text"},
+            {"content":{"text": "pizza"}, "content_type":"text"},
+            {"content":{"text": "order_pizza"}, "content_type":"text"},
+            {"content":{"text": "pizza"}, "content_type":"text"}
+        ]}"#;
+
+    let mut event = Event::new(
+        "text",
+        "I want a pizza",
+        serde_json::json!({"text": "I want a pizza"}),
+    );
+    let intent = Intent {
+        name: "order_pizza".to_owned(),
+        confidence: 0.92,
+    };
+    event.nlu = Some(NluResult {
+        intent: Some(intent.clone()),
+        intents: vec![intent],
+        entities: vec![NluEntity {
+            entity: "food".to_owned(),
+            value: serde_json::json!("pizza"),
+            confidence: Some(0.99),
+            start: Some(9),
+            end: Some(14),
+        }],
+    });
+
+    let msg = format_message(
+        event,
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "step_intent",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/event.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn event_step_nlu_content() {
+    let data = r#"{
+        "memories":[
+        ],
+        "messages":[
+            {"content":{"text": "from the channel"}, "content_type":"text"},
+            {"content":{"text": "greet"}, "content_type":"text"}
+        ]}"#;
+
+    // the nlu and intent keys of the event content are not replaced by the annotations
+    let mut event = Event::new(
+        "text",
+        "hello",
+        serde_json::json!({"text": "hello", "nlu": "from the channel", "intent": "greet"}),
+    );
+    event.nlu = Some(NluResult::default());
+
+    let msg = format_message(
+        event,
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "step_nlu_content",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/event.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
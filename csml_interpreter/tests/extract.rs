@@ -0,0 +1,158 @@
+mod support;
+
+use csml_interpreter::data::context::Context;
+use csml_interpreter::data::event::Event;
+use std::collections::HashMap;
+
+use crate::support::tools::format_message;
+use crate::support::tools::message_to_json_value;
+
+use serde_json::Value;
+
+#[test]
+fn ok_extract() {
+    let data = r#"
+        {"messages":[
+            {"content":["jane.doe@csml.dev"],"content_type":"array"},
+            {"content":["+33612345678"],"content_type":"array"},
+            {"content":["https://www.csml.dev"],"content_type":"array"},
+            {"content":[3],"content_type":"array"},
+            {"content":[{"value": 12.5, "currency": "EUR"}],"content_type":"array"},
+            {"content":{"text": "2022-07-14"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "start",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/built-in/extract.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ok_extract_timezone() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "1657749600000"},"content_type":"text"},
+            {"content":{"text": "2022-07-14 00:00"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "timezone",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/built-in/extract.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ok_extract_relative_date() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "true"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "relative",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/built-in/extract.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ok_extract_next_weekday() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "5"},"content_type":"text"},
+            {"content":{"text": "true"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "next_weekday",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/built-in/extract.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ok_extract_phones() {
+    let data = r#"
+        {"messages":[
+            {"content":["0612345678", "+14155550123"],"content_type":"array"},
+            {"content":[12345678],"content_type":"array"}
+        ],
+        "memories":[]
+        }"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "phones",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/built-in/extract.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
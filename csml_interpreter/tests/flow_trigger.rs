@@ -0,0 +1,159 @@
+use csml_interpreter::data::{CsmlFlow, CsmlTrigger, Intent, TriggerKind};
+
+fn init_flow(triggers: Vec<CsmlTrigger>) -> CsmlFlow {
+    let mut flow = CsmlFlow::new(
+        "id",
+        "flow",
+        "start: say \"hello\"",
+        vec!["Hello".to_owned()],
+    );
+    flow.triggers = triggers;
+
+    flow
+}
+
+#[test]
+fn ok_trigger_command() {
+    let flow = init_flow(vec![]);
+
+    let trigger = flow
+        .search_trigger("hello", None, &serde_json::json!({}))
+        .unwrap();
+
+    assert_eq!(trigger.kind, TriggerKind::Exact);
+    assert_eq!(trigger.score, 1.0);
+    assert!(flow
+        .search_trigger("hello you", None, &serde_json::json!({}))
+        .is_none());
+}
+
+#[test]
+fn ok_trigger_regex() {
+    let flow = init_flow(vec![CsmlTrigger::new(
+        "^(order|buy) .+$",
+        TriggerKind::Regex,
+    )]);
+
+    let trigger = flow
+        .search_trigger("buy a pizza", None, &serde_json::json!({}))
+        .unwrap();
+
+    assert_eq!(trigger.kind, TriggerKind::Regex);
+    assert!(flow
+        .search_trigger("sell", None, &serde_json::json!({}))
+        .is_none());
+}
+
+#[test]
+fn ok_trigger_fuzzy() {
+    let mut fuzzy = CsmlTrigger::new("pizza", TriggerKind::Fuzzy);
+    fuzzy.threshold = Some(0.7);
+    let flow = init_flow(vec![fuzzy]);
+
+    let trigger = flow
+        .search_trigger("piza", None, &serde_json::json!({}))
+        .unwrap();
+
+    assert_eq!(trigger.kind, TriggerKind::Fuzzy);
+    assert_eq!(trigger.score, 0.8);
+    assert!(flow
+        .search_trigger("pasta", None, &serde_json::json!({}))
+        .is_none());
+}
+
+#[test]
+fn ok_trigger_priority() {
+    let mut regex = CsmlTrigger::new("^hel", TriggerKind::Regex);
+    regex.priority = 1;
+    regex.weight = 0.5;
+    let flow = init_flow(vec![regex]);
+
+    let trigger = flow
+        .search_trigger("hello", None, &serde_json::json!({}))
+        .unwrap();
+
+    assert_eq!(trigger.kind, TriggerKind::Regex);
+    assert_eq!(trigger.score, 0.5);
+}
+
+#[test]
+fn ok_trigger_conditions() {
+    let mut trigger = CsmlTrigger::new("start", TriggerKind::Exact);
+    let mut conditions = serde_json::Map::new();
+    conditions.insert("channel".to_owned(), serde_json::json!(["web", "app"]));
+    trigger.conditions = Some(conditions);
+    let flow = init_flow(vec![trigger]);
+
+    assert!(flow
+        .search_trigger("start", None, &serde_json::json!({"channel": "app"}))
+        .is_some());
+    assert!(flow
+        .search_trigger("start", None, &serde_json::json!({"channel": "sms"}))
+        .is_none());
+    assert!(flow
+        .search_trigger("start", None, &serde_json::json!({}))
+        .is_none());
+}
+
+#[test]
+fn ok_trigger_intent() {
+    let mut trigger = CsmlTrigger::new("order_pizza", TriggerKind::Intent);
+    trigger.threshold = Some(0.6);
+    let flow = init_flow(vec![trigger]);
+
+    let intent = Intent {
+        name: "order_pizza".to_owned(),
+        confidence: 0.9,
+    };
+    let trigger = flow
+        .search_trigger(
+            "I'd like a margherita",
+            Some(&intent),
+            &serde_json::json!({}),
+        )
+        .unwrap();
+
+    assert_eq!(trigger.kind, TriggerKind::Intent);
+    assert_eq!(trigger.score, 0.9);
+
+    let intent = Intent {
+        name: "order_pizza".to_owned(),
+        confidence: 0.4,
+    };
+    assert!(flow
+        .search_trigger("pizza?", Some(&intent), &serde_json::json!({}))
+        .is_none());
+}
+
+#[test]
+fn ok_trigger_regex_invalid() {
+    let flow = init_flow(vec![
+        CsmlTrigger::new("(order", TriggerKind::Regex),
+        CsmlTrigger::new("^order", TriggerKind::Regex),
+    ]);
+
+    for _ in 0..2 {
+        let trigger = flow
+            .search_trigger("order a pizza", None, &serde_json::json!({}))
+            .unwrap();
+
+        assert_eq!(trigger.value, "^order");
+    }
+}
+
+#[test]
+fn ok_trigger_regex_event() {
+    let flow = init_flow(vec![CsmlTrigger::new("^buy", TriggerKind::Regex)]);
+
+    let trigger = flow
+        .search_regex(&regex::Regex::new("^Hel").unwrap())
+        .unwrap();
+
+    assert_eq!(trigger.flow, "flow");
+    assert_eq!(trigger.value, "Hello");
+    assert_eq!(trigger.kind, TriggerKind::Regex);
+    // only the exact triggers are matched by the regex of the event
+    assert!(flow
+        .search_regex(&regex::Regex::new("buy").unwrap())
+        .is_none());
+}
@@ -0,0 +1,103 @@
+mod support;
+
+use csml_interpreter::data::event::Event;
+use csml_interpreter::data::hold::Hold;
+use csml_interpreter::data::primitive::PrimitiveString;
+use csml_interpreter::data::{Context, Interval, Literal};
+use std::collections::HashMap;
+
+use crate::support::tools::format_message;
+use crate::support::tools::message_to_json_value;
+
+use serde_json::Value;
+
+fn send_text(
+    step: &str,
+    text: &str,
+    hold: Option<Hold>,
+    metadata: HashMap<String, Literal>,
+) -> (Value, Option<Hold>) {
+    let msg = format_message(
+        Event::new("text", text, serde_json::json!({ "text": text })),
+        Context::new(HashMap::new(), metadata, None, hold, step, "flow", None),
+        "CSML/basic_test/form.csml",
+    );
+    let hold = msg.hold.clone();
+
+    (message_to_json_value(msg), hold)
+}
+
+fn texts(value: &Value) -> Vec<String> {
+    value["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|message| message["content"]["text"].to_string())
+        .collect()
+}
+
+#[test]
+fn ok_form() {
+    let (msg, hold) = send_text("start", "", None, HashMap::new());
+    assert_eq!(texts(&msg), vec!["\"What is your name?\""]);
+    assert!(hold.is_some());
+
+    let (msg, hold) = send_text("start", "Jane", hold, HashMap::new());
+    assert_eq!(texts(&msg), vec!["\"How old are you?\""]);
+
+    let (msg, hold) = send_text("start", "twenty", hold, HashMap::new());
+    assert_eq!(
+        texts(&msg),
+        vec!["\"You must be an adult\"", "\"How old are you?\""]
+    );
+
+    let (msg, hold) = send_text("start", "42", hold, HashMap::new());
+    assert_eq!(texts(&msg), vec!["\"What is your email?\""]);
+
+    let (msg, hold) = send_text("start", "not an email", hold, HashMap::new());
+    assert_eq!(texts(&msg), vec!["\"What is your email?\""]);
+
+    let (msg, hold) = send_text("start", "jane@example.com", hold, HashMap::new());
+    assert_eq!(texts(&msg), vec!["\"Jane 42 jane@example.com\""]);
+    assert!(hold.is_none());
+}
+
+#[test]
+fn ok_form_retries_and_skip() {
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "email".to_owned(),
+        PrimitiveString::get_literal("jane@example.com", Interval::default()),
+    );
+
+    let (_, hold) = send_text("start", "", None, metadata.clone());
+    let (_, hold) = send_text("start", "Jane", hold, metadata.clone());
+    let (msg, hold) = send_text("start", "16", hold, metadata.clone());
+    assert_eq!(
+        texts(&msg),
+        vec!["\"You must be an adult\"", "\"How old are you?\""]
+    );
+
+    // the last retry leaves the field to null and the email is skipped
+    let (msg, hold) = send_text("start", "17", hold, metadata);
+    assert_eq!(
+        texts(&msg),
+        vec!["\"You must be an adult\"", "\"Jane Null Null\""]
+    );
+    assert!(hold.is_none());
+}
+
+#[test]
+fn ok_form_in_if() {
+    let (msg, hold) = send_text("in_if", "", None, HashMap::new());
+    assert_eq!(texts(&msg), vec!["\"How many?\""]);
+
+    let (msg, hold) = send_text("in_if", "a few", hold, HashMap::new());
+    assert_eq!(
+        texts(&msg),
+        vec!["\"Please give a number\"", "\"How many?\""]
+    );
+
+    let (msg, _) = send_text("in_if", "3", hold, HashMap::new());
+    assert_eq!(texts(&msg), vec!["\"6\""]);
+}
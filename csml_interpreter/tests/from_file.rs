@@ -0,0 +1,126 @@
+mod support;
+
+use csml_interpreter::data::context::Context;
+use csml_interpreter::data::csml_bot::CsmlBot;
+use csml_interpreter::data::csml_flow::CsmlFlow;
+use csml_interpreter::data::event::Event;
+use csml_interpreter::data::BotFiles;
+use csml_interpreter::{interpret, load_components};
+use std::collections::HashMap;
+
+use crate::support::tools::message_to_json_value;
+use crate::support::tools::read_file;
+
+use serde_json::Value;
+
+fn init_files() -> HashMap<String, String> {
+    let mut files = HashMap::new();
+
+    files.insert(
+        "products.json".to_owned(),
+        r#"[{"name": "apple", "price": 1}, {"name": "pear", "price": 2}]"#.to_owned(),
+    );
+    files.insert(
+        "config.yaml".to_owned(),
+        "shop:\n  currency: EUR\n".to_owned(),
+    );
+    files.insert(
+        "stores.csv".to_owned(),
+        "city,opening\nParis,9am\nLyon,10am\n".to_owned(),
+    );
+
+    files
+}
+
+fn format_message(step: &str, files_cache: Option<String>) -> Value {
+    let content = read_file("CSML/basic_test/built-in/from_file.csml".to_owned()).unwrap();
+    let flow = CsmlFlow::new("id", "flow", &content, Vec::default());
+
+    let mut bot = CsmlBot::new(
+        "id",
+        "bot",
+        None,
+        vec![flow],
+        Some(load_components().unwrap()),
+        None,
+        "flow",
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    bot.files = Some(init_files());
+    bot.files_cache = files_cache;
+
+    let msg = interpret(
+        bot,
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            step,
+            "flow",
+            None,
+        ),
+        Event::new("payload", "", serde_json::json!({})),
+        None,
+    );
+
+    message_to_json_value(msg)
+}
+
+#[test]
+fn ok_from_file() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "2"},"content_type":"text"},
+            {"content":{"text": "pear"},"content_type":"text"},
+            {"content":{"text": "EUR"},"content_type":"text"},
+            {"content":{"text": "Paris"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(format_message("start", None), v2)
+}
+
+#[test]
+fn ok_from_file_cache() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "2"},"content_type":"text"},
+            {"content":{"text": "pear"},"content_type":"text"},
+            {"content":{"text": "EUR"},"content_type":"text"},
+            {"content":{"text": "Paris"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    // the files are parsed once and stored encoded with the bot version
+    let files_cache = BotFiles::from_files(&init_files()).unwrap().encode();
+
+    assert_eq!(format_message("start", Some(files_cache)), v2)
+}
+
+#[test]
+fn ko_from_file_missing() {
+    let msg = format_message("missing", None);
+
+    let message = msg["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("unknown.json"));
+}
+
+#[test]
+fn ko_from_file_invalid() {
+    let mut files = HashMap::new();
+    files.insert("products.json".to_owned(), "[{".to_owned());
+
+    assert!(BotFiles::from_files(&files).is_err());
+}
@@ -1,8 +1,16 @@
 mod support;
 
-use csml_interpreter::data::context::Context;
+use csml_interpreter::data::context::{get_hashmap_from_mem, Context};
 use csml_interpreter::data::event::Event;
+use csml_interpreter::data::primitive::PrimitiveString;
+use csml_interpreter::data::Interval;
+use csml_interpreter::interpreter::builtins::oauth2::{OAuth2Client, OAuth2Clients, OAuth2Token};
 use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
 
 use crate::support::tools::format_message;
 use crate::support::tools::message_to_json_value;
@@ -563,3 +571,641 @@ fn http_auth_0() {
 
     assert_eq!(v1, v2)
 }
+
+#[test]
+fn ok_oauth2() {
+    let data = r#"{
+        "memories":[],
+        "messages":[
+            {
+                "content":{
+                    "header":{
+                        "Accept":"application/json,text/*",
+                        "Content-Type":"application/json",
+                        "User-Agent": "csml/v1"
+                    },
+                    "oauth2":{
+                        "token_url":"https://auth.clevy.io/token",
+                        "client_id":"csml-client",
+                        "scope":"read",
+                        "client":"ab67ef12d306048126a12ef211f7c5f7affb737268fac993d4c3164fcdca0d59"
+                    },
+                    "method":"get",
+                    "url":"https://clevy.io"
+                },
+                "content_type":"http"
+            }
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "oauth2_0",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    // the client secret is never part of the HTTP object
+    assert!(!v1.to_string().contains("s3cr3t"));
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ko_oauth2_missing_secret() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "oauth2_1",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("[oauth2]"));
+}
+
+#[test]
+fn ok_oauth2_updated_clients() {
+    let context = Context::new(
+        HashMap::new(),
+        HashMap::new(),
+        None,
+        None,
+        "oauth2_0",
+        "flow",
+        None,
+    );
+    let clients = context.oauth2_clients.clone();
+
+    format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        context,
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let updated = clients.take_updated();
+    assert_eq!(updated.len(), 1);
+    assert_eq!(
+        updated[0].0,
+        "ab67ef12d306048126a12ef211f7c5f7affb737268fac993d4c3164fcdca0d59"
+    );
+    assert_eq!(updated[0].1.client_secret, "s3cr3t");
+    // the clients are only saved once
+    assert!(clients.take_updated().is_empty());
+}
+
+#[test]
+fn ok_oauth2_registered_client_loaded() {
+    let mut context = Context::new(
+        HashMap::new(),
+        HashMap::new(),
+        None,
+        None,
+        "oauth2_0",
+        "flow",
+        None,
+    );
+    // the client was saved by a previous request
+    context.oauth2_clients = OAuth2Clients::with_loader(|_| {
+        Some(OAuth2Client {
+            token_url: "https://auth.clevy.io/token".to_owned(),
+            client_id: "csml-client".to_owned(),
+            client_secret: "s3cr3t".to_owned(),
+            scope: Some("read".to_owned()),
+            token: None,
+        })
+    });
+    let clients = context.oauth2_clients.clone();
+
+    format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        context,
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    assert!(clients.take_updated().is_empty());
+}
+
+fn get_oauth2_memories(url: &str) -> HashMap<String, csml_interpreter::data::Literal> {
+    get_hashmap_from_mem(
+        &serde_json::json!({
+            "http_mem": {
+                "_content": {
+                    "header": {"Accept": "application/json,text/*"},
+                    "oauth2": {
+                        "token_url": "https://auth.clevy.io/token",
+                        "client_id": "csml-client",
+                        "client": "remembered-client"
+                    },
+                    "method": "get",
+                    "url": url
+                },
+                "_content_type": "http"
+            }
+        }),
+        "flow",
+    )
+}
+
+#[test]
+fn ok_oauth2_loaded_client() {
+    let (url, requests) = start_recording_server();
+
+    let mut context = Context::new(
+        get_oauth2_memories(&url),
+        HashMap::new(),
+        None,
+        None,
+        "oauth2_2",
+        "flow",
+        None,
+    );
+    // client loaded by the engine from the state of the bot
+    context.oauth2_clients = OAuth2Clients::with_loader(|id| match id {
+        "remembered-client" => Some(OAuth2Client {
+            token_url: "https://auth.clevy.io/token".to_owned(),
+            client_id: "csml-client".to_owned(),
+            client_secret: "s3cr3t".to_owned(),
+            scope: None,
+            token: Some(OAuth2Token {
+                access_token: "saved-token".to_owned(),
+                expires_at: i64::MAX,
+            }),
+        }),
+        _ => None,
+    });
+
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        context,
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    assert_eq!(
+        v1["messages"][0]["content"],
+        serde_json::json!({"ok": true})
+    );
+
+    let requests = requests.lock().unwrap();
+    assert!(requests[0].contains("authorization: bearer saved-token"));
+}
+
+#[test]
+fn ko_oauth2_unknown_client() {
+    let (url, requests) = start_recording_server();
+
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            get_oauth2_memories(&url),
+            HashMap::new(),
+            None,
+            None,
+            "oauth2_2",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("OAuth2 client not found"));
+    assert!(requests.lock().unwrap().is_empty());
+}
+
+#[test]
+fn ok_http_options() {
+    let data = r#"{
+        "memories":[],
+        "messages":[
+            {
+                "content":{
+                    "header":{
+                        "Accept":"application/json,text/*",
+                        "Content-Type":"application/json",
+                        "User-Agent": "csml/v1"
+                    },
+                    "timeout":2000,
+                    "retry":{"count":3, "backoff":200},
+                    "response_type":"binary",
+                    "max_size":1024,
+                    "method":"get",
+                    "url":"https://clevy.io"
+                },
+                "content_type":"http"
+            }
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "options_0",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ko_http_retry_count() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "options_1",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("usage: retry("));
+}
+
+#[test]
+fn ko_http_response_type() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "options_2",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("usage: response_type("));
+}
+
+#[test]
+fn ok_http_form() {
+    let data = r#"{
+        "memories":[],
+        "messages":[
+            {
+                "content":{
+                    "header":{
+                        "Accept":"application/json,text/*",
+                        "Content-Type":"application/x-www-form-urlencoded",
+                        "User-Agent": "csml/v1"
+                    },
+                    "body":{"name":"csml", "tags":["bot", "dsl"]},
+                    "body_type":"form",
+                    "method":"post",
+                    "url":"https://clevy.io"
+                },
+                "content_type":"http"
+            }
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "form_0",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ok_http_multipart() {
+    let data = r#"{
+        "memories":[],
+        "messages":[
+            {
+                "content":{
+                    "header":{
+                        "Accept":"application/json,text/*",
+                        "Content-Type":"multipart/form-data",
+                        "User-Agent": "csml/v1"
+                    },
+                    "body":{
+                        "file":{
+                            "filename":"hello.txt",
+                            "content":"aGVsbG8=",
+                            "content_type":"text/plain"
+                        }
+                    },
+                    "body_type":"multipart",
+                    "method":"post",
+                    "url":"https://clevy.io"
+                },
+                "content_type":"http"
+            }
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "multipart_0",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ok_http_all_empty() {
+    let data = r#"{"memories":[], "messages":[
+        {"content":{"text":"0"}, "content_type":"text"}
+    ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "all_0",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ok_http_all_errors() {
+    let data = r#"{"memories":[], "messages":[
+        {"content":{"text":"2"}, "content_type":"text"},
+        {"content":{"text":"true"}, "content_type":"text"},
+        {"content":{"text":"true"}, "content_type":"text"}
+    ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "all_1",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ko_http_all_requests() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "all_2",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("[all]"));
+}
+
+#[test]
+fn ko_http_all_concurrency() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "all_3",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("concurrency must be an Int between 1 and"));
+}
+
+#[test]
+fn ko_http_all_method() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "all_4",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("[HTTP] only has the 'all' method"));
+}
+
+#[test]
+fn ko_app_all_endpoint() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "all_5",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("apps_endpoint is not set"));
+}
+
+/**
+ * Local server answering 503 to every call, returns its url and the number of calls
+ */
+fn start_unavailable_server() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => break,
+            };
+            counter.fetch_add(1, Ordering::SeqCst);
+
+            let mut buffer = [0; 4096];
+            let _ = stream.read(&mut buffer);
+            let _ = stream.write_all(
+                b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            );
+        }
+    });
+
+    (url, calls)
+}
+
+fn start_recording_server() -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(vec![]));
+    let recorded = requests.clone();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => break,
+            };
+
+            let mut buffer = [0; 4096];
+            let size = stream.read(&mut buffer).unwrap_or(0);
+            recorded
+                .lock()
+                .unwrap()
+                .push(String::from_utf8_lossy(&buffer[..size]).to_lowercase());
+            let _ = stream.write_all(
+                b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 11\r\nConnection: close\r\n\r\n{\"ok\":true}",
+            );
+        }
+    });
+
+    (url, requests)
+}
+
+fn run_retry_step(step: &str) -> usize {
+    let (url, calls) = start_unavailable_server();
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "url".to_owned(),
+        PrimitiveString::get_literal(&url, Interval::default()),
+    );
+
+    format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(HashMap::new(), metadata, None, None, step, "flow", None),
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    calls.load(Ordering::SeqCst)
+}
+
+#[test]
+fn ok_http_retry_post_not_retried() {
+    assert_eq!(run_retry_step("retry_0"), 1);
+}
+
+#[test]
+fn ok_http_retry_post_non_idempotent() {
+    assert_eq!(run_retry_step("retry_1"), 3);
+}
+
+#[test]
+fn ko_http_multipart_content_type() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "multipart_1",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/http.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("can not have a line break in its 'content_type'"));
+}
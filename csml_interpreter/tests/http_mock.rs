@@ -0,0 +1,116 @@
+mod support;
+
+use csml_interpreter::data::context::Context;
+use csml_interpreter::data::event::Event;
+use csml_interpreter::data::{ApiInfo, Client, HttpMock, HttpMockMode};
+use std::collections::HashMap;
+
+use crate::support::tools::format_message;
+use crate::support::tools::message_to_json_value;
+
+use serde_json::Value;
+
+fn run_step(step: &str, mode: HttpMockMode, file: &str) -> Value {
+    let mut event = Event::new("payload", "", serde_json::json!({}));
+    event.http_mock = Some(HttpMock::new(mode, file));
+
+    let api_info = ApiInfo {
+        client: Client::new("bot".to_owned(), "channel".to_owned(), "user".to_owned()),
+        apps_endpoint: "https://apps.clevy.io".to_owned(),
+    };
+
+    let msg = format_message(
+        event,
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            Some(api_info),
+            None,
+            step,
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/http_mock.csml",
+    );
+
+    message_to_json_value(msg)
+}
+
+fn replay(step: &str) -> Value {
+    run_step(
+        step,
+        HttpMockMode::Replay,
+        "CSML/basic_test/http_mock/replay.json",
+    )
+}
+
+fn stub(step: &str) -> Value {
+    run_step(
+        step,
+        HttpMockMode::Stub,
+        "CSML/basic_test/http_mock/stub.json",
+    )
+}
+
+#[test]
+fn ok_http_mock_replay() {
+    let data = r#"{"memories":[], "messages":[
+        {"content":{"text":"csml"}, "content_type":"text"},
+        {"content":{"text":"200"}, "content_type":"text"}
+    ]}"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(replay("replay_0"), v2)
+}
+
+#[test]
+fn ok_http_mock_replay_status() {
+    let msg = replay("replay_1");
+
+    let message = msg["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("status code 409"));
+}
+
+#[test]
+fn ko_http_mock_replay_unknown() {
+    let msg = replay("replay_2");
+
+    let message = msg["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("no mocked response for request [GET https://api.clevy.io/unknown]"));
+}
+
+#[test]
+fn ok_http_mock_replay_app() {
+    let data = r#"{"memories":[], "messages":[
+        {"content":{"text":"hello"}, "content_type":"text"}
+    ]}"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(replay("replay_3"), v2)
+}
+
+#[test]
+fn ok_http_mock_stub() {
+    let data = r#"{"memories":[], "messages":[
+        {"content":{"text":"42"}, "content_type":"text"},
+        {"content":{"text":"503"}, "content_type":"text"},
+        {"content":{"text":"42"}, "content_type":"text"}
+    ]}"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(stub("stub_0"), v2)
+}
+
+#[test]
+fn ko_http_mock_stub_unknown() {
+    let msg = stub("stub_1");
+
+    let message = msg["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("no mocked response for request [GET https://example.com]"));
+}
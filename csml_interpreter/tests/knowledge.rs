@@ -0,0 +1,117 @@
+mod support;
+
+use csml_interpreter::data::context::Context;
+use csml_interpreter::data::csml_bot::CsmlBot;
+use csml_interpreter::data::csml_flow::CsmlFlow;
+use csml_interpreter::data::event::Event;
+use csml_interpreter::data::{Knowledge, KnowledgeIndex};
+use csml_interpreter::{interpret, load_components};
+use std::collections::HashMap;
+
+use crate::support::tools::message_to_json_value;
+use crate::support::tools::read_file;
+
+use serde_json::Value;
+
+const KNOWLEDGE: &str = r#"id,question,answer
+1,How do I reset my password?,"Click on ""forgot password"" on the login page"
+2,What are your opening hours?,"We are open from 9am to 6pm, Monday to Friday"
+3,How do I delete my account?,Go to your account settings
+"#;
+
+fn format_message(step: &str) -> Value {
+    let content = read_file("CSML/basic_test/knowledge.csml".to_owned()).unwrap();
+    let flow = CsmlFlow::new("id", "flow", &content, Vec::default());
+
+    let mut bot = CsmlBot::new(
+        "id",
+        "bot",
+        None,
+        vec![flow],
+        Some(load_components().unwrap()),
+        None,
+        "flow",
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    bot.knowledge = Some(Knowledge::Csv(KNOWLEDGE.to_owned()));
+
+    let msg = interpret(
+        bot,
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            step,
+            "flow",
+            None,
+        ),
+        Event::new("payload", "", serde_json::json!({})),
+        None,
+    );
+
+    message_to_json_value(msg)
+}
+
+#[test]
+fn ok_knowledge_search() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "1"},"content_type":"text"},
+            {"content":{"text": "1"},"content_type":"text"},
+            {"content":{"text": "Click on \"forgot password\" on the login page"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(format_message("start"), v2)
+}
+
+#[test]
+fn ok_knowledge_search_no_match() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "0"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(format_message("no_match"), v2)
+}
+
+#[test]
+fn ko_knowledge_search_unknown_arg() {
+    let msg = format_message("unknown_arg");
+    let message = msg["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("unknown argument of the method [limit]"));
+}
+
+#[test]
+fn ko_knowledge_search_duplicate_arg() {
+    let msg = format_message("duplicate_arg");
+    let message = msg["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("argument of the method given twice [query]"));
+}
+
+#[test]
+fn ok_knowledge_index() {
+    let knowledge = Knowledge::Csv(KNOWLEDGE.to_owned());
+    let index = KnowledgeIndex::from_knowledge(&knowledge).unwrap();
+
+    // the index is stored encoded with the bot
+    let index = KnowledgeIndex::decode(&index.encode()).unwrap();
+    let results = index.search("opening hours on monday", 3);
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].0.id, Some("2".to_owned()));
+}
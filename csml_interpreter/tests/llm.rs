@@ -0,0 +1,121 @@
+mod support;
+
+use csml_interpreter::data::context::Context;
+use csml_interpreter::data::csml_bot::CsmlBot;
+use csml_interpreter::data::csml_flow::CsmlFlow;
+use csml_interpreter::data::event::Event;
+use csml_interpreter::{interpret, load_components};
+use std::collections::HashMap;
+
+use crate::support::tools::message_to_json_value;
+use crate::support::tools::read_file;
+
+use serde_json::{json, Value};
+
+fn format_message(step: &str, env: Value) -> Value {
+    let content = read_file("CSML/basic_test/built-in/llm.csml".to_owned()).unwrap();
+    let flow = CsmlFlow::new("id", "flow", &content, Vec::default());
+
+    let bot = CsmlBot::new(
+        "id",
+        "bot",
+        None,
+        vec![flow],
+        Some(load_components().unwrap()),
+        None,
+        "flow",
+        None,
+        None,
+        Some(env),
+        None,
+        None,
+    );
+
+    let msg = interpret(
+        bot,
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            step,
+            "flow",
+            None,
+        ),
+        Event::new("payload", "", serde_json::json!({})),
+        None,
+    );
+
+    message_to_json_value(msg)
+}
+
+#[test]
+fn ok_llm_mock() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "Hello world"},"content_type":"text"},
+            {"content":{"text": "6"},"content_type":"text"},
+            {"content":{"text": "stop"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(format_message("start", json!({"LLM_PROVIDER": "mock"})), v2)
+}
+
+#[test]
+fn ok_llm_methods() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "Hi there"},"content_type":"text"},
+            {"content":{"text": "4"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(
+        format_message("methods", json!({"LLM_PROVIDER": "mock"})),
+        v2
+    )
+}
+
+#[test]
+fn ok_llm_json() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "Ada"},"content_type":"text"},
+            {"content":{"text": "36"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+    let env = json!({
+        "LLM_PROVIDER": "mock",
+        "LLM_MOCK_RESPONSE": r#"{"name": "Ada", "age": 36}"#
+    });
+
+    assert_eq!(format_message("json", env), v2)
+}
+
+#[test]
+fn ko_llm_invalid_json() {
+    let msg = format_message("invalid_json", json!({"LLM_PROVIDER": "mock"}));
+
+    let message = msg["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("JSON"));
+}
+
+#[test]
+fn ko_llm_bad_messages() {
+    let msg = format_message("bad_messages", json!({"LLM_PROVIDER": "mock"}));
+
+    let message = msg["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("role"));
+}
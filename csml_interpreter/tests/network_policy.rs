@@ -0,0 +1,128 @@
+mod support;
+
+use csml_interpreter::data::context::Context;
+use csml_interpreter::data::event::Event;
+use csml_interpreter::data::{CsmlBot, CsmlFlow, NetworkPolicy};
+use csml_interpreter::{interpret, load_components};
+use std::collections::HashMap;
+
+use crate::support::tools::message_to_json_value;
+use crate::support::tools::read_file;
+
+use serde_json::{json, Value};
+
+fn run_step(step: &str, policy: Value) -> String {
+    let content = read_file("CSML/basic_test/network_policy.csml".to_owned()).unwrap();
+    let flow = CsmlFlow::new("id", "flow", &content, Vec::default());
+
+    let mut bot = CsmlBot::new(
+        "id",
+        "bot",
+        None,
+        vec![flow],
+        Some(load_components().unwrap()),
+        None,
+        "flow",
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    bot.network_policy = Some(serde_json::from_value::<NetworkPolicy>(policy).unwrap());
+
+    let context = Context::new(
+        HashMap::new(),
+        HashMap::new(),
+        None,
+        None,
+        step,
+        "flow",
+        None,
+    );
+
+    let msg = message_to_json_value(interpret(
+        bot,
+        context,
+        Event::new("payload", "", json!({})),
+        None,
+    ));
+
+    msg["messages"][0]["content"]["error"]
+        .as_str()
+        .unwrap()
+        .to_owned()
+}
+
+#[test]
+fn ko_network_policy_private_network() {
+    let message = run_step("http_0", json!({"block_private_networks": true}));
+
+    assert!(message.contains("request blocked by the network policy"));
+    assert!(message.contains("address 127.0.0.1 of host '127.0.0.1' is in a private network"));
+}
+
+#[test]
+fn ko_network_policy_reserved_networks() {
+    let steps = [
+        ("http_4", "240.0.0.1"),
+        ("http_5", "224.0.0.1"),
+        ("http_6", "198.18.0.1"),
+        ("http_7", "ff02::1"),
+        // NAT64 of 169.254.169.254
+        ("http_8", "64:ff9b::a9fe:a9fe"),
+    ];
+
+    for (step, address) in steps.iter() {
+        let message = run_step(step, json!({"block_private_networks": true}));
+
+        assert!(message.contains("request blocked by the network policy"));
+        assert!(message.contains(&format!("address {} of host", address)));
+    }
+}
+
+#[test]
+fn ok_network_policy_private_network_allowed() {
+    let message = run_step(
+        "http_0",
+        json!({"block_private_networks": true, "http": {"allowed": ["127.0.0.0/8"]}}),
+    );
+
+    // the request is sent and fails as nothing listens on the port
+    assert!(!message.contains("network policy"));
+}
+
+#[test]
+fn ko_network_policy_denied_host() {
+    let message = run_step("http_1", json!({"http": {"denied": ["*.example.com"]}}));
+
+    assert!(message.contains("host 'metadata.internal.example.com' is denied"));
+}
+
+#[test]
+fn ko_network_policy_not_allowed_host() {
+    let message = run_step("http_2", json!({"http": {"allowed": ["api.example.com"]}}));
+
+    assert!(message.contains("host 'example.org' is not allowed"));
+}
+
+#[test]
+fn ko_network_policy_disable_ssl_verify() {
+    let message = run_step("http_3", json!({"forbid_disable_ssl_verify": true}));
+
+    assert!(message.contains("the network policy does not allow to disable the SSL verification"));
+}
+
+#[test]
+fn ko_network_policy_smtp_private_network() {
+    let message = run_step("smtp_0", json!({"block_private_networks": true}));
+
+    assert!(message.contains("address 10.0.0.5 of host '10.0.0.5' is in a private network"));
+}
+
+#[test]
+fn ko_network_policy_smtp_tls() {
+    let message = run_step("smtp_1", json!({"forbid_disable_ssl_verify": true}));
+
+    assert!(message.contains("the network policy does not allow SMTP servers without TLS"));
+}
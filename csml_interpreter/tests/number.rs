@@ -239,3 +239,141 @@ fn string_step_1() {
 
     assert_eq!(v1, v2)
 }
+
+#[test]
+fn locale_number() {
+    let data = r#"{
+        "memories":[
+        ],
+        "messages":[
+            {"content":{"text": "1,234,567"}, "content_type":"text"},
+            {"content":{"text": "1\u202f234,50"}, "content_type":"text"},
+            {"content":{"text": "1.234,5"}, "content_type":"text"},
+            {"content":{"text": "-9,876.54"}, "content_type":"text"},
+            {"content":{"text": "1,234.57"}, "content_type":"text"}
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "locale_number",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/number.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn locale_currency() {
+    let data = r#"{
+        "memories":[
+        ],
+        "messages":[
+            {"content":{"text": "$1,234.50"}, "content_type":"text"},
+            {"content":{"text": "1\u202f234,50\u00a0€"}, "content_type":"text"},
+            {"content":{"text": "¥1,235"}, "content_type":"text"},
+            {"content":{"text": "R$\u00a099,00"}, "content_type":"text"}
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "locale_currency",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/number.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn locale_time() {
+    let data = r#"{
+        "memories":[
+        ],
+        "messages":[
+            {"content":{"text": "vendredi 15 mars 2024"}, "content_type":"text"},
+            {"content":{"text": "Fr. 15 März"}, "content_type":"text"}
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "locale_time",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/number.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn locale_unknown() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "locale_unknown",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/number.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("unknown locale"));
+}
+
+#[test]
+fn locale_decimals_out_of_range() {
+    for step in ["locale_decimals_0", "locale_decimals_1"].iter() {
+        let msg = format_message(
+            Event::new("payload", "", serde_json::json!({})),
+            Context::new(
+                HashMap::new(),
+                HashMap::new(),
+                None,
+                None,
+                step,
+                "flow",
+                None,
+            ),
+            "CSML/basic_test/stdlib/number.csml",
+        );
+
+        let v1: Value = message_to_json_value(msg);
+        let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+        assert!(message.contains("up to 20 for a Float and 100 otherwise"));
+    }
+}
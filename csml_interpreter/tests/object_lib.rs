@@ -294,3 +294,201 @@ fn object_step_8() {
 
     assert_eq!(v1, v2)
 }
+
+#[test]
+fn ok_object_step9() {
+    let data = r#"{
+        "memories":[
+        ],
+        "messages":[
+            {"content":{"text":"Paris"}, "content_type":"text"},
+            {"content":{"text":"2"}, "content_type":"text"},
+            {"content":{"text":"none"}, "content_type":"text"},
+            {"content":{"text":null}, "content_type":"text"},
+            {"content":{"text":"Lyon"}, "content_type":"text"},
+            {"content":{"text":"3"}, "content_type":"text"},
+            {"content":{"lang":"fr"}, "content_type":"object"}
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "step_9",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/object.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ok_object_step10() {
+    let data = r#"{
+        "memories":[
+        ],
+        "messages":[
+            {"content":{"lang":"en", "notify": {"email": true, "sms": true}}, "content_type":"object"},
+            {"content":{"lang":"en"}, "content_type":"object"},
+            {"content":{"lang":"en"}, "content_type":"object"},
+            {"content":[["a", 1], ["b", 2]], "content_type":"array"},
+            {"content":{"a":1, "b":2}, "content_type":"object"}
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "step_10",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/object.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ok_object_step11() {
+    let data = r#"{
+        "memories":[
+        ],
+        "messages":[
+            {"content":{"apple":4, "pear":6, "melon":10}, "content_type":"object"},
+            {"content":{"pear":3}, "content_type":"object"}
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "step_11",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/object.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ko_object_bad_path() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "step_12",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/object.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("path is bad formatted"));
+}
+
+#[test]
+fn ok_object_query() {
+    let data = r#"{
+        "memories":[
+        ],
+        "messages":[
+            {"content":["Ada", "Grace"], "content_type":"array"},
+            {"content":{"name":"Ada", "age":36}, "content_type":"object"},
+            {"content":{"text":"3"}, "content_type":"text"},
+            {"content":{"text":null}, "content_type":"text"}
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "step_13",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/object.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ko_object_query_expression() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "step_14",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/object.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("invalid JMESPath expression"));
+}
+
+#[test]
+fn ok_object_closure_http() {
+    let data = r#"{
+        "memories":[
+        ],
+        "messages":[
+            {"content":{"url":"https://example.com"}, "content_type":"object"}
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "step_15",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/object.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
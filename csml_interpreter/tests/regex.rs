@@ -207,3 +207,116 @@ fn ok_regex_8() {
 
     assert_eq!(v1, v2)
 }
+
+#[test]
+fn ok_regex_captures() {
+    let data = r#"{
+        "memories":[],
+        "messages":[
+            {"content_type":"array", "content":[
+                {"index": 0, "name": null, "value": "2024-03", "start": 6, "end": 13, "char_start": 4, "char_end": 11},
+                {"index": 1, "name": "year", "value": "2024", "start": 6, "end": 10, "char_start": 4, "char_end": 8},
+                {"index": 2, "name": null, "value": "03", "start": 11, "end": 13, "char_start": 9, "char_end": 11},
+                null
+            ]},
+            {"content_type":"array", "content":[]}
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "regex_9",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/regex.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ok_regex_captures_all() {
+    let data = r#"{
+        "memories":[],
+        "messages":[
+            {"content_type":"array", "content":[
+                [{"index": 0, "name": null, "value": "1", "start": 1, "end": 2, "char_start": 1, "char_end": 2}],
+                [{"index": 0, "name": null, "value": "22", "start": 3, "end": 5, "char_start": 3, "char_end": 5}]
+            ]}
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "regex_10",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/regex.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ok_regex_replace() {
+    let data = r#"{
+        "memories":[],
+        "messages":[
+            {"content_type":"text", "content":{"text": "03/2024"}},
+            {"content_type":"text", "content":{"text": "a1b3"}}
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "regex_11",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/regex.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ko_regex_captures() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "regex_12",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/regex.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("[captures]"));
+}
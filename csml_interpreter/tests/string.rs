@@ -558,3 +558,118 @@ fn string_step_18_html_escape() {
 
     assert_eq!(v1, v2)
 }
+
+#[test]
+fn string_step_19_from_formats() {
+    let data = r#"{
+        "memories":[],
+        "messages":[
+            {"content_type":"object", "content":{"Item": {"name": "Banana", "qty": 3}}},
+            {"content_type":"object", "content":{"name": "Banana", "tags": ["fruit", "yellow"]}},
+            {"content_type":"array", "content":[{"name": "Banana", "qty": "3"}, {"name": "Apple; green", "qty": "5"}]},
+            {"content_type":"array", "content":[["a", "b"], ["c", "d"]]}
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "step_19_from_formats",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/string.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn string_step_20_to_csv() {
+    let data = r#"{
+        "memories":[],
+        "messages":[
+            {"content_type":"text", "content":{"text": "name,note,qty\nBanana,,3\n\"Apple, green\",\"say \"\"hi\"\"\",\n"}},
+            {"content_type":"text", "content":{"text": "qty;name\n3;Banana\n;Apple, green\n"}}
+        ]}"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "step_20_to_csv",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/string.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn string_step_21_from_xml_invalid() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "step_21_from_xml_invalid",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/string.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("[from_xml]"));
+}
+
+#[test]
+fn string_step_22_csv_rows() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "step_22_csv_rows",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/stdlib/string.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+
+    assert_eq!(
+        v1["messages"][0]["content"],
+        serde_json::json!([
+            {"name": "Banana", "qty": "3", "note": null},
+            {"name": "Apple", "qty": "5", "note": "green"}
+        ])
+    );
+    assert!(v1["messages"][1]["content"]["error"]
+        .as_str()
+        .unwrap()
+        .contains("row 1 has 3 fields, the header has 2 columns"));
+    assert_eq!(
+        v1["messages"][3]["content"]["text"],
+        "name,qty\n\"Banana\rSplit\",3\n"
+    );
+}
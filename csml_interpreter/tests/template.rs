@@ -0,0 +1,104 @@
+mod support;
+
+use csml_interpreter::data::context::Context;
+use csml_interpreter::data::csml_bot::CsmlBot;
+use csml_interpreter::data::csml_flow::CsmlFlow;
+use csml_interpreter::data::event::Event;
+use csml_interpreter::{interpret, load_components};
+use std::collections::HashMap;
+
+use crate::support::tools::message_to_json_value;
+use crate::support::tools::read_file;
+
+use serde_json::Value;
+
+fn init_files() -> HashMap<String, String> {
+    let mut files = HashMap::new();
+
+    files.insert(
+        "receipt.mustache".to_owned(),
+        "{{! receipt }}\nHello {{name}},\n{{#each items}}\n{{@index}}. {{label}}: {{price}}{{#unless @last}},{{/unless}}\n{{/each}}\n{{#if paid}}\nPaid\n{{else}}\nDue: {{> total.txt}}\n{{/if}}\n".to_owned(),
+    );
+    files.insert("total.txt".to_owned(), "8 EUR".to_owned());
+
+    files
+}
+
+fn format_message(step: &str) -> Value {
+    let content = read_file("CSML/basic_test/built-in/template.csml".to_owned()).unwrap();
+    let flow = CsmlFlow::new("id", "flow", &content, Vec::default());
+
+    let mut bot = CsmlBot::new(
+        "id",
+        "bot",
+        None,
+        vec![flow],
+        Some(load_components().unwrap()),
+        None,
+        "flow",
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    bot.files = Some(init_files());
+
+    let msg = interpret(
+        bot,
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            step,
+            "flow",
+            None,
+        ),
+        Event::new("payload", "", serde_json::json!({})),
+        None,
+    );
+
+    message_to_json_value(msg)
+}
+
+#[test]
+fn ok_template_file() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "Hello Jane!"},"content_type":"text"},
+            {"content":{"text": "Hello Jane,\n0. Tea: 3,\n1. Cake &amp; co: 5\nDue: 8 EUR\n"},"content_type":"text"},
+            {"content":{"text": "Hello Jane,\n0. Tea: 3,\n1. Cake & co: 5\nDue: 8 EUR\n"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(format_message("start"), v2)
+}
+
+#[test]
+fn ok_template_inline() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "a b none"},"content_type":"text"},
+            {"content":{"text": "&lt;b&gt; <b> <b>"},"content_type":"text"},
+            {"content":{"text": "Hi Jo"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(format_message("inline"), v2)
+}
+
+#[test]
+fn ko_template_unclosed_section() {
+    let msg = format_message("invalid");
+
+    let message = msg["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("unclosed section 'a'"));
+}
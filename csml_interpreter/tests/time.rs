@@ -151,3 +151,184 @@ fn ok_with_timezone() {
 
     assert_eq!(v1, v2)
 }
+
+#[test]
+fn ok_time_diff() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "43"},"content_type":"text"},
+            {"content":{"text": "1"},"content_type":"text"},
+            {"content":{"text": "-1054"},"content_type":"text"},
+            {"content":{"text": "3796200"},"content_type":"text"},
+            {"content":{"text": "true"},"content_type":"text"},
+            {"content":{"text": "false"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "time_diff",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/built-in/time.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ok_time_calendar() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "5"},"content_type":"text"},
+            {"content":{"text": "11"},"content_type":"text"},
+            {"content":{"text": "2024-03-11T00:00:00.000Z"},"content_type":"text"},
+            {"content":{"text": "2024-03-31T23:59:59.999Z"},"content_type":"text"},
+            {"content":{"text": "2024-03-16T00:00:00.000+01:00"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "time_calendar",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/built-in/time.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ok_time_duration() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "2024-02-29T08:00:00.000Z"},"content_type":"text"},
+            {"content":{"text": "2024-03-15T09:00:00.000Z"},"content_type":"text"},
+            {"content":{"text": "2024-03-22T09:00:00.000Z"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "time_duration",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/built-in/time.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ok_time_business_hours() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "true"},"content_type":"text"},
+            {"content":{"text": "false"},"content_type":"text"},
+            {"content":{"text": "true"},"content_type":"text"},
+            {"content":{"text": "false"},"content_type":"text"},
+            {"content":{"text": "false"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "time_business_hours",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/built-in/time.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(v1, v2)
+}
+
+#[test]
+fn ko_time_invalid_duration() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "time_invalid_duration",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/built-in/time.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+    let message = v1["messages"][0]["content"]["error"].as_str().unwrap();
+
+    assert!(message.contains("ISO 8601 duration"));
+}
+
+#[test]
+fn ko_time_duration_overflow() {
+    let msg = format_message(
+        Event::new("payload", "", serde_json::json!({})),
+        Context::new(
+            HashMap::new(),
+            HashMap::new(),
+            None,
+            None,
+            "time_duration_overflow",
+            "flow",
+            None,
+        ),
+        "CSML/basic_test/built-in/time.csml",
+    );
+
+    let v1: Value = message_to_json_value(msg);
+
+    assert!(v1["messages"][0]["content"]["error"]
+        .as_str()
+        .unwrap()
+        .contains("out of the range of supported dates"));
+    assert!(v1["messages"][2]["content"]["error"]
+        .as_str()
+        .unwrap()
+        .contains("ISO 8601 duration"));
+    assert!(v1["messages"][4]["content"]["error"]
+        .as_str()
+        .unwrap()
+        .contains("ISO 8601 duration"));
+}
@@ -0,0 +1,168 @@
+mod support;
+
+use csml_interpreter::data::context::Context;
+use csml_interpreter::data::csml_bot::CsmlBot;
+use csml_interpreter::data::csml_flow::CsmlFlow;
+use csml_interpreter::data::event::Event;
+use csml_interpreter::data::{primitive::PrimitiveString, Interval, Literal};
+use csml_interpreter::{interpret, load_components, validate_bot};
+use std::collections::HashMap;
+
+use crate::support::tools::message_to_json_value;
+use crate::support::tools::read_file;
+
+use serde_json::{json, Value};
+
+fn init_bot() -> CsmlBot {
+    let content = read_file("CSML/basic_test/built-in/translate.csml".to_owned()).unwrap();
+    let flow = CsmlFlow::new("id", "flow", &content, Vec::default());
+
+    let mut bot = CsmlBot::new(
+        "id",
+        "bot",
+        None,
+        vec![flow],
+        Some(load_components().unwrap()),
+        None,
+        "flow",
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+
+    let mut locales = HashMap::new();
+    locales.insert(
+        "en".to_owned(),
+        json!({
+            "greetings": {"hello": "Hello {{name}}!"},
+            "cart": {"items": {"zero": "Your cart is empty", "one": "{{count}} item", "other": "{{count}} items"}},
+            "only_default": "only in english"
+        }),
+    );
+    locales.insert(
+        "fr".to_owned(),
+        json!({
+            "greetings": {"hello": "Bonjour {{ name }} !"},
+            "cart": {"items": {"one": "{{count}} article", "other": "{{count}} articles"}}
+        }),
+    );
+
+    bot.locales = Some(locales);
+    bot.default_locale = Some("en".to_owned());
+
+    bot
+}
+
+fn format_message(
+    step: &str,
+    memories: HashMap<String, Literal>,
+    metadata: HashMap<String, Literal>,
+) -> Value {
+    let msg = interpret(
+        init_bot(),
+        Context::new(memories, metadata, None, None, step, "flow", None),
+        Event::new("payload", "", serde_json::json!({})),
+        None,
+    );
+
+    message_to_json_value(msg)
+}
+
+fn gen_locale(locale: &str) -> HashMap<String, Literal> {
+    let mut map = HashMap::new();
+    map.insert(
+        "locale".to_owned(),
+        PrimitiveString::get_literal(locale, Interval::default()),
+    );
+
+    map
+}
+
+#[test]
+fn ok_translate_default_locale() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "Hello Jane!"},"content_type":"text"},
+            {"content":{"text": "Your cart is empty"},"content_type":"text"},
+            {"content":{"text": "1 item"},"content_type":"text"},
+            {"content":{"text": "3 items"},"content_type":"text"},
+            {"content":{"text": "greetings.unknown"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(format_message("start", HashMap::new(), HashMap::new()), v2)
+}
+
+#[test]
+fn ok_translate_metadata_locale() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "Bonjour Jane !"},"content_type":"text"},
+            {"content":{"text": "1 article"},"content_type":"text"},
+            {"content":{"text": "only in english"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    // fr-CA falls back to the fr catalog, then to the default locale
+    assert_eq!(
+        format_message("locale", gen_locale("en"), gen_locale("fr-CA")),
+        v2
+    )
+}
+
+#[test]
+fn ok_translate_memory_locale() {
+    let data = r#"
+        {"messages":[
+            {"content":{"text": "Bonjour Jane !"},"content_type":"text"},
+            {"content":{"text": "1 article"},"content_type":"text"},
+            {"content":{"text": "only in english"},"content_type":"text"}
+        ],
+        "memories":[]
+        }"#;
+
+    let v2: Value = serde_json::from_str(data).unwrap();
+
+    assert_eq!(
+        format_message("locale", gen_locale("fr"), HashMap::new()),
+        v2
+    )
+}
+
+#[test]
+fn ok_translate_missing_keys_warnings() {
+    let result = validate_bot(&init_bot());
+    let mut warnings: Vec<(String, String)> = result
+        .warnings
+        .unwrap()
+        .into_iter()
+        .map(|warning| (warning.position.flow, warning.message))
+        .collect();
+    warnings.sort();
+
+    assert_eq!(
+        warnings,
+        vec![
+            (
+                "flow".to_owned(),
+                "translation key 'greetings.unknown' is missing in locale 'en'".to_owned()
+            ),
+            (
+                "flow".to_owned(),
+                "translation key 'greetings.unknown' is missing in locale 'fr'".to_owned()
+            ),
+            (
+                "flow".to_owned(),
+                "translation key 'only_default' is missing in locale 'fr'".to_owned()
+            ),
+        ]
+    )
+}